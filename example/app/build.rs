@@ -0,0 +1,6 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    triton_codegen::configure()
+        .repository("../../models")
+        .compile("models.rs")?;
+    Ok(())
+}
@@ -1,23 +1,26 @@
-mod request;
+mod models;
 
-use crate::request::ModelTensors;
+use crate::models::mnist;
 use anyhow::Result;
-use triton_client::{InferenceOutput, TritonClient};
+use triton_client::{InferenceClient, TritonClient};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let mut client = TritonClient::new("http://localhost:8001").await?;
+    let client = TritonClient::new("http://localhost:8001").await?;
+    run(&client).await
+}
+
+async fn run(client: &impl InferenceClient) -> Result<()> {
     let server_is_ready = client.server_ready().await?;
     println!("Server is ready: {}", server_is_ready);
 
-    let model_is_ready = client.model_ready("mnist", "1").await?;
+    let model_is_ready = client.model_ready(mnist::NAME, "1").await?;
     println!("Model is ready: {}", model_is_ready);
 
-    let response = client
-        .infer(ModelTensors::new("mnist", vec![0.123f32; 784]).into())
-        .await?;
-
-    let output = InferenceOutput::from_response(&response)?;
+    let input = mnist::Inputs {
+        input3: vec![0.123f32; 784],
+    };
+    let output: mnist::Outputs = client.infer_typed(mnist::NAME, "", &input).await?;
     println!("Output: {:?}", output);
 
     Ok(())
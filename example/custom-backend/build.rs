@@ -0,0 +1,6 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    triton_codegen::configure()
+        .config("../../models/mnist_onnx/config.pbtxt")
+        .compile("models.rs")?;
+    Ok(())
+}
@@ -1,9 +1,16 @@
 use triton_ng::backend::Backend;
-use triton_ng::{InferenceRequest, Response, sys};
+use triton_ng::{InferenceRequest, Response, TritonError};
+use triton_types::{TritonInputs, TritonOutputs};
+
+mod models {
+    include!(concat!(env!("OUT_DIR"), "/models.rs"));
+}
+
+use models::mnist_onnx;
 
 struct MnistBackend;
 
-const MODEL_NAME: &str = "mnist_onnx";
+const MODEL_NAME: &str = mnist_onnx::NAME;
 const MODEL_VERSION: i64 = 1;
 
 impl Backend for MnistBackend {
@@ -30,49 +37,47 @@ impl Backend for MnistBackend {
             server.model_metadata(MODEL_NAME, MODEL_VERSION)?
         );
 
-        for request in requests {
-            let input = request.get_input("Input3")?;
-            let properties = input.properties()?;
-            let input_values = input.as_fp32_vec()?;
-
-            println!("[MNIST] input values count: {}", input_values.len());
-
-            let mut inference_req = InferenceRequest::new(&server, MODEL_NAME, MODEL_VERSION)?;
-
-            inference_req.add_input(
-                "Input3",
-                sys::TRITONSERVER_datatype_enum_TRITONSERVER_TYPE_FP32,
-                &properties.shape,
-            )?;
-
-            let input_bytes: Vec<u8> = input_values.iter().flat_map(|&f| f.to_le_bytes()).collect();
+        let input_names: Vec<&str> = mnist_onnx::Inputs::INPUTS
+            .iter()
+            .map(|spec| spec.name)
+            .collect();
 
-            inference_req.append_input_data("Input3", &input_bytes)?;
-            inference_req.add_requested_output("Plus214_Output_0")?;
+        for request in requests {
+            // SAFETY: `inference_req` is dropped at the end of this iteration,
+            // after its blocking inference and before `request` is released.
+            let mut inference_req = unsafe {
+                InferenceRequest::from_backend_request(
+                    &server,
+                    request,
+                    MODEL_NAME,
+                    MODEL_VERSION,
+                    &input_names,
+                )?
+            };
+            for spec in mnist_onnx::Outputs::OUTPUTS {
+                inference_req.add_requested_output(spec.name)?;
+            }
 
             println!("[MNIST] Running inference...");
-            let inference_result = server.infer_async(&inference_req)?;
-
-            let output_tensor = &inference_result.outputs[0];
-            println!("[MNIST] Got output: {} bytes", output_tensor.data.len());
-
-            let predictions: Vec<f32> = output_tensor
-                .data
-                .chunks_exact(4)
-                .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
-                .collect();
-
-            println!("[MNIST] Predictions: {:?}", predictions);
+            let inference_result = server.infer_blocking(&inference_req)?;
+
+            let output_name = mnist_onnx::output_names::PLUS214_OUTPUT_0;
+            let output_tensor = inference_result
+                .outputs
+                .iter()
+                .find(|tensor| tensor.name == output_name)
+                .ok_or_else(|| {
+                    TritonError::from_message(format!("BLS response has no '{output_name}'"))
+                })?;
+            println!("[MNIST] Got output: {} bytes", output_tensor.byte_size());
+
+            let output = mnist_onnx::Outputs {
+                plus214_output_0: output_tensor.to_vec()?,
+            };
+            println!("[MNIST] Predictions: {:?}", output.plus214_output_0);
 
             let mut response = Response::new(request)?;
-            response
-                .create_output(
-                    "Plus214_Output_0",
-                    sys::TRITONSERVER_datatype_enum_TRITONSERVER_TYPE_FP32,
-                    &[10],
-                )?
-                .write_fp32_vec(&predictions)?;
-
+            response.write_typed_outputs(&output)?;
             response.send()?;
         }
 
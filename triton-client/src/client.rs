@@ -0,0 +1,304 @@
+use crate::config::GrpcClient;
+use crate::shm;
+use crate::{
+    ClientConfig, InferRequest, InferStream, InferenceClient, InferenceOutput, LogSettings,
+    ModelConfig, ModelIndex, ModelMetadata, ModelStatistics, RepositoryParameter, RetryPolicy,
+    ServerMetadata, SharedMemoryStatus, TraceSettings,
+};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use tonic::{Request, Response, Status};
+use triton_grpc_client::inference::model_repository_parameter::ParameterChoice;
+use triton_grpc_client::inference::{
+    LogSettingsRequest, ModelConfigRequest, ModelInferRequest, ModelInferResponse,
+    ModelMetadataRequest, ModelReadyRequest, ModelRepositoryParameter, ModelStatisticsRequest,
+    RepositoryIndexRequest, RepositoryModelLoadRequest, RepositoryModelUnloadRequest,
+    ServerLiveRequest, ServerMetadataRequest, ServerReadyRequest, SystemSharedMemoryStatusRequest,
+    SystemSharedMemoryUnregisterRequest, TraceSettingRequest,
+};
+use triton_types::{TritonInputs, TritonOutputs};
+#[cfg(unix)]
+use {
+    crate::{SharedMemory, SharedMemoryRegion},
+    triton_grpc_client::inference::SystemSharedMemoryRegisterRequest,
+};
+
+/// Sends a request through the generated client's `$rpc` method, retrying
+/// as the policy allows if `$retry` holds.
+macro_rules! rpc {
+    ($self:ident, $rpc:ident, $retry:expr, $request:expr) => {
+        $self
+            .call(
+                stringify!($rpc),
+                $retry,
+                $request,
+                |mut client, request| async move { client.$rpc(request).await },
+            )
+            .await?
+    };
+}
+
+#[derive(Clone)]
+pub struct TritonClient {
+    client: GrpcClient,
+    retry: RetryPolicy,
+}
+
+impl TritonClient {
+    /// Connects to `url` with default settings. See [`ClientConfig`] for
+    /// TLS, timeouts and the like.
+    pub async fn new(url: &str) -> Result<Self> {
+        Self::with_config(ClientConfig::new(url)).await
+    }
+
+    pub async fn with_config(config: ClientConfig) -> Result<Self> {
+        let retry = config.retry_policy();
+        let client = config.client().await?;
+        Ok(TritonClient { client, retry })
+    }
+
+    async fn call<Req, Res, F, Fut>(
+        &self,
+        name: &'static str,
+        retry: bool,
+        request: Req,
+        rpc: F,
+    ) -> Result<Res>
+    where
+        Req: Clone,
+        F: Fn(GrpcClient, Request<Req>) -> Fut,
+        Fut: Future<Output = Result<Response<Res>, Status>>,
+    {
+        let response = self
+            .retry
+            .run(name, retry, request, |request| {
+                rpc(self.client.clone(), request)
+            })
+            .await?;
+        Ok(response)
+    }
+
+    pub async fn server_live(&self) -> Result<bool> {
+        let request = ServerLiveRequest {};
+        let response = rpc!(self, server_live, true, request);
+        Ok(response.live)
+    }
+
+    pub async fn server_ready(&self) -> Result<bool> {
+        let request = ServerReadyRequest {};
+        let response = rpc!(self, server_ready, true, request);
+        Ok(response.ready)
+    }
+
+    pub async fn model_ready(&self, model_name: &str, model_version: &str) -> Result<bool> {
+        let request = ModelReadyRequest {
+            name: model_name.to_string(),
+            version: model_version.to_string(),
+        };
+        let response = rpc!(self, model_ready, true, request);
+        Ok(response.ready)
+    }
+
+    pub async fn server_metadata(&self) -> Result<ServerMetadata> {
+        let request = ServerMetadataRequest {};
+        let response = rpc!(self, server_metadata, true, request);
+        Ok(response.into())
+    }
+
+    /// An empty `model_version` selects the version the server's policy
+    /// picks.
+    pub async fn model_metadata(
+        &self,
+        model_name: &str,
+        model_version: &str,
+    ) -> Result<ModelMetadata> {
+        let request = ModelMetadataRequest {
+            name: model_name.to_string(),
+            version: model_version.to_string(),
+        };
+        let response = rpc!(self, model_metadata, true, request);
+        response
+            .try_into()
+            .with_context(|| format!("Invalid metadata for '{model_name}'"))
+    }
+
+    /// The model's configuration; [`ModelConfig::raw`] has every field.
+    pub async fn model_config(&self, model_name: &str, model_version: &str) -> Result<ModelConfig> {
+        let request = ModelConfigRequest {
+            name: model_name.to_string(),
+            version: model_version.to_string(),
+        };
+        let response = rpc!(self, model_config, true, request);
+        response
+            .config
+            .map(Into::into)
+            .ok_or_else(|| anyhow::anyhow!("Server returned no config for '{model_name}'"))
+    }
+
+    /// Statistics for one model version, or for every model if `model_name`
+    /// is empty.
+    pub async fn model_statistics(
+        &self,
+        model_name: &str,
+        model_version: &str,
+    ) -> Result<Vec<ModelStatistics>> {
+        let request = ModelStatisticsRequest {
+            name: model_name.to_string(),
+            version: model_version.to_string(),
+        };
+        let response = rpc!(self, model_statistics, true, request);
+        Ok(response.model_stats.into_iter().map(Into::into).collect())
+    }
+
+    /// Lists the models in the repository, or only those ready for inference
+    /// if `ready_only` is set.
+    pub async fn repository_index(&self, ready_only: bool) -> Result<Vec<ModelIndex>> {
+        let request = RepositoryIndexRequest {
+            repository_name: String::new(),
+            ready: ready_only,
+        };
+        let response = rpc!(self, repository_index, true, request);
+        Ok(response.models.into_iter().map(Into::into).collect())
+    }
+
+    /// Loads or reloads a model. Requires the server to run with
+    /// `--model-control-mode=explicit`.
+    pub async fn load_model(&self, model_name: &str) -> Result<()> {
+        self.load_model_with(model_name, HashMap::new()).await
+    }
+
+    /// Loads a model with load parameters, see [`RepositoryParameter`].
+    pub async fn load_model_with(
+        &self,
+        model_name: &str,
+        parameters: HashMap<String, RepositoryParameter>,
+    ) -> Result<()> {
+        let request = RepositoryModelLoadRequest {
+            repository_name: String::new(),
+            model_name: model_name.to_string(),
+            parameters: parameters
+                .into_iter()
+                .map(|(key, value)| (key, value.into()))
+                .collect(),
+        };
+        rpc!(self, repository_model_load, false, request);
+        Ok(())
+    }
+
+    /// Unloads a model, and with `unload_dependents` also the models an
+    /// ensemble is composed of.
+    pub async fn unload_model(&self, model_name: &str, unload_dependents: bool) -> Result<()> {
+        let parameter = ModelRepositoryParameter {
+            parameter_choice: Some(ParameterChoice::BoolParam(unload_dependents)),
+        };
+        let request = RepositoryModelUnloadRequest {
+            repository_name: String::new(),
+            model_name: model_name.to_string(),
+            parameters: HashMap::from([("unload_dependents".to_string(), parameter)]),
+        };
+        rpc!(self, repository_model_unload, false, request);
+        Ok(())
+    }
+
+    /// Global trace settings, or those of `model_name` if it is not empty.
+    pub async fn trace_settings(&self, model_name: &str) -> Result<TraceSettings> {
+        self.update_trace_settings(model_name, &TraceSettings::default())
+            .await
+    }
+
+    /// Changes the set fields of `settings` and returns the resulting
+    /// settings.
+    pub async fn update_trace_settings(
+        &self,
+        model_name: &str,
+        settings: &TraceSettings,
+    ) -> Result<TraceSettings> {
+        let request = TraceSettingRequest {
+            settings: settings.to_request(),
+            model_name: model_name.to_string(),
+        };
+        let response = rpc!(self, trace_setting, true, request);
+        TraceSettings::from_response(response.settings)
+    }
+
+    pub async fn log_settings(&self) -> Result<LogSettings> {
+        self.update_log_settings(&LogSettings::default()).await
+    }
+
+    /// Changes the set fields of `settings` and returns the resulting
+    /// settings.
+    pub async fn update_log_settings(&self, settings: &LogSettings) -> Result<LogSettings> {
+        let request = LogSettingsRequest {
+            settings: settings.to_request(),
+        };
+        let response = rpc!(self, log_settings, true, request);
+        LogSettings::from_response(response.settings)
+    }
+
+    /// Registers `memory` under `name`, which inputs and outputs then refer
+    /// to. The region is unregistered when the returned value is dropped.
+    #[cfg(unix)]
+    pub async fn register_system_shared_memory(
+        &self,
+        name: &str,
+        memory: SharedMemory,
+    ) -> Result<SharedMemoryRegion> {
+        let request = SystemSharedMemoryRegisterRequest {
+            name: name.to_string(),
+            key: memory.key().to_string(),
+            offset: 0,
+            byte_size: memory.len() as u64,
+        };
+        rpc!(self, system_shared_memory_register, false, request);
+        Ok(SharedMemoryRegion::new(name, memory, self.clone()))
+    }
+
+    /// Status of one registered region, or of all if `name` is empty.
+    pub async fn system_shared_memory_status(&self, name: &str) -> Result<Vec<SharedMemoryStatus>> {
+        let request = SystemSharedMemoryStatusRequest {
+            name: name.to_string(),
+        };
+        let response = rpc!(self, system_shared_memory_status, true, request);
+        Ok(response.regions.into_values().map(Into::into).collect())
+    }
+
+    /// Unregisters a region, or all regions if `name` is empty.
+    pub async fn unregister_system_shared_memory(&self, name: &str) -> Result<()> {
+        let request = SystemSharedMemoryUnregisterRequest {
+            name: name.to_string(),
+        };
+        rpc!(self, system_shared_memory_unregister, true, request);
+        Ok(())
+    }
+
+    pub async fn infer(&self, request: ModelInferRequest) -> Result<ModelInferResponse> {
+        let retry = self.retry.retries_inference();
+        let outputs = request.outputs.clone();
+        let mut response = rpc!(self, model_infer, retry, request);
+        shm::mark_outputs(&outputs, &mut response);
+        Ok(response)
+    }
+
+    /// Builds and sends `request`, decoding every output.
+    pub async fn infer_request(&self, request: InferRequest) -> Result<Vec<InferenceOutput>> {
+        let response = self.infer(request.build()?).await?;
+        InferenceOutput::from_response(&response)
+    }
+
+    /// Opens a bidirectional `ModelStreamInfer` stream, required by
+    /// decoupled models. Requests to any model can share one stream.
+    pub async fn stream(&self) -> Result<InferStream> {
+        InferStream::open(self.client.clone()).await
+    }
+
+    /// Runs inference with typed inputs and outputs, requesting exactly the
+    /// outputs declared by `O`. Same as [`InferenceClient::infer_typed`].
+    pub async fn infer_typed<I: TritonInputs, O: TritonOutputs>(
+        &self,
+        model_name: &str,
+        model_version: &str,
+        inputs: &I,
+    ) -> Result<O> {
+        InferenceClient::infer_typed(self, model_name, model_version, inputs).await
+    }
+}
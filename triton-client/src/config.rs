@@ -0,0 +1,253 @@
+use crate::RetryPolicy;
+use anyhow::{Result, anyhow};
+use std::time::Duration;
+use tonic::codec::CompressionEncoding;
+use tonic::metadata::{AsciiMetadataKey, AsciiMetadataValue};
+use tonic::service::Interceptor;
+use tonic::service::interceptor::InterceptedService;
+#[cfg(feature = "tls")]
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Request, Status};
+use triton_grpc_client::inference::grpc_inference_service_client::GrpcInferenceServiceClient;
+
+pub(crate) type GrpcClient = GrpcInferenceServiceClient<InterceptedService<Channel, Headers>>;
+
+/// Connection settings for [`TritonClient`]:
+///
+/// ```ignore
+/// let client = ClientConfig::new("https://triton:8001")
+///     .ca_certificate(std::fs::read("ca.pem")?)
+///     .timeout(Duration::from_secs(5))
+///     .max_message_size(64 << 20)
+///     .bearer_token(token)
+///     .connect()
+///     .await?;
+/// ```
+///
+/// Mistakes such as an invalid header are reported by
+/// [`ClientConfig::connect`].
+///
+/// [`TritonClient`]: crate::TritonClient
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    url: String,
+    #[cfg(feature = "tls")]
+    tls: Option<ClientTlsConfig>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    keepalive_interval: Option<Duration>,
+    keepalive_timeout: Option<Duration>,
+    keepalive_while_idle: bool,
+    max_decoding_message_size: Option<usize>,
+    max_encoding_message_size: Option<usize>,
+    gzip: bool,
+    headers: Headers,
+    lazy: bool,
+    retry: RetryPolicy,
+    error: Option<String>,
+}
+
+impl ClientConfig {
+    /// `url` is the gRPC endpoint, e.g. `http://localhost:8001`. Use `https`
+    /// for TLS.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            #[cfg(feature = "tls")]
+            tls: None,
+            connect_timeout: None,
+            timeout: None,
+            keepalive_interval: None,
+            keepalive_timeout: None,
+            keepalive_while_idle: false,
+            max_decoding_message_size: None,
+            max_encoding_message_size: None,
+            gzip: false,
+            headers: Headers::default(),
+            lazy: false,
+            retry: RetryPolicy::disabled(),
+            error: None,
+        }
+    }
+
+    /// Trusts `pem` in addition to the system's root certificates.
+    #[cfg(feature = "tls")]
+    pub fn ca_certificate(mut self, pem: impl AsRef<[u8]>) -> Self {
+        let tls = self.tls.take().unwrap_or_default();
+        self.tls = Some(tls.ca_certificate(Certificate::from_pem(pem)));
+        self
+    }
+
+    /// Authenticates with a client certificate and its private key.
+    #[cfg(feature = "tls")]
+    pub fn identity(mut self, cert_pem: impl AsRef<[u8]>, key_pem: impl AsRef<[u8]>) -> Self {
+        let tls = self.tls.take().unwrap_or_default();
+        self.tls = Some(tls.identity(Identity::from_pem(cert_pem, key_pem)));
+        self
+    }
+
+    /// Verifies the server certificate against `domain` instead of the host
+    /// of the URL.
+    #[cfg(feature = "tls")]
+    pub fn domain_name(mut self, domain: impl Into<String>) -> Self {
+        let tls = self.tls.take().unwrap_or_default();
+        self.tls = Some(tls.domain_name(domain));
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Deadline for every request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends HTTP/2 pings every `interval` and drops the connection if one
+    /// is not answered within `timeout`.
+    pub fn keepalive(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.keepalive_interval = Some(interval);
+        self.keepalive_timeout = Some(timeout);
+        self
+    }
+
+    /// Keeps pinging while no request is in flight.
+    pub fn keepalive_while_idle(mut self, enabled: bool) -> Self {
+        self.keepalive_while_idle = enabled;
+        self
+    }
+
+    /// Limits the size of messages in both directions. tonic defaults to
+    /// 4 MiB for received messages, which large tensors easily exceed.
+    pub fn max_message_size(self, limit: usize) -> Self {
+        self.max_decoding_message_size(limit)
+            .max_encoding_message_size(limit)
+    }
+
+    pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+        self.max_decoding_message_size = Some(limit);
+        self
+    }
+
+    pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+        self.max_encoding_message_size = Some(limit);
+        self
+    }
+
+    /// Compresses requests with gzip and accepts gzip responses.
+    pub fn gzip(mut self, enabled: bool) -> Self {
+        self.gzip = enabled;
+        self
+    }
+
+    /// Adds a metadata header to every request.
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        match (key.parse(), value.parse()) {
+            (Ok(key), Ok(value)) => self.headers.0.push((key, value)),
+            _ => {
+                self.error
+                    .get_or_insert_with(|| format!("Invalid metadata header '{key}'"));
+            }
+        }
+        self
+    }
+
+    /// Sends `authorization: Bearer <token>` with every request.
+    pub fn bearer_token(self, token: &str) -> Self {
+        let (key, value) = bearer_header(token);
+        self.header(key, &value)
+    }
+
+    /// Connects on the first request instead of in [`ClientConfig::connect`].
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Retries failed RPCs, see [`RetryPolicy`]. Off by default.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    pub async fn connect(self) -> Result<crate::TritonClient> {
+        crate::TritonClient::with_config(self).await
+    }
+
+    pub(crate) fn retry_policy(&self) -> RetryPolicy {
+        self.retry.clone()
+    }
+
+    pub(crate) async fn client(self) -> Result<GrpcClient> {
+        if let Some(error) = self.error {
+            return Err(anyhow!(error));
+        }
+
+        #[cfg(feature = "tls")]
+        let tls = self
+            .tls
+            .or_else(|| self.url.starts_with("https://").then(ClientTlsConfig::new));
+
+        let mut endpoint = Endpoint::from_shared(self.url)?;
+        #[cfg(feature = "tls")]
+        if let Some(tls) = tls {
+            endpoint = endpoint.tls_config(tls.with_enabled_roots())?;
+        }
+        if let Some(timeout) = self.connect_timeout {
+            endpoint = endpoint.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            endpoint = endpoint.timeout(timeout);
+        }
+        if let Some(interval) = self.keepalive_interval {
+            endpoint = endpoint.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = self.keepalive_timeout {
+            endpoint = endpoint.keep_alive_timeout(timeout);
+        }
+        endpoint = endpoint.keep_alive_while_idle(self.keepalive_while_idle);
+
+        let channel = if self.lazy {
+            endpoint.connect_lazy()
+        } else {
+            endpoint.connect().await?
+        };
+
+        let mut client = GrpcInferenceServiceClient::with_interceptor(channel, self.headers);
+        if let Some(limit) = self.max_decoding_message_size {
+            client = client.max_decoding_message_size(limit);
+        }
+        if let Some(limit) = self.max_encoding_message_size {
+            client = client.max_encoding_message_size(limit);
+        }
+        if self.gzip {
+            client = client
+                .send_compressed(CompressionEncoding::Gzip)
+                .accept_compressed(CompressionEncoding::Gzip);
+        }
+
+        Ok(client)
+    }
+}
+
+/// The header both transports send for a bearer token.
+pub(crate) fn bearer_header(token: &str) -> (&'static str, String) {
+    ("authorization", format!("Bearer {token}"))
+}
+
+/// Metadata added to every request.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Headers(Vec<(AsciiMetadataKey, AsciiMetadataValue)>);
+
+impl Interceptor for Headers {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        for (key, value) in &self.0 {
+            request.metadata_mut().insert(key.clone(), value.clone());
+        }
+        Ok(request)
+    }
+}
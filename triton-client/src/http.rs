@@ -0,0 +1,862 @@
+use crate::config::bearer_header;
+use crate::output::encode_contents;
+use crate::shm::{self, SHARED_MEMORY_REGION};
+use crate::{ModelConfig, ModelMetadata, OutputData, ServerMetadata};
+use anyhow::{Context, Result, anyhow, bail};
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::header::{CONTENT_LENGTH, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use hyper::{Method, Request, StatusCode};
+use hyper_util::client::legacy::Client;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::rt::TokioExecutor;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::time::Duration;
+use triton_grpc_client::inference::infer_parameter::ParameterChoice;
+use triton_grpc_client::inference::model_infer_request::InferInputTensor;
+use triton_grpc_client::inference::model_infer_response::InferOutputTensor;
+use triton_grpc_client::inference::model_metadata_response::TensorMetadata as TensorMetadataProto;
+use triton_grpc_client::inference::{
+    InferParameter, ModelInferRequest, ModelInferResponse, ModelMetadataResponse,
+    ServerMetadataResponse,
+};
+use triton_types::{DataType, element_count};
+
+/// Length of the JSON header of a body that continues with binary tensors.
+const HEADER_LENGTH: &str = "inference-header-content-length";
+
+/// A client for Triton's HTTP/REST endpoint, usually on port 8000, speaking
+/// the KServe v2 protocol. It takes and returns the same types as
+/// [`TritonClient`]. Tensors travel in binary unless
+/// [`HttpClient::binary_data`] is turned off. [`InferRequest`]s and typed
+/// inference go through [`InferenceClient`].
+///
+/// Only plain `http` URLs are supported: there is no TLS. Reach a server
+/// behind `https` through a TLS-terminating proxy, or use [`TritonClient`]
+/// with the `tls` feature.
+///
+/// [`TritonClient`]: crate::TritonClient
+/// [`InferRequest`]: crate::InferRequest
+/// [`InferenceClient`]: crate::InferenceClient
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client<HttpConnector, Full<Bytes>>,
+    url: String,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+    binary_data: bool,
+}
+
+impl HttpClient {
+    /// `url` is the server's base URL, e.g. `http://localhost:8000`.
+    /// Connections are opened on demand. `https` URLs are rejected.
+    pub fn new(url: &str) -> Result<Self> {
+        if !url.starts_with("http://") {
+            bail!("Unsupported URL '{url}', expected http://host:port");
+        }
+
+        Ok(Self {
+            client: Client::builder(TokioExecutor::new()).build_http(),
+            url: url.trim_end_matches('/').to_string(),
+            headers: HeaderMap::new(),
+            timeout: None,
+            binary_data: true,
+        })
+    }
+
+    /// Adds a header to every request.
+    pub fn header(mut self, key: &str, value: &str) -> Result<Self> {
+        let key = HeaderName::try_from(key).with_context(|| format!("Invalid header '{key}'"))?;
+        let value = HeaderValue::try_from(value)
+            .with_context(|| format!("Invalid value for header '{key}'"))?;
+        self.headers.insert(key, value);
+        Ok(self)
+    }
+
+    /// Sends `authorization: Bearer <token>` with every request.
+    pub fn bearer_token(self, token: &str) -> Result<Self> {
+        let (key, value) = bearer_header(token);
+        self.header(key, &value)
+    }
+
+    /// Deadline for every request, including reading the response.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends tensors as binary data after the JSON header, the default, or
+    /// with `false` as JSON arrays in the header itself. JSON is larger and
+    /// slower but readable, e.g. for servers or proxies that only accept
+    /// JSON. FP16 and BF16 tensors can only be sent in binary.
+    pub fn binary_data(mut self, binary: bool) -> Self {
+        self.binary_data = binary;
+        self
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<(Vec<u8>, usize)>,
+    ) -> Result<(StatusCode, HeaderMap, Bytes)> {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(format!("{}{path}", self.url));
+        for (key, value) in &self.headers {
+            request = request.header(key, value);
+        }
+
+        let body = match body {
+            Some((body, header_length)) => {
+                request = if header_length < body.len() {
+                    request
+                        .header(CONTENT_TYPE, "application/octet-stream")
+                        .header(HEADER_LENGTH, header_length)
+                } else {
+                    request.header(CONTENT_TYPE, "application/json")
+                };
+                request = request.header(CONTENT_LENGTH, body.len());
+                Full::new(Bytes::from(body))
+            }
+            None => Full::default(),
+        };
+        let request = request.body(body)?;
+
+        let exchange = async {
+            let response = self.client.request(request).await?;
+            let (parts, body) = response.into_parts();
+            let body = body.collect().await?.to_bytes();
+            anyhow::Ok((parts.status, parts.headers, body))
+        };
+
+        match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, exchange)
+                .await
+                .map_err(|_| anyhow!("Request to {path} timed out"))?,
+            None => exchange.await,
+        }
+    }
+
+    /// Sends a request whose response is JSON, failing on error statuses.
+    async fn json(&self, path: &str) -> Result<Value> {
+        let (status, _, body) = self.send(Method::GET, path, None).await?;
+        check(status, &body)?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Sends a health request, which answers with an empty body and a
+    /// status of 200 if healthy.
+    async fn health(&self, path: &str) -> Result<bool> {
+        let (status, _, _) = self.send(Method::GET, path, None).await?;
+        Ok(status == StatusCode::OK)
+    }
+
+    pub async fn server_live(&self) -> Result<bool> {
+        self.health("/v2/health/live").await
+    }
+
+    pub async fn server_ready(&self) -> Result<bool> {
+        self.health("/v2/health/ready").await
+    }
+
+    pub async fn model_ready(&self, model_name: &str, model_version: &str) -> Result<bool> {
+        let path = model_path(model_name, model_version, "/ready");
+        self.health(&path).await
+    }
+
+    pub async fn server_metadata(&self) -> Result<ServerMetadata> {
+        let metadata: ServerMetadataJson = serde_json::from_value(self.json("/v2").await?)?;
+
+        Ok(ServerMetadataResponse {
+            name: metadata.name,
+            version: metadata.version,
+            extensions: metadata.extensions,
+        }
+        .into())
+    }
+
+    /// An empty `model_version` selects the version the server's policy
+    /// picks.
+    pub async fn model_metadata(
+        &self,
+        model_name: &str,
+        model_version: &str,
+    ) -> Result<ModelMetadata> {
+        let path = model_path(model_name, model_version, "");
+        let metadata: ModelMetadataJson = serde_json::from_value(self.json(&path).await?)?;
+
+        ModelMetadataResponse {
+            name: metadata.name,
+            versions: metadata.versions,
+            platform: metadata.platform,
+            inputs: metadata.inputs.into_iter().map(Into::into).collect(),
+            outputs: metadata.outputs.into_iter().map(Into::into).collect(),
+        }
+        .try_into()
+        .with_context(|| format!("Invalid metadata for '{model_name}'"))
+    }
+
+    /// See [`TritonClient::model_config`](crate::TritonClient::model_config).
+    pub async fn model_config(&self, model_name: &str, model_version: &str) -> Result<ModelConfig> {
+        let path = model_path(model_name, model_version, "/config");
+        let config = self.json(&path).await?;
+        triton_model_config::from_json(&config)
+            .map(Into::into)
+            .with_context(|| format!("Invalid config for '{model_name}'"))
+    }
+
+    pub async fn infer(&self, request: ModelInferRequest) -> Result<ModelInferResponse> {
+        let path = model_path(&request.model_name, &request.model_version, "/infer");
+        let body = encode_request(&request, self.binary_data)?;
+
+        let (status, headers, body) = self.send(Method::POST, &path, Some(body)).await?;
+        check(status, &body)?;
+        let mut response = decode_response(&headers, &body)?;
+        shm::mark_outputs(&request.outputs, &mut response);
+        Ok(response)
+    }
+}
+
+/// Characters left as they are in a path segment, as in RFC 3986.
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+fn model_path(model_name: &str, model_version: &str, suffix: &str) -> String {
+    let model_name = utf8_percent_encode(model_name, SEGMENT);
+    if model_version.is_empty() {
+        format!("/v2/models/{model_name}{suffix}")
+    } else {
+        let model_version = utf8_percent_encode(model_version, SEGMENT);
+        format!("/v2/models/{model_name}/versions/{model_version}{suffix}")
+    }
+}
+
+/// Turns an error status into the server's `{"error": "..."}` message.
+fn check(status: StatusCode, body: &[u8]) -> Result<()> {
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let message = serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|body| body.get("error")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| String::from_utf8_lossy(body).into_owned());
+    bail!("Server returned {status}: {message}")
+}
+
+#[derive(Deserialize)]
+struct ServerMetadataJson {
+    name: String,
+    version: String,
+    #[serde(default)]
+    extensions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TensorMetadataJson {
+    name: String,
+    datatype: String,
+    shape: Vec<i64>,
+}
+
+impl From<TensorMetadataJson> for TensorMetadataProto {
+    fn from(tensor: TensorMetadataJson) -> Self {
+        Self {
+            name: tensor.name,
+            datatype: tensor.datatype,
+            shape: tensor.shape,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ModelMetadataJson {
+    name: String,
+    #[serde(default)]
+    versions: Vec<String>,
+    #[serde(default)]
+    platform: String,
+    #[serde(default)]
+    inputs: Vec<TensorMetadataJson>,
+    #[serde(default)]
+    outputs: Vec<TensorMetadataJson>,
+}
+
+#[derive(Deserialize)]
+struct InferResponseJson {
+    model_name: String,
+    #[serde(default)]
+    model_version: String,
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    parameters: Map<String, Value>,
+    #[serde(default)]
+    outputs: Vec<OutputJson>,
+}
+
+#[derive(Deserialize)]
+struct OutputJson {
+    name: String,
+    datatype: String,
+    shape: Vec<i64>,
+    #[serde(default)]
+    parameters: Map<String, Value>,
+    data: Option<Value>,
+}
+
+/// Encodes the JSON header, followed by the data of every input if `binary`
+/// is set, and returns the body with the length of its header.
+fn encode_request(request: &ModelInferRequest, binary_data: bool) -> Result<(Vec<u8>, usize)> {
+    let mut binary = Vec::new();
+
+    let mut inputs = Vec::with_capacity(request.inputs.len());
+    let mut raw_index = 0;
+    for input in &request.inputs {
+        let mut parameters = to_json(&input.parameters);
+        let mut data = None;
+        // Inputs in shared memory have no entry in `raw_input_contents`.
+        if !input.parameters.contains_key(SHARED_MEMORY_REGION) {
+            let bytes = input_bytes(request, input, raw_index)?;
+            if binary_data {
+                parameters.insert("binary_data_size".to_string(), bytes.len().into());
+                binary.extend_from_slice(&bytes);
+            } else {
+                data = Some(
+                    decode_json_data(input, &bytes)
+                        .with_context(|| format!("Invalid data for input '{}'", input.name))?,
+                );
+            }
+            raw_index += 1;
+        }
+
+        let mut tensor = json!({
+            "name": input.name,
+            "datatype": input.datatype,
+            "shape": input.shape,
+            "parameters": parameters,
+        });
+        if let Some(data) = data {
+            tensor["data"] = data;
+        }
+        inputs.push(tensor);
+    }
+
+    let outputs: Vec<Value> = request
+        .outputs
+        .iter()
+        .map(|output| {
+            let mut parameters = to_json(&output.parameters);
+            if !output.parameters.contains_key(SHARED_MEMORY_REGION) {
+                parameters
+                    .entry("binary_data")
+                    .or_insert(Value::Bool(binary_data));
+            }
+            json!({ "name": output.name, "parameters": parameters })
+        })
+        .collect();
+
+    let mut parameters = to_json(&request.parameters);
+    if outputs.is_empty() && binary_data {
+        parameters.insert("binary_data_output".to_string(), Value::Bool(true));
+    }
+
+    let mut header = json!({ "inputs": inputs, "parameters": parameters });
+    if !request.id.is_empty() {
+        header["id"] = request.id.clone().into();
+    }
+    if !outputs.is_empty() {
+        header["outputs"] = outputs.into();
+    }
+
+    let mut body = serde_json::to_vec(&header)?;
+    let header_length = body.len();
+    body.extend_from_slice(&binary);
+    Ok((body, header_length))
+}
+
+fn input_bytes<'a>(
+    request: &'a ModelInferRequest,
+    input: &InferInputTensor,
+    raw_index: usize,
+) -> Result<Cow<'a, [u8]>> {
+    if let Some(data) = request.raw_input_contents.get(raw_index) {
+        return Ok(Cow::Borrowed(data));
+    }
+
+    let datatype = input.datatype.parse::<DataType>()?;
+    let contents = input.contents.clone().unwrap_or_default();
+    let bytes = encode_contents(datatype, contents)
+        .with_context(|| format!("Invalid contents for input '{}'", input.name))?;
+    Ok(Cow::Owned(bytes))
+}
+
+fn decode_response(headers: &HeaderMap, body: &[u8]) -> Result<ModelInferResponse> {
+    let header_length = match headers.get(HEADER_LENGTH) {
+        Some(length) => length.to_str()?.parse::<usize>()?,
+        None => body.len(),
+    };
+    if header_length > body.len() {
+        bail!(
+            "Response header of {header_length} bytes exceeds body of {}",
+            body.len()
+        );
+    }
+    let (header, mut binary) = body.split_at(header_length);
+    let header: InferResponseJson = serde_json::from_slice(header)?;
+
+    let mut response = ModelInferResponse {
+        model_name: header.model_name,
+        model_version: header.model_version,
+        id: header.id,
+        parameters: from_json(header.parameters),
+        ..Default::default()
+    };
+
+    for output in header.outputs {
+        let datatype = output.datatype.parse::<DataType>()?;
+        let data = match output.parameters.get("binary_data_size") {
+            Some(size) => {
+                let size = size
+                    .as_u64()
+                    .and_then(|size| usize::try_from(size).ok())
+                    .ok_or_else(|| anyhow!("Invalid binary size of output '{}'", output.name))?;
+                if size > binary.len() {
+                    bail!("Binary data of output '{}' is truncated", output.name);
+                }
+                let (data, rest) = binary.split_at(size);
+                binary = rest;
+                data.to_vec()
+            }
+            None => {
+                let data = output.data.unwrap_or(Value::Array(vec![]));
+                encode_json_data(datatype, &data)
+                    .with_context(|| format!("Invalid data for output '{}'", output.name))?
+            }
+        };
+
+        response.outputs.push(InferOutputTensor {
+            name: output.name,
+            datatype: output.datatype,
+            shape: output.shape,
+            parameters: from_json(output.parameters),
+            contents: None,
+        });
+        response.raw_output_contents.push(data);
+    }
+
+    Ok(response)
+}
+
+fn to_json(parameters: &HashMap<String, InferParameter>) -> Map<String, Value> {
+    parameters
+        .iter()
+        .filter_map(|(key, parameter)| {
+            let value = match parameter.parameter_choice.clone()? {
+                ParameterChoice::BoolParam(value) => value.into(),
+                ParameterChoice::Int64Param(value) => value.into(),
+                ParameterChoice::Uint64Param(value) => value.into(),
+                ParameterChoice::DoubleParam(value) => value.into(),
+                ParameterChoice::StringParam(value) => value.into(),
+            };
+            Some((key.clone(), value))
+        })
+        .collect()
+}
+
+fn from_json(parameters: Map<String, Value>) -> HashMap<String, InferParameter> {
+    parameters
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let choice = match value {
+                Value::Bool(value) => ParameterChoice::BoolParam(value),
+                Value::Number(number) => {
+                    if let Some(value) = number.as_i64() {
+                        ParameterChoice::Int64Param(value)
+                    } else if let Some(value) = number.as_u64() {
+                        ParameterChoice::Uint64Param(value)
+                    } else {
+                        ParameterChoice::DoubleParam(number.as_f64()?)
+                    }
+                }
+                Value::String(value) => ParameterChoice::StringParam(value),
+                _ => return None,
+            };
+            Some((
+                key,
+                InferParameter {
+                    parameter_choice: Some(choice),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Converts the raw little-endian data of an input to a flat JSON array, the
+/// inverse of [`encode_json_data`].
+fn decode_json_data(input: &InferInputTensor, bytes: &[u8]) -> Result<Value> {
+    fn floats<T: Copy + Into<f64>>(values: &[T]) -> Result<Value> {
+        values
+            .iter()
+            .map(|&value| {
+                let value = value.into();
+                if !value.is_finite() {
+                    bail!("{value} cannot be sent as JSON");
+                }
+                Ok(Value::from(value))
+            })
+            .collect()
+    }
+
+    let datatype = input.datatype.parse::<DataType>()?;
+    let count = element_count(&input.shape)
+        .ok_or_else(|| anyhow!("Invalid input shape {:?}", input.shape))?;
+
+    Ok(match OutputData::decode(datatype, bytes, count)? {
+        OutputData::BOOL(values) => values.into(),
+        OutputData::UINT8(values) => values.into(),
+        OutputData::UINT16(values) => values.into(),
+        OutputData::UINT32(values) => values.into(),
+        OutputData::UINT64(values) => values.into(),
+        OutputData::INT8(values) => values.into(),
+        OutputData::INT16(values) => values.into(),
+        OutputData::INT32(values) => values.into(),
+        OutputData::INT64(values) => values.into(),
+        OutputData::FP32(values) => floats(&values)?,
+        OutputData::FP64(values) => floats(&values)?,
+        OutputData::Bytes(values) => values
+            .into_iter()
+            .map(|value| {
+                String::from_utf8(value)
+                    .map(Value::from)
+                    .map_err(|_| anyhow!("BYTES elements must be UTF-8 to be sent as JSON"))
+            })
+            .collect::<Result<_>>()?,
+        _ => bail!("{datatype} cannot be sent as JSON"),
+    })
+}
+
+/// Converts the `data` of a JSON tensor, flat or nested in row-major order,
+/// to raw little-endian bytes.
+fn encode_json_data(datatype: DataType, data: &Value) -> Result<Vec<u8>> {
+    fn flatten<'a>(value: &'a Value, elements: &mut Vec<&'a Value>) {
+        match value {
+            Value::Array(values) => values.iter().for_each(|value| flatten(value, elements)),
+            value => elements.push(value),
+        }
+    }
+
+    fn integers<T: TryFrom<i128>, const N: usize>(
+        elements: &[&Value],
+        to_le: fn(T) -> [u8; N],
+    ) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(elements.len() * N);
+        for element in elements {
+            let value = element
+                .as_i64()
+                .map(i128::from)
+                .or_else(|| element.as_u64().map(i128::from))
+                .ok_or_else(|| anyhow!("Expected integer, found {element}"))?;
+            let value = T::try_from(value)
+                .map_err(|_| anyhow!("{value} does not fit in {}", std::any::type_name::<T>()))?;
+            bytes.extend_from_slice(&to_le(value));
+        }
+        Ok(bytes)
+    }
+
+    fn floats<const N: usize>(elements: &[&Value], to_le: fn(f64) -> [u8; N]) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(elements.len() * N);
+        for element in elements {
+            let value = element
+                .as_f64()
+                .ok_or_else(|| anyhow!("Expected number, found {element}"))?;
+            bytes.extend_from_slice(&to_le(value));
+        }
+        Ok(bytes)
+    }
+
+    let mut elements = Vec::new();
+    flatten(data, &mut elements);
+
+    match datatype {
+        DataType::Bool => elements
+            .iter()
+            .map(|element| {
+                element
+                    .as_bool()
+                    .map(u8::from)
+                    .ok_or_else(|| anyhow!("Expected boolean, found {element}"))
+            })
+            .collect(),
+        DataType::Uint8 => integers(&elements, u8::to_le_bytes),
+        DataType::Uint16 => integers(&elements, u16::to_le_bytes),
+        DataType::Uint32 => integers(&elements, u32::to_le_bytes),
+        DataType::Uint64 => integers(&elements, u64::to_le_bytes),
+        DataType::Int8 => integers(&elements, i8::to_le_bytes),
+        DataType::Int16 => integers(&elements, i16::to_le_bytes),
+        DataType::Int32 => integers(&elements, i32::to_le_bytes),
+        DataType::Int64 => integers(&elements, i64::to_le_bytes),
+        DataType::Fp32 => floats(&elements, |value| (value as f32).to_le_bytes()),
+        DataType::Fp64 => floats(&elements, f64::to_le_bytes),
+        DataType::Bytes => {
+            let mut bytes = Vec::new();
+            for element in elements {
+                let value = element
+                    .as_str()
+                    .ok_or_else(|| anyhow!("Expected string, found {element}"))?;
+                bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(value.as_bytes());
+            }
+            Ok(bytes)
+        }
+        DataType::Fp16 | DataType::Bf16 | DataType::Invalid => {
+            bail!("{datatype} cannot be sent as JSON")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InferRequest, InferenceOutput};
+    use triton_grpc_client::inference::InferTensorContents;
+
+    /// Splits an encoded body at its header length.
+    fn split(body: &(Vec<u8>, usize)) -> (Value, &[u8]) {
+        let (body, header_length) = body;
+        let (header, binary) = body.split_at(*header_length);
+        (serde_json::from_slice(header).unwrap(), binary)
+    }
+
+    fn response(header: Value, binary: &[u8]) -> (HeaderMap, Vec<u8>) {
+        let mut body = serde_json::to_vec(&header).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(HEADER_LENGTH, body.len().into());
+        body.extend_from_slice(binary);
+        (headers, body)
+    }
+
+    #[test]
+    fn encode_binary_inputs() {
+        let mut request = InferRequest::new("add")
+            .id("1")
+            .input("a", &[2], &vec![1i32, 2])
+            .shm_input("b", DataType::Int32, &[2], "io", 8, 8)
+            .output("sum")
+            .shm_output("carry", "io", 16, 8)
+            .build()
+            .unwrap();
+        request.inputs.push(InferInputTensor {
+            name: "c".to_string(),
+            datatype: "INT32".to_string(),
+            shape: vec![1],
+            contents: Some(InferTensorContents {
+                int_contents: vec![3],
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        request.raw_input_contents.truncate(1);
+
+        let body = encode_request(&request, true).unwrap();
+        let (header, binary) = split(&body);
+        assert_eq!(header["id"], "1");
+        assert_eq!(header["inputs"][0]["parameters"]["binary_data_size"], 8);
+        assert_eq!(
+            header["inputs"][1]["parameters"]["shared_memory_region"],
+            "io"
+        );
+        assert!(header["inputs"][1]["parameters"]["binary_data_size"].is_null());
+        assert_eq!(header["inputs"][2]["parameters"]["binary_data_size"], 4);
+        assert_eq!(header["outputs"][0]["parameters"]["binary_data"], true);
+        assert!(header["outputs"][1]["parameters"]["binary_data"].is_null());
+        assert_eq!(binary, [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn encode_without_outputs() {
+        let request = InferRequest::new("add").build().unwrap();
+        let body = encode_request(&request, true).unwrap();
+        let (header, binary) = split(&body);
+        assert_eq!(header["parameters"]["binary_data_output"], true);
+        assert!(header.get("outputs").is_none());
+        assert!(binary.is_empty());
+    }
+
+    #[test]
+    fn encode_json_inputs() {
+        let request = InferRequest::new("add")
+            .input("a", &[2, 2], &vec![1i32, -2, 3, 4])
+            .input("b", &[2], &vec![0.5f64, 1.5])
+            .input("c", &[1], &vec!["text".to_string()])
+            .input("d", &[2], &vec![true, false])
+            .shm_input("e", DataType::Int32, &[2], "io", 8, 8)
+            .output("sum")
+            .build()
+            .unwrap();
+
+        let (body, header_length) = encode_request(&request, false).unwrap();
+        assert_eq!(header_length, body.len());
+        let header: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(header["inputs"][0]["data"], json!([1, -2, 3, 4]));
+        assert_eq!(header["inputs"][0]["shape"], json!([2, 2]));
+        assert_eq!(header["inputs"][1]["data"], json!([0.5, 1.5]));
+        assert_eq!(header["inputs"][2]["data"], json!(["text"]));
+        assert_eq!(header["inputs"][3]["data"], json!([true, false]));
+        assert!(header["inputs"][4].get("data").is_none());
+        for input in header["inputs"].as_array().unwrap() {
+            assert!(input["parameters"].get("binary_data_size").is_none());
+        }
+        assert_eq!(header["outputs"][0]["parameters"]["binary_data"], false);
+
+        // The server's decoding of the JSON data gives back the raw bytes.
+        let bytes = encode_json_data(DataType::Int32, &header["inputs"][0]["data"]).unwrap();
+        assert_eq!(bytes, request.raw_input_contents[0]);
+        let bytes = encode_json_data(DataType::Bytes, &header["inputs"][2]["data"]).unwrap();
+        assert_eq!(bytes, request.raw_input_contents[2]);
+    }
+
+    #[test]
+    fn encode_json_rejects_what_json_cannot_carry() {
+        let encode = |request: InferRequest| {
+            let request = request.build().unwrap();
+            encode_request(&request, false).map(|_| ()).unwrap_err()
+        };
+
+        let error = encode(InferRequest::new("m").input("x", &[1], &vec![f32::NAN]));
+        assert_eq!(
+            format!("{error:#}"),
+            "Invalid data for input 'x': NaN cannot be sent as JSON"
+        );
+
+        let mut request = InferRequest::new("m")
+            .input("x", &[1], &vec!["a".to_string()])
+            .build()
+            .unwrap();
+        request.raw_input_contents[0] = vec![1, 0, 0, 0, 0xff].into();
+        let error = encode_request(&request, false).unwrap_err();
+        assert!(format!("{error:#}").contains("UTF-8"), "{error:#}");
+
+        let mut request = InferRequest::new("m")
+            .input("x", &[1], &vec![0u16])
+            .build()
+            .unwrap();
+        request.inputs[0].datatype = "FP16".to_string();
+        let error = encode_request(&request, false).unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Invalid data for input 'x': FP16 cannot be sent as JSON"
+        );
+    }
+
+    #[test]
+    fn decode_binary_and_json_outputs() {
+        let header = json!({
+            "model_name": "add",
+            "model_version": "1",
+            "outputs": [
+                {
+                    "name": "sum",
+                    "datatype": "INT32",
+                    "shape": [2],
+                    "parameters": { "binary_data_size": 8 },
+                },
+                { "name": "label", "datatype": "BYTES", "shape": [1], "data": ["ok"] },
+                { "name": "scores", "datatype": "FP32", "shape": [2, 1], "data": [[0.5], [1.5]] },
+            ],
+        });
+        let (headers, body) = response(header, &[3, 0, 0, 0, 4, 0, 0, 0]);
+
+        let response = decode_response(&headers, &body).unwrap();
+        assert_eq!(response.model_version, "1");
+        let outputs = InferenceOutput::from_response(&response).unwrap();
+        assert_eq!(outputs[0].as_slice::<i32>(), Some(&[3, 4][..]));
+        assert_eq!(response.raw_output_contents[1], b"\x02\0\0\0ok");
+        assert_eq!(outputs[2].as_slice::<f32>(), Some(&[0.5, 1.5][..]));
+    }
+
+    #[test]
+    fn decode_json_only_body() {
+        let header = json!({
+            "model_name": "add",
+            "outputs": [{ "name": "sum", "datatype": "INT64", "shape": [1], "data": [7] }],
+        });
+        let body = serde_json::to_vec(&header).unwrap();
+
+        let response = decode_response(&HeaderMap::new(), &body).unwrap();
+        assert_eq!(response.raw_output_contents, [7i64.to_le_bytes()]);
+    }
+
+    #[test]
+    fn decode_truncated_binary_data() {
+        let header = json!({
+            "model_name": "add",
+            "outputs": [{
+                "name": "sum",
+                "datatype": "INT32",
+                "shape": [2],
+                "parameters": { "binary_data_size": 8 },
+            }],
+        });
+        let (headers, body) = response(header, &[3, 0, 0, 0]);
+        let error = decode_response(&headers, &body).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Binary data of output 'sum' is truncated"
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(HEADER_LENGTH, (body.len() + 1).into());
+        assert!(decode_response(&headers, &body).is_err());
+    }
+
+    #[test]
+    fn decode_shared_memory_outputs() {
+        let request = InferRequest::new("add")
+            .output("sum")
+            .shm_output("carry", "io", 0, 8)
+            .build()
+            .unwrap();
+        let header = json!({
+            "model_name": "add",
+            "outputs": [
+                { "name": "carry", "datatype": "INT32", "shape": [2] },
+                {
+                    "name": "sum",
+                    "datatype": "INT32",
+                    "shape": [1],
+                    "parameters": { "binary_data_size": 4 },
+                },
+            ],
+        });
+        let (headers, body) = response(header, &[5, 0, 0, 0]);
+
+        let mut response = decode_response(&headers, &body).unwrap();
+        shm::mark_outputs(&request.outputs, &mut response);
+        assert!(shm::in_shared_memory(&response.outputs[0]));
+        let outputs = InferenceOutput::from_response(&response).unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].name, "sum");
+        assert_eq!(outputs[0].as_slice::<i32>(), Some(&[5][..]));
+    }
+
+    #[test]
+    fn model_paths_are_percent_encoded() {
+        assert_eq!(
+            model_path("a/b c", "", "/ready"),
+            "/v2/models/a%2Fb%20c/ready"
+        );
+        assert_eq!(
+            model_path("resnet-50_v1.2", "3?", "/infer"),
+            "/v2/models/resnet-50_v1.2/versions/3%3F/infer"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    ClientPool, InferRequest, InferenceOutput, ModelMetadata, Parameter, ServerMetadata,
+    TritonClient,
+};
+use anyhow::Result;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use triton_grpc_client::inference::ModelInferResponse;
+use triton_types::{TensorError, TensorSource, TensorSpec, TritonInputs, TritonOutputs};
+
+/// The decoded outputs of an inference together with the response's
+/// metadata.
+#[derive(Debug)]
+pub struct InferResult {
+    pub model_name: String,
+    pub model_version: String,
+    pub id: String,
+    pub parameters: HashMap<String, Parameter>,
+    pub outputs: Vec<InferenceOutput>,
+}
+
+impl InferResult {
+    pub fn from_response(response: &ModelInferResponse) -> Result<Self> {
+        Ok(Self {
+            model_name: response.model_name.clone(),
+            model_version: response.model_version.clone(),
+            id: response.id.clone(),
+            parameters: response
+                .parameters
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), Parameter::from_proto(value)?)))
+                .collect(),
+            outputs: InferenceOutput::from_response(response)?,
+        })
+    }
+
+    pub fn output(&self, name: &str) -> Option<&InferenceOutput> {
+        self.outputs.iter().find(|output| output.name == name)
+    }
+
+    /// Reads a `#[derive(TritonOutputs)]` struct from the outputs.
+    pub fn typed<O: TritonOutputs>(&self) -> Result<O> {
+        Ok(O::read_outputs(self)?)
+    }
+}
+
+impl TensorSource for InferResult {
+    fn tensor_bytes(&self, spec: &TensorSpec) -> Result<Cow<'_, [u8]>, TensorError> {
+        let output = self
+            .output(spec.name)
+            .ok_or_else(|| TensorError::new(format!("Missing output '{}'", spec.name)))?;
+
+        if output.datatype != spec.datatype {
+            return Err(TensorError::new(format!(
+                "Output '{}' is {}, not {}",
+                spec.name, output.datatype, spec.datatype
+            )));
+        }
+
+        Ok(Cow::Owned(output.data.to_bytes()))
+    }
+}
+
+/// Health, metadata and inference independent of the transport. Write
+/// application code against this trait to switch between gRPC and HTTP, or
+/// to test it against a fake.
+pub trait InferenceClient: Send + Sync {
+    fn server_live(&self) -> impl Future<Output = Result<bool>> + Send;
+
+    fn server_ready(&self) -> impl Future<Output = Result<bool>> + Send;
+
+    fn model_ready(
+        &self,
+        model_name: &str,
+        model_version: &str,
+    ) -> impl Future<Output = Result<bool>> + Send;
+
+    fn server_metadata(&self) -> impl Future<Output = Result<ServerMetadata>> + Send;
+
+    fn model_metadata(
+        &self,
+        model_name: &str,
+        model_version: &str,
+    ) -> impl Future<Output = Result<ModelMetadata>> + Send;
+
+    fn infer(&self, request: InferRequest) -> impl Future<Output = Result<InferResult>> + Send;
+
+    /// Runs inference with typed inputs and outputs, requesting exactly the
+    /// outputs declared by `O`.
+    fn infer_typed<I: TritonInputs, O: TritonOutputs>(
+        &self,
+        model_name: &str,
+        model_version: &str,
+        inputs: &I,
+    ) -> impl Future<Output = Result<O>> + Send {
+        let request = InferRequest::new(model_name)
+            .version(model_version)
+            .inputs(inputs)
+            .outputs::<O>();
+
+        async move { self.infer(request).await?.typed() }
+    }
+}
+
+/// Implements [`InferenceClient`] by calling the inherent methods of
+/// `$target`, in which `$client` is bound to `self`.
+macro_rules! delegate_inference_client {
+    ($ty:ty, $client:ident => $target:expr) => {
+        impl InferenceClient for $ty {
+            async fn server_live(&self) -> Result<bool> {
+                let $client = self;
+                $target.server_live().await
+            }
+
+            async fn server_ready(&self) -> Result<bool> {
+                let $client = self;
+                $target.server_ready().await
+            }
+
+            async fn model_ready(&self, model_name: &str, model_version: &str) -> Result<bool> {
+                let $client = self;
+                $target.model_ready(model_name, model_version).await
+            }
+
+            async fn server_metadata(&self) -> Result<ServerMetadata> {
+                let $client = self;
+                $target.server_metadata().await
+            }
+
+            async fn model_metadata(
+                &self,
+                model_name: &str,
+                model_version: &str,
+            ) -> Result<ModelMetadata> {
+                let $client = self;
+                $target.model_metadata(model_name, model_version).await
+            }
+
+            async fn infer(&self, request: InferRequest) -> Result<InferResult> {
+                let $client = self;
+                let response = $target.infer(request.build()?).await?;
+                InferResult::from_response(&response)
+            }
+        }
+    };
+}
+
+delegate_inference_client!(TritonClient, client => client);
+delegate_inference_client!(ClientPool, pool => pool.get());
+#[cfg(feature = "http")]
+delegate_inference_client!(crate::HttpClient, client => client);
@@ -1,115 +1,90 @@
+#[path = "client.rs"]
+pub mod client;
+#[path = "config.rs"]
+pub mod config;
+#[cfg(feature = "http")]
+#[path = "http.rs"]
+pub mod http;
+#[path = "inference.rs"]
+pub mod inference;
+#[path = "metadata.rs"]
+pub mod metadata;
+#[path = "model_config.rs"]
+pub mod model_config;
+#[path = "output.rs"]
+pub mod output;
+#[path = "pool.rs"]
+pub mod pool;
+#[path = "repository.rs"]
+pub mod repository;
+#[path = "request.rs"]
+pub mod request;
+#[path = "retry.rs"]
+pub mod retry;
+#[path = "settings.rs"]
+pub mod settings;
+#[path = "shm.rs"]
+pub mod shm;
+#[path = "statistics.rs"]
+pub mod statistics;
+#[path = "stream.rs"]
+pub mod stream;
+
+pub use client::*;
+pub use config::*;
+#[cfg(feature = "http")]
+pub use http::*;
+pub use inference::*;
+pub use metadata::*;
+pub use model_config::*;
+pub use output::*;
+pub use pool::*;
+pub use repository::*;
+pub use request::*;
+pub use retry::*;
+pub use settings::*;
+pub use shm::*;
+pub use statistics::*;
+pub use stream::*;
+
 use anyhow::Result;
-use tonic::Request;
-use tonic::transport::Channel;
-use triton_grpc_client::inference::grpc_inference_service_client::GrpcInferenceServiceClient;
-use triton_grpc_client::inference::{
-    ModelInferRequest, ModelInferResponse, ModelReadyRequest, ServerReadyRequest,
+use std::borrow::Cow;
+use triton_grpc_client::inference::ModelInferResponse;
+
+#[cfg(feature = "ndarray")]
+pub use ndarray;
+pub use triton_types as types;
+pub use triton_types::{
+    DataType, TensorError, TensorSink, TensorSource, TensorSpec, TensorValue, TritonInputs,
+    TritonOutputs,
 };
-
-#[derive(Debug)]
-pub struct InferenceOutput {
-    pub name: String,
-    pub datatype: String,
-    pub shape: Vec<i64>,
-    pub data: OutputData,
-}
-
-#[derive(Debug)]
-pub enum OutputData {
-    FP32(Vec<f32>),
-    FP64(Vec<f64>),
-    INT32(Vec<i32>),
-    INT64(Vec<i64>),
-    Bytes(Vec<String>),
-    Unknown(Vec<u8>),
-}
-
-impl InferenceOutput {
-    pub fn from_response(response: &ModelInferResponse) -> Result<Vec<Self>> {
-        let mut outputs = Vec::new();
-
-        for (i, output_meta) in response.outputs.iter().enumerate() {
-            let raw_data = response
-                .raw_output_contents
-                .get(i)
-                .ok_or_else(|| anyhow::anyhow!("Missing raw data for output {}", i))?;
-
-            let data = match output_meta.datatype.as_str() {
-                "FP32" => OutputData::FP32(decode_fp32_tensor(raw_data)),
-                "FP64" => OutputData::FP64(decode_fp64_tensor(raw_data)),
-                "INT32" => OutputData::INT32(decode_int32_tensor(raw_data)),
-                "INT64" => OutputData::INT64(decode_int64_tensor(raw_data)),
-                "BYTES" => OutputData::Bytes(decode_string_tensor(raw_data)),
-                _ => OutputData::Unknown(raw_data.to_vec()),
-            };
-
-            outputs.push(InferenceOutput {
-                name: output_meta.name.clone(),
-                datatype: output_meta.datatype.clone(),
-                shape: output_meta.shape.clone(),
-                data,
-            });
+#[cfg(feature = "half")]
+pub use triton_types::{bf16, bf16_to_f32, f16, f16_to_f32, f32_to_bf16, f32_to_f16};
+
+struct ResponseSource<'a>(&'a ModelInferResponse);
+
+impl TensorSource for ResponseSource<'_> {
+    fn tensor_bytes(&self, spec: &TensorSpec) -> Result<Cow<'_, [u8]>, TensorError> {
+        let response = self.0;
+        let index = response
+            .outputs
+            .iter()
+            .position(|output| output.name == spec.name)
+            .ok_or_else(|| TensorError::new(format!("Missing output '{}'", spec.name)))?;
+
+        let datatype = &response.outputs[index].datatype;
+        if *datatype != spec.datatype.as_str() {
+            return Err(TensorError::new(format!(
+                "Output '{}' is {}, not {}",
+                spec.name, datatype, spec.datatype
+            )));
         }
 
-        Ok(outputs)
+        output_bytes(response, index).map_err(|error| TensorError::new(format!("{error:#}")))
     }
 }
 
-fn decode_string_tensor(_data: &[u8]) -> Vec<String> {
-    todo!()
-}
-
-fn decode_int64_tensor(_data: &[u8]) -> Vec<i64> {
-    todo!()
-}
-
-fn decode_int32_tensor(_data: &[u8]) -> Vec<i32> {
-    todo!()
-}
-
-fn decode_fp64_tensor(_data: &[u8]) -> Vec<f64> {
-    todo!()
-}
-
-fn decode_fp32_tensor(data: &[u8]) -> Vec<f32> {
-    data.chunks_exact(4)
-        .map(|chunk| {
-            let bytes: [u8; 4] = chunk.try_into().unwrap();
-            f32::from_le_bytes(bytes)
-        })
-        .collect()
-}
-
-pub struct TritonClient {
-    client: GrpcInferenceServiceClient<Channel>,
-}
-
-impl TritonClient {
-    pub async fn new(url: &str) -> Result<Self> {
-        let channel = Channel::from_shared(url.to_string())?.connect().await?;
-        let client = GrpcInferenceServiceClient::new(channel);
-
-        Ok(TritonClient { client })
-    }
-
-    pub async fn server_ready(&mut self) -> Result<bool> {
-        let request = Request::new(ServerReadyRequest {});
-        let response = self.client.server_ready(request).await?;
-        Ok(response.into_inner().ready)
-    }
-
-    pub async fn model_ready(&mut self, model_name: &str, model_version: &str) -> Result<bool> {
-        let request = Request::new(ModelReadyRequest {
-            name: model_name.to_string(),
-            version: model_version.to_string(),
-        });
-        let response = self.client.model_ready(request).await?;
-        Ok(response.into_inner().ready)
-    }
-
-    pub async fn infer(&mut self, request: ModelInferRequest) -> Result<ModelInferResponse> {
-        let request = Request::new(request);
-        let response = self.client.model_infer(request).await?;
-        Ok(response.into_inner())
-    }
+/// Reads a `#[derive(TritonOutputs)]` struct from a response.
+pub fn typed_outputs<O: TritonOutputs>(response: &ModelInferResponse) -> Result<O> {
+    Ok(O::read_outputs(&ResponseSource(response))?)
 }
@@ -0,0 +1,79 @@
+use triton_grpc_client::inference::model_metadata_response::TensorMetadata as TensorMetadataProto;
+use triton_grpc_client::inference::{ModelMetadataResponse, ServerMetadataResponse};
+use triton_types::{DataType, ParseDataTypeError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerMetadata {
+    pub name: String,
+    pub version: String,
+    /// Protocol extensions the server supports, e.g. `model_repository`.
+    pub extensions: Vec<String>,
+}
+
+impl From<ServerMetadataResponse> for ServerMetadata {
+    fn from(response: ServerMetadataResponse) -> Self {
+        Self {
+            name: response.name,
+            version: response.version,
+            extensions: response.extensions,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TensorMetadata {
+    pub name: String,
+    pub datatype: DataType,
+    /// `-1` marks a variable-size dimension.
+    pub shape: Vec<i64>,
+}
+
+impl TryFrom<TensorMetadataProto> for TensorMetadata {
+    type Error = ParseDataTypeError;
+
+    fn try_from(tensor: TensorMetadataProto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            datatype: tensor.datatype.parse()?,
+            name: tensor.name,
+            shape: tensor.shape,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelMetadata {
+    pub name: String,
+    pub versions: Vec<String>,
+    pub platform: String,
+    pub inputs: Vec<TensorMetadata>,
+    pub outputs: Vec<TensorMetadata>,
+}
+
+impl ModelMetadata {
+    pub fn input(&self, name: &str) -> Option<&TensorMetadata> {
+        self.inputs.iter().find(|input| input.name == name)
+    }
+
+    pub fn output(&self, name: &str) -> Option<&TensorMetadata> {
+        self.outputs.iter().find(|output| output.name == name)
+    }
+}
+
+/// Fails on a tensor datatype this crate does not know.
+impl TryFrom<ModelMetadataResponse> for ModelMetadata {
+    type Error = ParseDataTypeError;
+
+    fn try_from(response: ModelMetadataResponse) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: response.name,
+            versions: response.versions,
+            platform: response.platform,
+            inputs: tensors(response.inputs)?,
+            outputs: tensors(response.outputs)?,
+        })
+    }
+}
+
+fn tensors(tensors: Vec<TensorMetadataProto>) -> Result<Vec<TensorMetadata>, ParseDataTypeError> {
+    tensors.into_iter().map(TryInto::try_into).collect()
+}
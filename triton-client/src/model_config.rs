@@ -0,0 +1,133 @@
+use triton_grpc_client::inference::ModelConfig as ModelConfigProto;
+use triton_types::DataType;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TensorConfig {
+    pub name: String,
+    /// `Invalid` for a `data_type` this crate does not know.
+    pub datatype: DataType,
+    /// Without the batch dimension; `-1` marks a variable-size dimension.
+    pub dims: Vec<i64>,
+}
+
+/// The parts of a model's `config.pbtxt` most callers need. The full config,
+/// with every field Triton defines, is available through
+/// [`ModelConfig::raw`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelConfig {
+    pub name: String,
+    pub platform: String,
+    pub backend: String,
+    /// `0` if the model does not batch.
+    pub max_batch_size: i32,
+    pub inputs: Vec<TensorConfig>,
+    pub outputs: Vec<TensorConfig>,
+    raw: ModelConfigProto,
+}
+
+impl ModelConfig {
+    pub fn input(&self, name: &str) -> Option<&TensorConfig> {
+        self.inputs.iter().find(|input| input.name == name)
+    }
+
+    pub fn output(&self, name: &str) -> Option<&TensorConfig> {
+        self.outputs.iter().find(|output| output.name == name)
+    }
+
+    /// The config as the server returned it, which is also the type
+    /// `triton_model_config` reads and writes `config.pbtxt` as.
+    pub fn raw(&self) -> &ModelConfigProto {
+        &self.raw
+    }
+
+    pub fn into_raw(self) -> ModelConfigProto {
+        self.raw
+    }
+}
+
+impl From<ModelConfigProto> for ModelConfig {
+    fn from(config: ModelConfigProto) -> Self {
+        let tensor = |name: &str, data_type: i32, dims: &[i64]| TensorConfig {
+            name: name.to_string(),
+            datatype: u32::try_from(data_type)
+                .map(DataType::from_raw)
+                .unwrap_or(DataType::Invalid),
+            dims: dims.to_vec(),
+        };
+
+        Self {
+            name: config.name.clone(),
+            platform: config.platform.clone(),
+            backend: config.backend.clone(),
+            max_batch_size: config.max_batch_size,
+            inputs: config
+                .input
+                .iter()
+                .map(|input| tensor(&input.name, input.data_type, &input.dims))
+                .collect(),
+            outputs: config
+                .output
+                .iter()
+                .map(|output| tensor(&output.name, output.data_type, &output.dims))
+                .collect(),
+            raw: config,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use triton_grpc_client::inference::{DataType as ConfigDataType, ModelInput, ModelOutput};
+
+    #[test]
+    fn from_proto() {
+        let raw = ModelConfigProto {
+            name: "mnist".to_string(),
+            backend: "onnxruntime".to_string(),
+            max_batch_size: 8,
+            input: vec![ModelInput {
+                name: "Input3".to_string(),
+                data_type: ConfigDataType::TypeFp32 as i32,
+                dims: vec![1, 28, 28],
+                ..Default::default()
+            }],
+            output: vec![
+                ModelOutput {
+                    name: "Plus214_Output_0".to_string(),
+                    data_type: ConfigDataType::TypeFp16 as i32,
+                    dims: vec![-1],
+                    ..Default::default()
+                },
+                ModelOutput {
+                    name: "LABEL".to_string(),
+                    data_type: 1000,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let config = ModelConfig::from(raw.clone());
+        assert_eq!(config.name, "mnist");
+        assert_eq!(config.platform, "");
+        assert_eq!(config.backend, "onnxruntime");
+        assert_eq!(config.max_batch_size, 8);
+        assert_eq!(
+            config.input("Input3"),
+            Some(&TensorConfig {
+                name: "Input3".to_string(),
+                datatype: DataType::Fp32,
+                dims: vec![1, 28, 28],
+            })
+        );
+        assert_eq!(
+            config.output("Plus214_Output_0").unwrap().datatype,
+            DataType::Fp16
+        );
+        assert_eq!(config.output("LABEL").unwrap().datatype, DataType::Invalid);
+        assert_eq!(config.output("Input3"), None);
+        assert_eq!(config.raw(), &raw);
+        assert_eq!(config.into_raw(), raw);
+    }
+}
@@ -0,0 +1,616 @@
+use crate::shm::in_shared_memory;
+use anyhow::{Context, Result, anyhow, bail};
+use std::borrow::Cow;
+use triton_grpc_client::inference::model_infer_response::InferOutputTensor;
+use triton_grpc_client::inference::{InferTensorContents, ModelInferResponse};
+use triton_types::{DataType, element_count};
+#[cfg(feature = "half")]
+use triton_types::{bf16, f16};
+
+#[derive(Debug)]
+pub struct InferenceOutput {
+    pub name: String,
+    pub datatype: DataType,
+    pub shape: Vec<i64>,
+    pub data: OutputData,
+}
+
+/// Decoded tensor data. Non-exhaustive because the `half` feature adds the
+/// FP16 and BF16 variants.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum OutputData {
+    BOOL(Vec<bool>),
+    UINT8(Vec<u8>),
+    UINT16(Vec<u16>),
+    UINT32(Vec<u32>),
+    UINT64(Vec<u64>),
+    INT8(Vec<i8>),
+    INT16(Vec<i16>),
+    INT32(Vec<i32>),
+    INT64(Vec<i64>),
+    #[cfg(feature = "half")]
+    FP16(Vec<f16>),
+    #[cfg(feature = "half")]
+    BF16(Vec<bf16>),
+    FP32(Vec<f32>),
+    FP64(Vec<f64>),
+    /// One entry per element. BYTES elements are arbitrary binary data; see
+    /// [`InferenceOutput::to_strings`] for text.
+    Bytes(Vec<Vec<u8>>),
+    /// Raw little-endian data of a datatype this build cannot decode, such
+    /// as FP16 without the `half` feature.
+    Unknown(Vec<u8>),
+}
+
+/// Element types that can be read back out of [`OutputData`].
+pub trait OutputElement: Clone {
+    fn from_data(data: &OutputData) -> Option<&[Self]>;
+}
+
+macro_rules! output_element {
+    ($ty:ty, $variant:ident) => {
+        impl OutputElement for $ty {
+            fn from_data(data: &OutputData) -> Option<&[Self]> {
+                match data {
+                    OutputData::$variant(values) => Some(values),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+output_element!(bool, BOOL);
+output_element!(u8, UINT8);
+output_element!(u16, UINT16);
+output_element!(u32, UINT32);
+output_element!(u64, UINT64);
+output_element!(i8, INT8);
+output_element!(i16, INT16);
+output_element!(i32, INT32);
+output_element!(i64, INT64);
+output_element!(f32, FP32);
+output_element!(f64, FP64);
+output_element!(Vec<u8>, Bytes);
+#[cfg(feature = "half")]
+output_element!(f16, FP16);
+#[cfg(feature = "half")]
+output_element!(bf16, BF16);
+
+impl InferenceOutput {
+    /// Borrows the decoded data if it holds elements of `T`.
+    pub fn as_slice<T: OutputElement>(&self) -> Option<&[T]> {
+        T::from_data(&self.data)
+    }
+
+    /// Reads a BYTES output as UTF-8 strings.
+    pub fn to_strings(&self) -> Result<Vec<&str>> {
+        let OutputData::Bytes(values) = &self.data else {
+            bail!(
+                "Output '{}' of type {} is not BYTES",
+                self.name,
+                self.datatype
+            );
+        };
+
+        values
+            .iter()
+            .map(|value| std::str::from_utf8(value))
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Output '{}' is not valid UTF-8", self.name))
+    }
+
+    /// Copies the decoded data into an n-dimensional array. Triton sends
+    /// tensors in row-major order, which is ndarray's default layout.
+    #[cfg(feature = "ndarray")]
+    pub fn to_array<T: OutputElement>(&self) -> Result<ndarray::ArrayD<T>> {
+        let data = self.as_slice::<T>().ok_or_else(|| {
+            anyhow!(
+                "Output '{}' of type {} does not hold {}",
+                self.name,
+                self.datatype,
+                std::any::type_name::<T>()
+            )
+        })?;
+        let shape = self
+            .shape
+            .iter()
+            .map(|&dim| usize::try_from(dim))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ndarray::ArrayD::from_shape_vec(
+            ndarray::IxDyn(&shape),
+            data.to_vec(),
+        )?)
+    }
+
+    /// Decodes every output of a response, whether the server sent it in
+    /// `raw_output_contents` or in the typed `contents` field. Outputs
+    /// written to shared memory are skipped.
+    pub fn from_response(response: &ModelInferResponse) -> Result<Vec<Self>> {
+        (0..response.outputs.len())
+            .filter(|&index| !in_shared_memory(&response.outputs[index]))
+            .map(|index| {
+                let output = &response.outputs[index];
+                let datatype = output.datatype.parse::<DataType>()?;
+                let count = element_count(&output.shape)
+                    .ok_or_else(|| anyhow!("Invalid output shape {:?}", output.shape))?;
+                let bytes = output_bytes(response, index)?;
+
+                let data = OutputData::decode(datatype, &bytes, count)
+                    .with_context(|| format!("Invalid data for output '{}'", output.name))?;
+
+                Ok(InferenceOutput {
+                    name: output.name.clone(),
+                    datatype,
+                    shape: output.shape.clone(),
+                    data,
+                })
+            })
+            .collect()
+    }
+}
+
+impl OutputData {
+    /// Decodes `count` elements of little-endian `datatype` data, failing if
+    /// the byte length does not match.
+    pub fn decode(datatype: DataType, bytes: &[u8], count: usize) -> Result<Self> {
+        Ok(match datatype {
+            DataType::Bool => OutputData::BOOL(fixed(bytes, count, |[b]| b != 0)?),
+            DataType::Uint8 => OutputData::UINT8(fixed(bytes, count, u8::from_le_bytes)?),
+            DataType::Uint16 => OutputData::UINT16(fixed(bytes, count, u16::from_le_bytes)?),
+            DataType::Uint32 => OutputData::UINT32(fixed(bytes, count, u32::from_le_bytes)?),
+            DataType::Uint64 => OutputData::UINT64(fixed(bytes, count, u64::from_le_bytes)?),
+            DataType::Int8 => OutputData::INT8(fixed(bytes, count, i8::from_le_bytes)?),
+            DataType::Int16 => OutputData::INT16(fixed(bytes, count, i16::from_le_bytes)?),
+            DataType::Int32 => OutputData::INT32(fixed(bytes, count, i32::from_le_bytes)?),
+            DataType::Int64 => OutputData::INT64(fixed(bytes, count, i64::from_le_bytes)?),
+            #[cfg(feature = "half")]
+            DataType::Fp16 => OutputData::FP16(fixed(bytes, count, f16::from_le_bytes)?),
+            #[cfg(feature = "half")]
+            DataType::Bf16 => OutputData::BF16(fixed(bytes, count, bf16::from_le_bytes)?),
+            DataType::Fp32 => OutputData::FP32(fixed(bytes, count, f32::from_le_bytes)?),
+            DataType::Fp64 => OutputData::FP64(fixed(bytes, count, f64::from_le_bytes)?),
+            DataType::Bytes => OutputData::Bytes(decode_bytes(bytes, count)?),
+            #[cfg(not(feature = "half"))]
+            DataType::Fp16 | DataType::Bf16 => OutputData::Unknown(bytes.to_vec()),
+            DataType::Invalid => bail!("Invalid datatype"),
+        })
+    }
+}
+
+impl OutputData {
+    /// Encodes the elements as little-endian bytes, the inverse of
+    /// [`OutputData::decode`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        fn fixed<T: Copy, const N: usize>(values: &[T], to_le: fn(T) -> [u8; N]) -> Vec<u8> {
+            values.iter().flat_map(|&value| to_le(value)).collect()
+        }
+
+        match self {
+            OutputData::BOOL(values) => values.iter().map(|&value| u8::from(value)).collect(),
+            OutputData::UINT8(values) => values.clone(),
+            OutputData::UINT16(values) => fixed(values, u16::to_le_bytes),
+            OutputData::UINT32(values) => fixed(values, u32::to_le_bytes),
+            OutputData::UINT64(values) => fixed(values, u64::to_le_bytes),
+            OutputData::INT8(values) => fixed(values, i8::to_le_bytes),
+            OutputData::INT16(values) => fixed(values, i16::to_le_bytes),
+            OutputData::INT32(values) => fixed(values, i32::to_le_bytes),
+            OutputData::INT64(values) => fixed(values, i64::to_le_bytes),
+            #[cfg(feature = "half")]
+            OutputData::FP16(values) => fixed(values, f16::to_le_bytes),
+            #[cfg(feature = "half")]
+            OutputData::BF16(values) => fixed(values, bf16::to_le_bytes),
+            OutputData::FP32(values) => fixed(values, f32::to_le_bytes),
+            OutputData::FP64(values) => fixed(values, f64::to_le_bytes),
+            OutputData::Bytes(values) => {
+                let mut bytes = Vec::new();
+                for value in values {
+                    bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                    bytes.extend_from_slice(value);
+                }
+                bytes
+            }
+            OutputData::Unknown(bytes) => bytes.clone(),
+        }
+    }
+}
+
+fn fixed<T, const N: usize>(bytes: &[u8], count: usize, from: fn([u8; N]) -> T) -> Result<Vec<T>> {
+    if Some(bytes.len()) != count.checked_mul(N) {
+        bail!(
+            "Expected {} bytes for {count} elements, got {}",
+            count.saturating_mul(N),
+            bytes.len()
+        );
+    }
+
+    Ok(bytes
+        .chunks_exact(N)
+        .map(|chunk| from(chunk.try_into().unwrap()))
+        .collect())
+}
+
+/// Splits BYTES data, where each element is prefixed by its length as a
+/// little-endian `u32`.
+fn decode_bytes(mut bytes: &[u8], count: usize) -> Result<Vec<Vec<u8>>> {
+    let mut values = Vec::with_capacity(count.min(bytes.len() / 4));
+
+    while !bytes.is_empty() {
+        let (length, rest) = bytes
+            .split_first_chunk::<4>()
+            .ok_or_else(|| anyhow!("Truncated length prefix of element {}", values.len()))?;
+        let length = u32::from_le_bytes(*length) as usize;
+        if rest.len() < length {
+            bail!(
+                "Element {} needs {length} bytes, only {} left",
+                values.len(),
+                rest.len()
+            );
+        }
+
+        let (value, rest) = rest.split_at(length);
+        values.push(value.to_vec());
+        bytes = rest;
+    }
+
+    if values.len() != count {
+        bail!("Expected {count} elements, got {}", values.len());
+    }
+
+    Ok(values)
+}
+
+/// Raw little-endian bytes of output `index`, borrowed from
+/// `raw_output_contents` or encoded from the typed contents.
+pub(crate) fn output_bytes(response: &ModelInferResponse, index: usize) -> Result<Cow<'_, [u8]>> {
+    let output = &response.outputs[index];
+    if in_shared_memory(output) {
+        bail!("Output '{}' is in shared memory", output.name);
+    }
+
+    let raw = &response.raw_output_contents;
+    if !raw.is_empty() {
+        // Outputs in shared memory may or may not have an empty entry.
+        let in_response = |outputs: &[InferOutputTensor]| {
+            outputs
+                .iter()
+                .filter(|output| !in_shared_memory(output))
+                .count()
+        };
+        let raw_index = if raw.len() == response.outputs.len() {
+            index
+        } else if raw.len() == in_response(&response.outputs) {
+            in_response(&response.outputs[..index])
+        } else {
+            bail!(
+                "Response has {} outputs but {} raw output contents",
+                response.outputs.len(),
+                raw.len()
+            );
+        };
+        return Ok(Cow::Borrowed(&raw[raw_index]));
+    }
+
+    let datatype = output.datatype.parse::<DataType>()?;
+    let contents = output.contents.clone().unwrap_or_default();
+    let bytes = encode_contents(datatype, contents)
+        .with_context(|| format!("Invalid contents for output '{}'", output.name))?;
+
+    Ok(Cow::Owned(bytes))
+}
+
+pub(crate) fn encode_contents(
+    datatype: DataType,
+    contents: InferTensorContents,
+) -> Result<Vec<u8>> {
+    fn narrow<S: Copy + std::fmt::Display, T: TryFrom<S>, const N: usize>(
+        values: &[S],
+        to_le: fn(T) -> [u8; N],
+    ) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(values.len() * N);
+        for &value in values {
+            let value = T::try_from(value)
+                .map_err(|_| anyhow!("{value} does not fit in {}", std::any::type_name::<T>()))?;
+            bytes.extend_from_slice(&to_le(value));
+        }
+        Ok(bytes)
+    }
+
+    match datatype {
+        DataType::Bool => Ok(contents
+            .bool_contents
+            .iter()
+            .map(|&b| u8::from(b))
+            .collect()),
+        DataType::Uint8 => narrow(&contents.uint_contents, u8::to_le_bytes),
+        DataType::Uint16 => narrow(&contents.uint_contents, u16::to_le_bytes),
+        DataType::Uint32 => narrow(&contents.uint_contents, u32::to_le_bytes),
+        DataType::Uint64 => narrow(&contents.uint64_contents, u64::to_le_bytes),
+        DataType::Int8 => narrow(&contents.int_contents, i8::to_le_bytes),
+        DataType::Int16 => narrow(&contents.int_contents, i16::to_le_bytes),
+        DataType::Int32 => narrow(&contents.int_contents, i32::to_le_bytes),
+        DataType::Int64 => narrow(&contents.int64_contents, i64::to_le_bytes),
+        DataType::Fp32 => narrow(&contents.fp32_contents, f32::to_le_bytes),
+        DataType::Fp64 => narrow(&contents.fp64_contents, f64::to_le_bytes),
+        DataType::Bytes => {
+            let mut bytes = Vec::new();
+            for value in &contents.bytes_contents {
+                bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(value);
+            }
+            Ok(bytes)
+        }
+        DataType::Fp16 | DataType::Bf16 | DataType::Invalid => {
+            bail!("{datatype} has no typed contents and must be sent raw")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shm::SHARED_MEMORY_REGION;
+    use triton_grpc_client::inference::InferParameter;
+
+    fn output(name: &str, datatype: DataType, shape: &[i64]) -> InferOutputTensor {
+        InferOutputTensor {
+            name: name.to_string(),
+            datatype: datatype.to_string(),
+            shape: shape.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn bytes_element(value: &[u8]) -> Vec<u8> {
+        let mut bytes = (value.len() as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(value);
+        bytes
+    }
+
+    #[test]
+    fn decodes_every_datatype() {
+        let cases = [
+            (DataType::Bool, vec![1, 0]),
+            (DataType::Uint8, vec![1, 255]),
+            (
+                DataType::Uint16,
+                [1u16, 65535].map(u16::to_le_bytes).concat(),
+            ),
+            (DataType::Uint32, [1u32, 7].map(u32::to_le_bytes).concat()),
+            (
+                DataType::Uint64,
+                [1u64, u64::MAX].map(u64::to_le_bytes).concat(),
+            ),
+            (DataType::Int8, vec![1, 0x80]),
+            (DataType::Int16, [1i16, -2].map(i16::to_le_bytes).concat()),
+            (DataType::Int32, [1i32, -2].map(i32::to_le_bytes).concat()),
+            (
+                DataType::Int64,
+                [1i64, i64::MIN].map(i64::to_le_bytes).concat(),
+            ),
+            (DataType::Fp16, vec![0x00, 0x3c, 0x00, 0xc0]),
+            (DataType::Bf16, vec![0x80, 0x3f, 0x00, 0xc0]),
+            (DataType::Fp32, [1f32, -2.5].map(f32::to_le_bytes).concat()),
+            (DataType::Fp64, [1f64, -2.5].map(f64::to_le_bytes).concat()),
+            (
+                DataType::Bytes,
+                [bytes_element(b"a"), bytes_element(b"")].concat(),
+            ),
+        ];
+
+        for (datatype, bytes) in &cases {
+            let data = OutputData::decode(*datatype, bytes, 2).unwrap();
+            assert_eq!(&data.to_bytes(), bytes, "{datatype}");
+        }
+
+        let data = OutputData::decode(DataType::Int16, &cases[6].1, 2).unwrap();
+        assert!(matches!(data, OutputData::INT16(values) if values == [1, -2]));
+        let data = OutputData::decode(DataType::Bool, &[2, 0], 2).unwrap();
+        assert!(matches!(data, OutputData::BOOL(values) if values == [true, false]));
+        assert!(OutputData::decode(DataType::Invalid, &[], 0).is_err());
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn decodes_half_floats() {
+        let data = OutputData::decode(DataType::Fp16, &[0x00, 0x3c], 1).unwrap();
+        assert!(matches!(data, OutputData::FP16(values) if values == [f16::ONE]));
+        let data = OutputData::decode(DataType::Bf16, &[0x80, 0x3f], 1).unwrap();
+        assert!(matches!(data, OutputData::BF16(values) if values == [bf16::ONE]));
+    }
+
+    #[cfg(not(feature = "half"))]
+    #[test]
+    fn keeps_half_floats_raw() {
+        let data = OutputData::decode(DataType::Fp16, &[0x00, 0x3c], 1).unwrap();
+        assert!(matches!(data, OutputData::Unknown(bytes) if bytes == [0x00, 0x3c]));
+    }
+
+    #[test]
+    fn rejects_misaligned_data() {
+        let bytes = [0u8; 7];
+        let error = OutputData::decode(DataType::Fp32, &bytes, 2).unwrap_err();
+        assert_eq!(error.to_string(), "Expected 8 bytes for 2 elements, got 7");
+        assert!(OutputData::decode(DataType::Fp32, &bytes[..4], 2).is_err());
+        assert!(OutputData::decode(DataType::Int64, &[0; 16], usize::MAX).is_err());
+        assert!(OutputData::decode(DataType::Uint8, &[], 0).is_ok());
+    }
+
+    #[test]
+    fn splits_bytes_elements() {
+        let bytes = [bytes_element(b"cat"), bytes_element(&[0, 159])].concat();
+        assert_eq!(
+            decode_bytes(&bytes, 2).unwrap(),
+            [b"cat".to_vec(), vec![0, 159]]
+        );
+        assert_eq!(decode_bytes(&[], 0).unwrap(), Vec::<Vec<u8>>::new());
+        assert_eq!(decode_bytes(&[0; 4], 1).unwrap(), [Vec::<u8>::new()]);
+    }
+
+    #[test]
+    fn rejects_broken_bytes_elements() {
+        let error = decode_bytes(&[3, 0], 1).unwrap_err();
+        assert_eq!(error.to_string(), "Truncated length prefix of element 0");
+
+        let bytes = [bytes_element(b"a"), vec![5, 0, 0, 0, b'b']].concat();
+        let error = decode_bytes(&bytes, 2).unwrap_err();
+        assert_eq!(error.to_string(), "Element 1 needs 5 bytes, only 1 left");
+
+        let error = decode_bytes(&bytes_element(b"a"), 2).unwrap_err();
+        assert_eq!(error.to_string(), "Expected 2 elements, got 1");
+    }
+
+    #[test]
+    fn encodes_typed_contents() {
+        let contents = InferTensorContents {
+            bool_contents: vec![true, false],
+            int_contents: vec![-1, 2],
+            int64_contents: vec![-3],
+            uint_contents: vec![255, 4],
+            uint64_contents: vec![5],
+            fp32_contents: vec![1.5],
+            fp64_contents: vec![-0.5],
+            bytes_contents: vec![b"ab".to_vec(), vec![]],
+        };
+        let encode = |datatype| encode_contents(datatype, contents.clone()).unwrap();
+
+        assert_eq!(encode(DataType::Bool), [1, 0]);
+        assert_eq!(encode(DataType::Int8), [0xff, 2]);
+        assert_eq!(encode(DataType::Int16), [0xff, 0xff, 2, 0]);
+        assert_eq!(
+            encode(DataType::Int32),
+            [-1i32, 2].map(i32::to_le_bytes).concat()
+        );
+        assert_eq!(encode(DataType::Int64), (-3i64).to_le_bytes());
+        assert_eq!(encode(DataType::Uint8), [255, 4]);
+        assert_eq!(encode(DataType::Uint16), [255, 0, 4, 0]);
+        assert_eq!(
+            encode(DataType::Uint32),
+            [255u32, 4].map(u32::to_le_bytes).concat()
+        );
+        assert_eq!(encode(DataType::Uint64), 5u64.to_le_bytes());
+        assert_eq!(encode(DataType::Fp32), 1.5f32.to_le_bytes());
+        assert_eq!(encode(DataType::Fp64), (-0.5f64).to_le_bytes());
+        assert_eq!(
+            encode(DataType::Bytes),
+            [bytes_element(b"ab"), bytes_element(b"")].concat()
+        );
+
+        let narrow = |datatype, contents| encode_contents(datatype, contents).unwrap_err();
+        let too_big = InferTensorContents {
+            uint_contents: vec![256],
+            ..Default::default()
+        };
+        assert_eq!(
+            narrow(DataType::Uint8, too_big).to_string(),
+            "256 does not fit in u8"
+        );
+        let too_small = InferTensorContents {
+            int_contents: vec![-129],
+            ..Default::default()
+        };
+        assert!(
+            narrow(DataType::Int8, too_small)
+                .to_string()
+                .contains("-129")
+        );
+        for datatype in [DataType::Fp16, DataType::Bf16, DataType::Invalid] {
+            assert!(encode_contents(datatype, InferTensorContents::default()).is_err());
+        }
+    }
+
+    #[test]
+    fn reads_raw_and_typed_outputs() {
+        let raw = ModelInferResponse {
+            outputs: vec![
+                output("SCORES", DataType::Fp32, &[1, 2]),
+                output("LABELS", DataType::Bytes, &[1]),
+            ],
+            raw_output_contents: vec![
+                [0.25f32, 0.75].map(f32::to_le_bytes).concat(),
+                bytes_element(b"cat"),
+            ],
+            ..Default::default()
+        };
+        let mut typed = raw.clone();
+        typed.raw_output_contents.clear();
+        typed.outputs[0].contents = Some(InferTensorContents {
+            fp32_contents: vec![0.25, 0.75],
+            ..Default::default()
+        });
+        typed.outputs[1].contents = Some(InferTensorContents {
+            bytes_contents: vec![b"cat".to_vec()],
+            ..Default::default()
+        });
+
+        for response in [raw, typed] {
+            let outputs = InferenceOutput::from_response(&response).unwrap();
+            assert_eq!(outputs[0].as_slice::<f32>(), Some(&[0.25, 0.75][..]));
+            assert_eq!(outputs[0].as_slice::<f64>(), None);
+            assert_eq!(outputs[1].to_strings().unwrap(), ["cat"]);
+            assert!(outputs[0].to_strings().is_err());
+            assert_eq!(output_bytes(&response, 1).unwrap(), bytes_element(b"cat"));
+        }
+    }
+
+    #[test]
+    fn skips_outputs_in_shared_memory() {
+        let mut in_shm = output("SHM", DataType::Int32, &[4]);
+        in_shm
+            .parameters
+            .insert(SHARED_MEMORY_REGION.to_string(), InferParameter::default());
+        let response = ModelInferResponse {
+            outputs: vec![in_shm, output("OUT", DataType::Int32, &[1])],
+            raw_output_contents: vec![7i32.to_le_bytes().to_vec()],
+            ..Default::default()
+        };
+
+        let outputs = InferenceOutput::from_response(&response).unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].as_slice::<i32>(), Some(&[7][..]));
+        assert!(output_bytes(&response, 0).is_err());
+    }
+
+    #[test]
+    fn rejects_mismatched_responses() {
+        let shape_mismatch = ModelInferResponse {
+            outputs: vec![output("OUT", DataType::Int32, &[2, 2])],
+            raw_output_contents: vec![vec![0; 12]],
+            ..Default::default()
+        };
+        let error = InferenceOutput::from_response(&shape_mismatch).unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Invalid data for output 'OUT': Expected 16 bytes for 4 elements, got 12"
+        );
+
+        let count_mismatch = ModelInferResponse {
+            outputs: vec![
+                output("A", DataType::Uint8, &[1]),
+                output("B", DataType::Uint8, &[1]),
+            ],
+            raw_output_contents: vec![vec![1]],
+            ..Default::default()
+        };
+        assert!(InferenceOutput::from_response(&count_mismatch).is_err());
+
+        let negative = ModelInferResponse {
+            outputs: vec![output("OUT", DataType::Uint8, &[-1])],
+            raw_output_contents: vec![vec![]],
+            ..Default::default()
+        };
+        assert!(InferenceOutput::from_response(&negative).is_err());
+
+        let unknown = ModelInferResponse {
+            outputs: vec![InferOutputTensor {
+                datatype: "FP8".to_string(),
+                ..output("OUT", DataType::Uint8, &[1])
+            }],
+            raw_output_contents: vec![vec![1]],
+            ..Default::default()
+        };
+        assert!(InferenceOutput::from_response(&unknown).is_err());
+    }
+}
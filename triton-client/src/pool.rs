@@ -0,0 +1,125 @@
+use crate::{ClientConfig, TritonClient};
+use anyhow::{Result, bail};
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// How [`ClientPool`] picks a client for each call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Balance {
+    /// Takes the clients in turn.
+    #[default]
+    RoundRobin,
+    /// Takes the client with the fewest calls in flight, so that slow
+    /// replicas get less work.
+    LeastOutstanding,
+}
+
+struct Member {
+    client: TritonClient,
+    outstanding: Arc<AtomicUsize>,
+}
+
+/// Spreads calls over several clients, e.g. one per Triton replica or
+/// several channels to one server:
+///
+/// ```ignore
+/// let pool = ClientPool::connect(
+///     ["http://triton-0:8001", "http://triton-1:8001"].map(ClientConfig::new),
+///     Balance::LeastOutstanding,
+/// )
+/// .await?;
+/// let outputs = pool.get().infer_request(request).await?;
+/// ```
+///
+/// Cloning a pool is cheap and shares its clients and counters.
+#[derive(Clone)]
+pub struct ClientPool {
+    members: Arc<[Member]>,
+    balance: Balance,
+    next: Arc<AtomicUsize>,
+}
+
+impl ClientPool {
+    pub fn new(clients: impl IntoIterator<Item = TritonClient>, balance: Balance) -> Result<Self> {
+        let members: Arc<[Member]> = clients
+            .into_iter()
+            .map(|client| Member {
+                client,
+                outstanding: Arc::default(),
+            })
+            .collect();
+        if members.is_empty() {
+            bail!("A client pool needs at least one client");
+        }
+
+        Ok(Self {
+            members,
+            balance,
+            next: Arc::default(),
+        })
+    }
+
+    /// Connects one client per config.
+    pub async fn connect(
+        configs: impl IntoIterator<Item = ClientConfig>,
+        balance: Balance,
+    ) -> Result<Self> {
+        let mut clients = vec![];
+        for config in configs {
+            clients.push(config.connect().await?);
+        }
+        Self::new(clients, balance)
+    }
+
+    /// Picks a client. It counts as busy until the returned guard is
+    /// dropped, so keep the guard for the whole call.
+    pub fn get(&self) -> PooledClient {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let count = self.members.len();
+
+        let index = match self.balance {
+            Balance::RoundRobin => start % count,
+            // Starting at the round-robin position spreads ties.
+            Balance::LeastOutstanding => (0..count)
+                .map(|offset| (start + offset) % count)
+                .min_by_key(|&index| self.members[index].outstanding.load(Ordering::Relaxed))
+                .unwrap(),
+        };
+
+        let member = &self.members[index];
+        member.outstanding.fetch_add(1, Ordering::Relaxed);
+        PooledClient {
+            client: member.client.clone(),
+            outstanding: member.outstanding.clone(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+}
+
+/// A client taken from a [`ClientPool`].
+pub struct PooledClient {
+    client: TritonClient,
+    outstanding: Arc<AtomicUsize>,
+}
+
+impl Deref for PooledClient {
+    type Target = TritonClient;
+
+    fn deref(&self) -> &TritonClient {
+        &self.client
+    }
+}
+
+impl Drop for PooledClient {
+    fn drop(&mut self) {
+        self.outstanding.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+use triton_grpc_client::inference::ModelRepositoryParameter;
+use triton_grpc_client::inference::model_repository_parameter::ParameterChoice;
+use triton_grpc_client::inference::repository_index_response::ModelIndex as ModelIndexProto;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelState {
+    Unknown,
+    Ready,
+    Unavailable,
+    Loading,
+    Unloading,
+    Other(String),
+}
+
+impl From<&str> for ModelState {
+    fn from(state: &str) -> Self {
+        match state {
+            "" | "UNKNOWN" => ModelState::Unknown,
+            "READY" => ModelState::Ready,
+            "UNAVAILABLE" => ModelState::Unavailable,
+            "LOADING" => ModelState::Loading,
+            "UNLOADING" => ModelState::Unloading,
+            other => ModelState::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ModelState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = match self {
+            ModelState::Unknown => "UNKNOWN",
+            ModelState::Ready => "READY",
+            ModelState::Unavailable => "UNAVAILABLE",
+            ModelState::Loading => "LOADING",
+            ModelState::Unloading => "UNLOADING",
+            ModelState::Other(state) => state,
+        };
+        write!(f, "{state}")
+    }
+}
+
+/// A model (version) known to the repository, whether loaded or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelIndex {
+    pub name: String,
+    /// Empty for models that have never been loaded.
+    pub version: String,
+    pub state: ModelState,
+    /// Why the model is in its state, e.g. a load error.
+    pub reason: String,
+}
+
+impl From<ModelIndexProto> for ModelIndex {
+    fn from(index: ModelIndexProto) -> Self {
+        Self {
+            state: ModelState::from(index.state.as_str()),
+            name: index.name,
+            version: index.version,
+            reason: index.reason,
+        }
+    }
+}
+
+/// A parameter of a load request. Triton understands `config`, a JSON model
+/// config overriding the one in the repository, and `file:<path>`, the
+/// contents of a model file such as `file:1/model.onnx`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepositoryParameter {
+    Bool(bool),
+    Int64(i64),
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+impl From<RepositoryParameter> for ModelRepositoryParameter {
+    fn from(parameter: RepositoryParameter) -> Self {
+        let choice = match parameter {
+            RepositoryParameter::Bool(value) => ParameterChoice::BoolParam(value),
+            RepositoryParameter::Int64(value) => ParameterChoice::Int64Param(value),
+            RepositoryParameter::String(value) => ParameterChoice::StringParam(value),
+            RepositoryParameter::Bytes(value) => ParameterChoice::BytesParam(value),
+        };
+
+        ModelRepositoryParameter {
+            parameter_choice: Some(choice),
+        }
+    }
+}
@@ -0,0 +1,603 @@
+use crate::OutputData;
+use crate::shm::{SHARED_MEMORY_BYTE_SIZE, SHARED_MEMORY_OFFSET, SHARED_MEMORY_REGION};
+use anyhow::{Result, bail};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use triton_grpc_client::inference::infer_parameter::ParameterChoice;
+use triton_grpc_client::inference::model_infer_request::{
+    InferInputTensor, InferRequestedOutputTensor,
+};
+use triton_grpc_client::inference::{InferParameter, InferTensorContents, ModelInferRequest};
+use triton_types::{
+    DataType, TensorError, TensorSink, TensorSource, TensorSpec, TensorValue, TritonInputs,
+    TritonOutputs, element_count,
+};
+
+/// A request or tensor parameter, e.g. `priority`, `timeout` or the
+/// `sequence_*` parameters of stateful models.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Parameter {
+    Bool(bool),
+    Int64(i64),
+    Uint64(u64),
+    Double(f64),
+    String(String),
+}
+
+macro_rules! parameter_from {
+    ($ty:ty, $variant:ident) => {
+        impl From<$ty> for Parameter {
+            fn from(value: $ty) -> Self {
+                Parameter::$variant(value.into())
+            }
+        }
+    };
+}
+
+parameter_from!(bool, Bool);
+parameter_from!(i32, Int64);
+parameter_from!(i64, Int64);
+parameter_from!(u32, Uint64);
+parameter_from!(u64, Uint64);
+parameter_from!(f64, Double);
+parameter_from!(&str, String);
+parameter_from!(String, String);
+
+impl Parameter {
+    pub(crate) fn from_proto(parameter: &InferParameter) -> Option<Self> {
+        Some(match parameter.parameter_choice.clone()? {
+            ParameterChoice::BoolParam(value) => Parameter::Bool(value),
+            ParameterChoice::Int64Param(value) => Parameter::Int64(value),
+            ParameterChoice::Uint64Param(value) => Parameter::Uint64(value),
+            ParameterChoice::DoubleParam(value) => Parameter::Double(value),
+            ParameterChoice::StringParam(value) => Parameter::String(value),
+        })
+    }
+}
+
+impl From<Parameter> for InferParameter {
+    fn from(parameter: Parameter) -> Self {
+        let choice = match parameter {
+            Parameter::Bool(value) => ParameterChoice::BoolParam(value),
+            Parameter::Int64(value) => ParameterChoice::Int64Param(value),
+            Parameter::Uint64(value) => ParameterChoice::Uint64Param(value),
+            Parameter::Double(value) => ParameterChoice::DoubleParam(value),
+            Parameter::String(value) => ParameterChoice::StringParam(value),
+        };
+
+        InferParameter {
+            parameter_choice: Some(choice),
+        }
+    }
+}
+
+struct Input {
+    name: String,
+    datatype: DataType,
+    shape: Vec<i64>,
+    count: usize,
+    data: Vec<u8>,
+    parameters: HashMap<String, Parameter>,
+}
+
+impl Input {
+    fn in_shared_memory(&self) -> bool {
+        self.parameters.contains_key(SHARED_MEMORY_REGION)
+    }
+}
+
+fn shared_memory(region: &str, offset: usize, byte_size: usize) -> HashMap<String, Parameter> {
+    HashMap::from([
+        (SHARED_MEMORY_REGION.to_string(), Parameter::from(region)),
+        (
+            SHARED_MEMORY_OFFSET.to_string(),
+            Parameter::Int64(offset as i64),
+        ),
+        (
+            SHARED_MEMORY_BYTE_SIZE.to_string(),
+            Parameter::Int64(byte_size as i64),
+        ),
+    ])
+}
+
+/// Builds a [`ModelInferRequest`]:
+///
+/// ```ignore
+/// let request = InferRequest::new("mnist")
+///     .input("Input3", &[1, 1, 28, 28], &data)
+///     .output("Plus214_Output_0")
+///     .param("priority", 1)
+///     .build()?;
+/// ```
+///
+/// Mistakes such as a shape that does not match the data are reported by
+/// [`InferRequest::build`].
+pub struct InferRequest {
+    model_name: String,
+    model_version: String,
+    id: String,
+    parameters: HashMap<String, Parameter>,
+    inputs: Vec<Input>,
+    outputs: Vec<InferRequestedOutputTensor>,
+    typed_contents: bool,
+    error: Option<anyhow::Error>,
+}
+
+impl InferRequest {
+    pub fn new(model_name: impl Into<String>) -> Self {
+        Self {
+            model_name: model_name.into(),
+            model_version: String::new(),
+            id: String::new(),
+            parameters: HashMap::new(),
+            inputs: vec![],
+            outputs: vec![],
+            typed_contents: false,
+            error: None,
+        }
+    }
+
+    /// Defaults to the version picked by the model's version policy.
+    pub fn version(mut self, model_version: impl Into<String>) -> Self {
+        self.model_version = model_version.into();
+        self
+    }
+
+    /// An identifier the server copies into the response.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<Parameter>) -> Self {
+        self.parameters.insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds an input. `shape` must match the number of elements in `data`.
+    pub fn input<V: TensorValue>(
+        mut self,
+        name: impl Into<String>,
+        shape: &[i64],
+        data: &V,
+    ) -> Self {
+        if let Err(error) = self.push_input(
+            name.into(),
+            V::DATATYPE,
+            shape,
+            data.element_count(),
+            data.encode(),
+        ) {
+            self.fail(error);
+        }
+        self
+    }
+
+    /// Adds the inputs of a `#[derive(TritonInputs)]` struct.
+    pub fn inputs<I: TritonInputs>(mut self, inputs: &I) -> Self {
+        if let Err(error) = inputs.write_inputs(&mut self) {
+            self.fail(error.into());
+        }
+        self
+    }
+
+    /// Adds an input whose `byte_size` bytes of data the server reads from
+    /// a registered shared memory region, e.g. written with
+    /// [`SharedMemoryRegion::write`](crate::SharedMemoryRegion::write).
+    pub fn shm_input(
+        mut self,
+        name: impl Into<String>,
+        datatype: DataType,
+        shape: &[i64],
+        region: &str,
+        offset: usize,
+        byte_size: usize,
+    ) -> Self {
+        let count = element_count(shape).unwrap_or_default();
+        match self.push_input(name.into(), datatype, shape, count, vec![]) {
+            Ok(()) => {
+                let input = self.inputs.last_mut().expect("input was just added");
+                input.parameters = shared_memory(region, offset, byte_size);
+            }
+            Err(error) => self.fail(error),
+        }
+        self
+    }
+
+    fn push_input(
+        &mut self,
+        name: String,
+        datatype: DataType,
+        shape: &[i64],
+        count: usize,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        if element_count(shape) != Some(count) {
+            bail!("Input '{name}' has shape {shape:?} but {count} elements");
+        }
+        if self.inputs.iter().any(|input| input.name == name) {
+            bail!("Input '{name}' is added twice");
+        }
+
+        self.inputs.push(Input {
+            name,
+            datatype,
+            shape: shape.to_vec(),
+            count,
+            data,
+            parameters: HashMap::new(),
+        });
+        Ok(())
+    }
+
+    /// Requests an output. Without any, the server returns all outputs.
+    pub fn output(self, name: impl Into<String>) -> Self {
+        self.output_with(name, HashMap::new())
+    }
+
+    /// Requests the top `count` classes of an output instead of its tensor.
+    /// Each class comes back as a BYTES element `"<score>:<index>[:<label>]"`.
+    pub fn classification(self, name: impl Into<String>, count: u32) -> Self {
+        let parameters = HashMap::from([("classification".to_string(), Parameter::from(count))]);
+        self.output_with(name, parameters)
+    }
+
+    /// Requests an output that the server writes into a registered shared
+    /// memory region instead of the response. Read it back with
+    /// [`SharedMemoryRegion::output`](crate::SharedMemoryRegion::output).
+    pub fn shm_output(
+        self,
+        name: impl Into<String>,
+        region: &str,
+        offset: usize,
+        byte_size: usize,
+    ) -> Self {
+        self.output_with(name, shared_memory(region, offset, byte_size))
+    }
+
+    pub fn output_with(
+        mut self,
+        name: impl Into<String>,
+        parameters: HashMap<String, Parameter>,
+    ) -> Self {
+        self.outputs.push(InferRequestedOutputTensor {
+            name: name.into(),
+            parameters: parameters
+                .into_iter()
+                .map(|(key, value)| (key, value.into()))
+                .collect(),
+        });
+        self
+    }
+
+    /// Requests the outputs of a `#[derive(TritonOutputs)]` struct.
+    pub fn outputs<O: TritonOutputs>(self) -> Self {
+        O::OUTPUTS
+            .iter()
+            .fold(self, |request, spec| request.output(spec.name))
+    }
+
+    /// Sends inputs in the typed `contents` fields rather than
+    /// `raw_input_contents`. FP16 and BF16 inputs can only be sent raw.
+    pub fn typed_contents(mut self, typed: bool) -> Self {
+        self.typed_contents = typed;
+        self
+    }
+
+    pub fn model_name(&self) -> &str {
+        &self.model_name
+    }
+
+    pub fn model_version(&self) -> &str {
+        &self.model_version
+    }
+
+    pub fn request_id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn parameter(&self, key: &str) -> Option<&Parameter> {
+        self.parameters.get(key)
+    }
+
+    pub fn input_names(&self) -> impl Iterator<Item = &str> {
+        self.inputs.iter().map(|input| input.name.as_str())
+    }
+
+    /// Names of the requested outputs, empty if all are requested.
+    pub fn output_names(&self) -> impl Iterator<Item = &str> {
+        self.outputs.iter().map(|output| output.name.as_str())
+    }
+
+    fn fail(&mut self, error: anyhow::Error) {
+        self.error.get_or_insert(error);
+    }
+
+    pub fn build(self) -> Result<ModelInferRequest> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if self.model_name.is_empty() {
+            bail!("Model name must not be empty");
+        }
+
+        let mut request = ModelInferRequest {
+            model_name: self.model_name,
+            model_version: self.model_version,
+            id: self.id,
+            parameters: self
+                .parameters
+                .into_iter()
+                .map(|(key, value)| (key, value.into()))
+                .collect(),
+            outputs: self.outputs,
+            ..Default::default()
+        };
+
+        for input in self.inputs {
+            let contents = if input.in_shared_memory() {
+                None
+            } else if self.typed_contents {
+                Some(to_contents(&input)?)
+            } else {
+                request.raw_input_contents.push(input.data.into());
+                None
+            };
+
+            request.inputs.push(InferInputTensor {
+                name: input.name,
+                datatype: input.datatype.to_string(),
+                shape: input.shape,
+                parameters: input
+                    .parameters
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect(),
+                contents,
+            });
+        }
+
+        Ok(request)
+    }
+}
+
+fn to_contents(input: &Input) -> Result<InferTensorContents> {
+    let mut contents = InferTensorContents::default();
+
+    match OutputData::decode(input.datatype, &input.data, input.count)? {
+        OutputData::BOOL(values) => contents.bool_contents = values,
+        OutputData::UINT8(values) => contents.uint_contents = widen(values),
+        OutputData::UINT16(values) => contents.uint_contents = widen(values),
+        OutputData::UINT32(values) => contents.uint_contents = values,
+        OutputData::UINT64(values) => contents.uint64_contents = values,
+        OutputData::INT8(values) => contents.int_contents = widen(values),
+        OutputData::INT16(values) => contents.int_contents = widen(values),
+        OutputData::INT32(values) => contents.int_contents = values,
+        OutputData::INT64(values) => contents.int64_contents = values,
+        OutputData::FP32(values) => contents.fp32_contents = values,
+        OutputData::FP64(values) => contents.fp64_contents = values,
+        OutputData::Bytes(values) => contents.bytes_contents = values,
+        _ => bail!(
+            "Input '{}' is {}, which has no typed contents",
+            input.name,
+            input.datatype
+        ),
+    }
+
+    Ok(contents)
+}
+
+fn widen<S, T: From<S>>(values: Vec<S>) -> Vec<T> {
+    values.into_iter().map(T::from).collect()
+}
+
+/// Reads inputs back, e.g. in a fake [`InferenceClient`](crate::InferenceClient)
+/// with `I::read_inputs(&request)`.
+impl TensorSource for InferRequest {
+    fn tensor_bytes(&self, spec: &TensorSpec) -> Result<Cow<'_, [u8]>, TensorError> {
+        let input = self
+            .inputs
+            .iter()
+            .find(|input| input.name == spec.name)
+            .ok_or_else(|| TensorError::new(format!("Missing input '{}'", spec.name)))?;
+
+        if input.datatype != spec.datatype {
+            return Err(TensorError::new(format!(
+                "Input '{}' is {}, not {}",
+                spec.name, input.datatype, spec.datatype
+            )));
+        }
+        if input.in_shared_memory() {
+            return Err(TensorError::new(format!(
+                "Input '{}' is in shared memory",
+                spec.name
+            )));
+        }
+
+        Ok(Cow::Borrowed(&input.data))
+    }
+}
+
+impl TensorSink for InferRequest {
+    fn write_tensor(
+        &mut self,
+        spec: &TensorSpec,
+        shape: &[i64],
+        bytes: Vec<u8>,
+    ) -> Result<(), TensorError> {
+        let count = element_count(shape).ok_or_else(|| {
+            TensorError::new(format!("Invalid shape {shape:?} of input '{}'", spec.name))
+        })?;
+
+        self.push_input(spec.name.to_string(), spec.datatype, shape, count, bytes)
+            .map_err(|error| TensorError::new(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_raw_inputs() {
+        let request = InferRequest::new("mnist")
+            .version("2")
+            .id("42")
+            .param("priority", 1)
+            .input("IMAGE", &[1, 2], &vec![0.5f32, -1.0])
+            .input("LABEL", &[1], &vec!["cat".to_string()])
+            .output("SCORES")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            (request.model_name.as_str(), request.model_version.as_str()),
+            ("mnist", "2")
+        );
+        assert_eq!(request.id, "42");
+        assert_eq!(
+            Parameter::from_proto(&request.parameters["priority"]),
+            Some(Parameter::Int64(1))
+        );
+        assert_eq!(request.inputs[0].datatype, "FP32");
+        assert_eq!(request.inputs[0].shape, [1, 2]);
+        assert_eq!(request.inputs[0].contents, None);
+        assert_eq!(request.inputs[1].datatype, "BYTES");
+        assert_eq!(
+            request.raw_input_contents,
+            [
+                [0.5f32, -1.0].map(f32::to_le_bytes).concat(),
+                [&3u32.to_le_bytes()[..], b"cat"].concat(),
+            ]
+        );
+        assert_eq!(request.outputs[0].name, "SCORES");
+    }
+
+    #[test]
+    fn builds_typed_contents() {
+        let request = InferRequest::new("model")
+            .input("A", &[2], &vec![-3i8, 4])
+            .input("B", &[1], &vec![u64::MAX])
+            .input("C", &[2], &vec![true, false])
+            .input("D", &[1], &vec!["cat".to_string()])
+            .typed_contents(true)
+            .build()
+            .unwrap();
+
+        assert!(request.raw_input_contents.is_empty());
+        let contents = |index: usize| request.inputs[index].contents.clone().unwrap();
+        assert_eq!(contents(0).int_contents, [-3, 4]);
+        assert_eq!(contents(1).uint64_contents, [u64::MAX]);
+        assert_eq!(contents(2).bool_contents, [true, false]);
+        assert_eq!(contents(3).bytes_contents, [b"cat".to_vec()]);
+    }
+
+    #[test]
+    fn typed_contents_reject_half_floats() {
+        let spec = TensorSpec {
+            name: "HALF",
+            datatype: DataType::Fp16,
+            shape: &[-1],
+        };
+        let mut request = InferRequest::new("model").typed_contents(true);
+        request.write_tensor(&spec, &[1], vec![0, 0x3c]).unwrap();
+
+        let error = request.build().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Input 'HALF' is FP16, which has no typed contents"
+        );
+    }
+
+    #[test]
+    fn keeps_the_first_error_until_build() {
+        let request = InferRequest::new("model")
+            .input("A", &[3], &vec![1i32, 2])
+            .input("B", &[1], &vec![1i32])
+            .input("B", &[1], &vec![2i32])
+            .output("OUT");
+
+        assert_eq!(request.input_names().collect::<Vec<_>>(), ["B"]);
+        let error = request.build().unwrap_err();
+        assert_eq!(error.to_string(), "Input 'A' has shape [3] but 2 elements");
+
+        let error = InferRequest::new("model")
+            .input("B", &[1], &vec![1i32])
+            .input("B", &[1], &vec![2i32])
+            .build()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Input 'B' is added twice");
+
+        assert!(InferRequest::new("").build().is_err());
+        assert!(
+            InferRequest::new("model")
+                .input("A", &[-1], &vec![1i32])
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn shared_memory_inputs_and_outputs() {
+        let request = InferRequest::new("model")
+            .shm_input("IN", DataType::Fp32, &[2, 2], "inputs", 64, 16)
+            .input("RAW", &[1], &vec![7u8])
+            .shm_output("OUT", "outputs", 0, 32)
+            .typed_contents(true)
+            .build()
+            .unwrap();
+
+        let input = &request.inputs[0];
+        assert_eq!(input.contents, None);
+        assert_eq!(
+            Parameter::from_proto(&input.parameters[SHARED_MEMORY_REGION]),
+            Some(Parameter::from("inputs"))
+        );
+        assert_eq!(
+            Parameter::from_proto(&input.parameters[SHARED_MEMORY_OFFSET]),
+            Some(Parameter::Int64(64))
+        );
+        assert_eq!(
+            Parameter::from_proto(&input.parameters[SHARED_MEMORY_BYTE_SIZE]),
+            Some(Parameter::Int64(16))
+        );
+        assert_eq!(
+            request.inputs[1].contents.clone().unwrap().uint_contents,
+            [7]
+        );
+        assert_eq!(
+            Parameter::from_proto(&request.outputs[0].parameters[SHARED_MEMORY_REGION]),
+            Some(Parameter::from("outputs"))
+        );
+
+        let error = InferRequest::new("model")
+            .shm_input("IN", DataType::Fp32, &[-1], "inputs", 0, 16)
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("'IN'"), "{error}");
+    }
+
+    #[test]
+    fn reads_inputs_back() {
+        let spec = TensorSpec {
+            name: "IMAGE",
+            datatype: DataType::Fp32,
+            shape: &[-1],
+        };
+        let mut request =
+            InferRequest::new("model").shm_input("SHM", DataType::Fp32, &[1], "inputs", 0, 4);
+        spec.write(&mut request, &vec![1.0f32, 2.0]).unwrap();
+
+        assert_eq!(spec.read::<Vec<f32>, _>(&request).unwrap(), [1.0, 2.0]);
+        let wrong_type = TensorSpec {
+            datatype: DataType::Int32,
+            ..spec
+        };
+        assert!(wrong_type.read::<Vec<i32>, _>(&request).is_err());
+        let in_shm = TensorSpec {
+            name: "SHM",
+            ..spec
+        };
+        assert!(in_shm.read::<Vec<f32>, _>(&request).is_err());
+    }
+}
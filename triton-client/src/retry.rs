@@ -0,0 +1,362 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tonic::{Code, Request, Response, Status};
+
+/// A failed attempt that is about to be retried.
+#[derive(Debug)]
+pub struct RetryEvent<'a> {
+    /// The RPC, e.g. `model_infer`.
+    pub rpc: &'static str,
+    /// The attempt that failed, starting at 1.
+    pub attempt: u32,
+    pub status: &'a Status,
+    /// How long the client waits before the next attempt.
+    pub backoff: Duration,
+}
+
+type RetryHook = Arc<dyn Fn(&RetryEvent<'_>) + Send + Sync>;
+
+/// When and how often [`TritonClient`] retries failed RPCs:
+///
+/// ```ignore
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .backoff(Duration::from_millis(50), Duration::from_secs(1))
+///     .budget(Duration::from_secs(10))
+///     .on_retry(|event| eprintln!("{} failed: {}", event.rpc, event.status));
+/// ```
+///
+/// Read-only RPCs and settings updates are retried. Loading and unloading
+/// models and streams never are, and inference only with
+/// [`RetryPolicy::inference`].
+///
+/// [`TritonClient`]: crate::TritonClient
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    codes: Vec<Code>,
+    budget: Option<Duration>,
+    inference: bool,
+    on_retry: Option<RetryHook>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .field("codes", &self.codes)
+            .field("budget", &self.budget)
+            .field("inference", &self.inference)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RetryPolicy {
+    /// Three attempts on `UNAVAILABLE`, backing off from 100 ms up to 2 s.
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            multiplier: 2.0,
+            jitter: true,
+            codes: vec![Code::Unavailable],
+            budget: None,
+            inference: false,
+            on_retry: None,
+        }
+    }
+
+    /// Sends every RPC exactly once.
+    pub fn disabled() -> Self {
+        Self::new().max_attempts(1)
+    }
+
+    /// Attempts in total, including the first one.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Waits `initial` after the first failure, growing up to `max`.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Factor by which the backoff grows after each failure.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Randomizes each backoff to between half and all of its length, so
+    /// that many clients do not retry in lockstep.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Status codes worth retrying. Defaults to `UNAVAILABLE`, which Triton
+    /// returns while a model is reloading.
+    pub fn codes(mut self, codes: impl IntoIterator<Item = Code>) -> Self {
+        self.codes = codes.into_iter().collect();
+        self
+    }
+
+    /// Limits the total time of a call across all attempts. Each attempt
+    /// is sent with the remaining time as its deadline.
+    pub fn budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Also retries inference. Only safe if running a request twice has no
+    /// side effects, which is not the case for sequence models.
+    pub fn inference(mut self, retry: bool) -> Self {
+        self.inference = retry;
+        self
+    }
+
+    /// Called before every retry.
+    pub fn on_retry(mut self, hook: impl Fn(&RetryEvent<'_>) + Send + Sync + 'static) -> Self {
+        self.on_retry = Some(Arc::new(hook));
+        self
+    }
+
+    pub(crate) fn retries_inference(&self) -> bool {
+        self.inference
+    }
+
+    /// Runs `call` until it succeeds, fails with a status that is not
+    /// retryable, or runs out of attempts or budget. With `retry` unset it
+    /// runs once. Every attempt but the last sends a clone of `request`,
+    /// which for inference shares the raw input data rather than copying it.
+    pub(crate) async fn run<Req, Res, F, Fut>(
+        &self,
+        rpc: &'static str,
+        retry: bool,
+        request: Req,
+        mut call: F,
+    ) -> Result<Res, Status>
+    where
+        Req: Clone,
+        F: FnMut(Request<Req>) -> Fut,
+        Fut: Future<Output = Result<Response<Res>, Status>>,
+    {
+        let start = Instant::now();
+        let attempts = if retry { self.max_attempts } else { 1 };
+        let mut backoff = self.initial_backoff.min(self.max_backoff);
+        let mut request = Some(request);
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let message = if attempt < attempts {
+                request.clone()
+            } else {
+                request.take()
+            };
+            let mut message =
+                Request::new(message.expect("request is kept until the last attempt"));
+            if let Some(budget) = self.budget {
+                message.set_timeout(budget.saturating_sub(start.elapsed()));
+            }
+
+            let status = match call(message).await {
+                Ok(response) => return Ok(response.into_inner()),
+                Err(status) => status,
+            };
+            if attempt >= attempts || !self.codes.contains(&status.code()) {
+                return Err(status);
+            }
+
+            let delay = self.jittered(backoff, random(attempt));
+            if let Some(budget) = self.budget
+                && start.elapsed() + delay >= budget
+            {
+                return Err(status);
+            }
+
+            if let Some(hook) = &self.on_retry {
+                hook(&RetryEvent {
+                    rpc,
+                    attempt,
+                    status: &status,
+                    backoff: delay,
+                });
+            }
+            tokio::time::sleep(delay).await;
+            backoff = self.grown(backoff);
+        }
+    }
+
+    fn grown(&self, backoff: Duration) -> Duration {
+        scaled(backoff, self.multiplier)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    /// `random` is in `0.0..=1.0`.
+    fn jittered(&self, backoff: Duration, random: f64) -> Duration {
+        if !self.jitter {
+            return backoff;
+        }
+
+        scaled(backoff, 0.5 + random / 2.0).unwrap_or(backoff)
+    }
+}
+
+/// `duration * factor`, or `None` if that does not fit in a `Duration`.
+fn scaled(duration: Duration, factor: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(duration.as_secs_f64() * factor).ok()
+}
+
+fn random(attempt: u32) -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(attempt);
+    hasher.finish() as f64 / u64::MAX as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Runs a call that fails with `code` every time, returning the backoffs
+    /// the policy reported and the number of attempts.
+    async fn failing(policy: &RetryPolicy, code: Code) -> (Vec<Duration>, u32) {
+        let backoffs = Arc::new(Mutex::new(vec![]));
+        let policy = policy.clone().on_retry({
+            let backoffs = Arc::clone(&backoffs);
+            move |event| backoffs.lock().unwrap().push(event.backoff)
+        });
+
+        let mut attempts = 0;
+        let result = policy
+            .run("model_infer", true, (), |_: Request<()>| {
+                attempts += 1;
+                async move { Err::<Response<()>, _>(Status::new(code, "down")) }
+            })
+            .await;
+        assert_eq!(result.unwrap_err().code(), code);
+
+        let backoffs = backoffs.lock().unwrap().clone();
+        (backoffs, attempts)
+    }
+
+    #[tokio::test]
+    async fn backoff_grows_up_to_the_maximum() {
+        let ms = Duration::from_millis;
+        let policy = RetryPolicy::new()
+            .max_attempts(5)
+            .backoff(ms(1), ms(3))
+            .jitter(false);
+
+        assert_eq!(
+            failing(&policy, Code::Unavailable).await,
+            (vec![ms(1), ms(2), ms(3), ms(3)], 5)
+        );
+        assert_eq!(failing(&policy, Code::InvalidArgument).await, (vec![], 1));
+    }
+
+    #[tokio::test]
+    async fn initial_backoff_is_capped() {
+        let policy = RetryPolicy::new()
+            .max_attempts(2)
+            .backoff(Duration::from_secs(60), Duration::from_millis(1))
+            .jitter(false);
+
+        assert_eq!(
+            failing(&policy, Code::Unavailable).await,
+            (vec![Duration::from_millis(1)], 2)
+        );
+    }
+
+    #[test]
+    fn growth_saturates_at_the_maximum() {
+        let policy = RetryPolicy::new()
+            .backoff(Duration::from_secs(1), Duration::MAX)
+            .multiplier(f64::MAX);
+
+        assert_eq!(policy.grown(Duration::from_secs(1)), Duration::MAX);
+        assert_eq!(policy.grown(Duration::MAX), Duration::MAX);
+
+        let policy = policy.multiplier(f64::NAN);
+        assert_eq!(policy.grown(Duration::from_secs(1)), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_stays_between_half_and_all_of_the_backoff() {
+        let policy = RetryPolicy::new();
+        let backoff = Duration::from_millis(100);
+
+        assert_eq!(policy.jittered(backoff, 0.0), Duration::from_millis(50));
+        assert_eq!(policy.jittered(backoff, 1.0), backoff);
+        for attempt in 0..100 {
+            let delay = policy.jittered(backoff, random(attempt));
+            assert!(delay >= backoff / 2 && delay <= backoff, "{delay:?}");
+        }
+        assert_eq!(policy.jittered(Duration::MAX, 1.0), Duration::MAX);
+        assert_eq!(policy.clone().jitter(false).jittered(backoff, 0.0), backoff);
+    }
+
+    #[tokio::test]
+    async fn budget_limits_attempts_and_deadlines() {
+        let ms = Duration::from_millis;
+        let policy = RetryPolicy::new()
+            .max_attempts(100)
+            .backoff(ms(20), ms(20))
+            .jitter(false)
+            .budget(ms(50));
+
+        let start = Instant::now();
+        let (backoffs, attempts) = failing(&policy, Code::Unavailable).await;
+        // A slow machine may run out of budget one attempt earlier.
+        assert!((2..=3).contains(&attempts), "{attempts}");
+        assert_eq!(backoffs.len() as u32, attempts - 1);
+        assert!(start.elapsed() < ms(50));
+
+        let mut timeouts = vec![];
+        let _ = policy
+            .run("server_live", true, (), |request: Request<()>| {
+                timeouts.push(request.metadata().get("grpc-timeout").cloned());
+                async { Ok(Response::new(())) }
+            })
+            .await;
+        assert_eq!(timeouts.len(), 1);
+        assert!(timeouts[0].is_some());
+    }
+
+    #[tokio::test]
+    async fn runs_once_without_retry() {
+        let mut attempts = 0;
+        let result = RetryPolicy::new()
+            .run("load_model", false, (), |_: Request<()>| {
+                attempts += 1;
+                async { Err::<Response<()>, _>(Status::unavailable("down")) }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
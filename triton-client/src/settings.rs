@@ -0,0 +1,310 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::str::FromStr;
+use triton_grpc_client::inference::log_settings_request::SettingValue as LogValueRequest;
+use triton_grpc_client::inference::log_settings_request::setting_value::ParameterChoice as LogRequestChoice;
+use triton_grpc_client::inference::log_settings_response::SettingValue as LogValueResponse;
+use triton_grpc_client::inference::log_settings_response::setting_value::ParameterChoice as LogResponseChoice;
+use triton_grpc_client::inference::trace_setting_request::SettingValue as TraceValueRequest;
+use triton_grpc_client::inference::trace_setting_response::SettingValue as TraceValueResponse;
+
+/// Trace settings, either global or for one model. Fields left as `None` are
+/// not changed by an update.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceSettings {
+    /// `OFF`, `TIMESTAMPS` and/or `TENSORS`.
+    pub trace_level: Option<Vec<String>>,
+    pub trace_rate: Option<u32>,
+    /// `-1` traces without limit.
+    pub trace_count: Option<i32>,
+    pub log_frequency: Option<u32>,
+    pub trace_file: Option<String>,
+    /// `triton` or `opentelemetry`.
+    pub trace_mode: Option<String>,
+    /// Settings this type has no field for, such as `trace_config`.
+    pub other: HashMap<String, Vec<String>>,
+}
+
+impl TraceSettings {
+    pub(crate) fn from_response(settings: HashMap<String, TraceValueResponse>) -> Result<Self> {
+        let mut trace = TraceSettings::default();
+
+        for (key, value) in settings {
+            let values = value.value;
+            match key.as_str() {
+                "trace_level" => trace.trace_level = Some(values),
+                "trace_rate" => trace.trace_rate = parse_single(&key, &values)?,
+                "trace_count" => trace.trace_count = parse_single(&key, &values)?,
+                "log_frequency" => trace.log_frequency = parse_single(&key, &values)?,
+                "trace_file" => trace.trace_file = values.into_iter().next(),
+                "trace_mode" => trace.trace_mode = values.into_iter().next(),
+                _ => {
+                    trace.other.insert(key, values);
+                }
+            }
+        }
+
+        Ok(trace)
+    }
+
+    pub(crate) fn to_request(&self) -> HashMap<String, TraceValueRequest> {
+        let mut settings: HashMap<String, Vec<String>> = self.other.clone();
+
+        let fields = [
+            ("trace_level", self.trace_level.clone()),
+            ("trace_rate", self.trace_rate.map(|v| vec![v.to_string()])),
+            ("trace_count", self.trace_count.map(|v| vec![v.to_string()])),
+            (
+                "log_frequency",
+                self.log_frequency.map(|v| vec![v.to_string()]),
+            ),
+            ("trace_file", self.trace_file.clone().map(|v| vec![v])),
+            ("trace_mode", self.trace_mode.clone().map(|v| vec![v])),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                settings.insert(key.to_string(), value);
+            }
+        }
+
+        settings
+            .into_iter()
+            .map(|(key, value)| (key, TraceValueRequest { value }))
+            .collect()
+    }
+}
+
+fn parse_single<T: FromStr>(key: &str, values: &[String]) -> Result<Option<T>>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    values
+        .first()
+        .map(|value| value.parse::<T>())
+        .transpose()
+        .with_context(|| format!("Invalid value for trace setting '{key}': {values:?}"))
+}
+
+/// Server log settings. Fields left as `None` are not changed by an update.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogSettings {
+    pub log_file: Option<String>,
+    pub log_info: Option<bool>,
+    pub log_warning: Option<bool>,
+    pub log_error: Option<bool>,
+    pub log_verbose_level: Option<u32>,
+    /// `default` or `ISO8601`.
+    pub log_format: Option<String>,
+}
+
+impl LogSettings {
+    pub(crate) fn from_response(settings: HashMap<String, LogValueResponse>) -> Result<Self> {
+        let mut log = LogSettings::default();
+
+        for (key, value) in settings {
+            let Some(value) = value.parameter_choice else {
+                continue;
+            };
+            let mismatch = || anyhow::anyhow!("Unexpected value for log setting '{key}'");
+
+            match (key.as_str(), value) {
+                ("log_file", LogResponseChoice::StringParam(value)) => log.log_file = Some(value),
+                ("log_info", LogResponseChoice::BoolParam(value)) => log.log_info = Some(value),
+                ("log_warning", LogResponseChoice::BoolParam(value)) => {
+                    log.log_warning = Some(value)
+                }
+                ("log_error", LogResponseChoice::BoolParam(value)) => log.log_error = Some(value),
+                ("log_verbose_level", LogResponseChoice::Uint32Param(value)) => {
+                    log.log_verbose_level = Some(value)
+                }
+                ("log_format", LogResponseChoice::StringParam(value)) => {
+                    log.log_format = Some(value)
+                }
+                (
+                    "log_file" | "log_info" | "log_warning" | "log_error" | "log_verbose_level"
+                    | "log_format",
+                    _,
+                ) => return Err(mismatch()),
+                _ => {}
+            }
+        }
+
+        Ok(log)
+    }
+
+    pub(crate) fn to_request(&self) -> HashMap<String, LogValueRequest> {
+        let fields = [
+            (
+                "log_file",
+                self.log_file.clone().map(LogRequestChoice::StringParam),
+            ),
+            ("log_info", self.log_info.map(LogRequestChoice::BoolParam)),
+            (
+                "log_warning",
+                self.log_warning.map(LogRequestChoice::BoolParam),
+            ),
+            ("log_error", self.log_error.map(LogRequestChoice::BoolParam)),
+            (
+                "log_verbose_level",
+                self.log_verbose_level.map(LogRequestChoice::Uint32Param),
+            ),
+            (
+                "log_format",
+                self.log_format.clone().map(LogRequestChoice::StringParam),
+            ),
+        ];
+
+        fields
+            .into_iter()
+            .filter_map(|(key, value)| {
+                value.map(|value| {
+                    (
+                        key.to_string(),
+                        LogValueRequest {
+                            parameter_choice: Some(value),
+                        },
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace_response(settings: &[(&str, &[&str])]) -> HashMap<String, TraceValueResponse> {
+        settings
+            .iter()
+            .map(|(key, values)| {
+                let value = values.iter().map(|value| value.to_string()).collect();
+                (key.to_string(), TraceValueResponse { value })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn trace_settings_from_response() {
+        let trace = TraceSettings::from_response(trace_response(&[
+            ("trace_level", &["TIMESTAMPS", "TENSORS"]),
+            ("trace_rate", &["1000"]),
+            ("trace_count", &["-1"]),
+            ("log_frequency", &[]),
+            ("trace_file", &["/tmp/trace.json"]),
+            ("trace_mode", &["triton"]),
+            ("trace_config", &["opentelemetry,url=localhost"]),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            trace,
+            TraceSettings {
+                trace_level: Some(vec!["TIMESTAMPS".to_string(), "TENSORS".to_string()]),
+                trace_rate: Some(1000),
+                trace_count: Some(-1),
+                log_frequency: None,
+                trace_file: Some("/tmp/trace.json".to_string()),
+                trace_mode: Some("triton".to_string()),
+                other: HashMap::from([(
+                    "trace_config".to_string(),
+                    vec!["opentelemetry,url=localhost".to_string()],
+                )]),
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_trace_setting() {
+        let error = TraceSettings::from_response(trace_response(&[("trace_rate", &["often"])]))
+            .unwrap_err();
+        assert!(error.to_string().contains("'trace_rate'"), "{error}");
+    }
+
+    #[test]
+    fn trace_settings_to_request() {
+        let trace = TraceSettings {
+            trace_rate: Some(10),
+            trace_mode: Some("opentelemetry".to_string()),
+            other: HashMap::from([("trace_config".to_string(), vec!["a=b".to_string()])]),
+            ..Default::default()
+        };
+
+        let request = trace.to_request();
+        let values = |key: &str| request.get(key).map(|value| value.value.clone());
+        assert_eq!(request.len(), 3);
+        assert_eq!(values("trace_rate"), Some(vec!["10".to_string()]));
+        assert_eq!(
+            values("trace_mode"),
+            Some(vec!["opentelemetry".to_string()])
+        );
+        assert_eq!(values("trace_config"), Some(vec!["a=b".to_string()]));
+    }
+
+    fn log_response(settings: Vec<(&str, LogResponseChoice)>) -> HashMap<String, LogValueResponse> {
+        settings
+            .into_iter()
+            .map(|(key, value)| {
+                let value = LogValueResponse {
+                    parameter_choice: Some(value),
+                };
+                (key.to_string(), value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn log_settings_from_response() {
+        let log = LogSettings::from_response(log_response(vec![
+            ("log_file", LogResponseChoice::StringParam(String::new())),
+            ("log_info", LogResponseChoice::BoolParam(true)),
+            ("log_warning", LogResponseChoice::BoolParam(false)),
+            ("log_error", LogResponseChoice::BoolParam(true)),
+            ("log_verbose_level", LogResponseChoice::Uint32Param(2)),
+            (
+                "log_format",
+                LogResponseChoice::StringParam("ISO8601".into()),
+            ),
+            ("log_future", LogResponseChoice::BoolParam(true)),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            log,
+            LogSettings {
+                log_file: Some(String::new()),
+                log_info: Some(true),
+                log_warning: Some(false),
+                log_error: Some(true),
+                log_verbose_level: Some(2),
+                log_format: Some("ISO8601".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn mistyped_log_setting() {
+        let response = log_response(vec![("log_info", LogResponseChoice::Uint32Param(1))]);
+        assert!(LogSettings::from_response(response).is_err());
+    }
+
+    #[test]
+    fn log_settings_to_request() {
+        let log = LogSettings {
+            log_verbose_level: Some(1),
+            log_error: Some(false),
+            ..Default::default()
+        };
+
+        let request = log.to_request();
+        assert_eq!(request.len(), 2);
+        assert_eq!(
+            request["log_verbose_level"].parameter_choice,
+            Some(LogRequestChoice::Uint32Param(1))
+        );
+        assert_eq!(
+            request["log_error"].parameter_choice,
+            Some(LogRequestChoice::BoolParam(false))
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use triton_grpc_client::inference::ModelInferResponse;
+use triton_grpc_client::inference::model_infer_request::InferRequestedOutputTensor;
+use triton_grpc_client::inference::model_infer_response::InferOutputTensor;
+use triton_grpc_client::inference::system_shared_memory_status_response::RegionStatus;
+#[cfg(unix)]
+use {
+    crate::{InferenceOutput, OutputData, TritonClient},
+    anyhow::{Context, Result, anyhow, bail},
+    std::collections::HashMap,
+    std::ffi::CString,
+    std::io,
+    std::mem::ManuallyDrop,
+    std::os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    std::ptr::NonNull,
+    triton_grpc_client::inference::InferParameter,
+    triton_grpc_client::inference::infer_parameter::ParameterChoice,
+    triton_types::{DataType, TensorValue, element_count},
+};
+
+pub(crate) const SHARED_MEMORY_REGION: &str = "shared_memory_region";
+pub(crate) const SHARED_MEMORY_OFFSET: &str = "shared_memory_offset";
+pub(crate) const SHARED_MEMORY_BYTE_SIZE: &str = "shared_memory_byte_size";
+
+/// A system shared memory region registered with the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedMemoryStatus {
+    pub name: String,
+    pub key: String,
+    pub offset: u64,
+    pub byte_size: u64,
+}
+
+impl From<RegionStatus> for SharedMemoryStatus {
+    fn from(status: RegionStatus) -> Self {
+        Self {
+            name: status.name,
+            key: status.key,
+            offset: status.offset,
+            byte_size: status.byte_size,
+        }
+    }
+}
+
+/// A POSIX shared memory object created with `shm_open` and mapped into
+/// this process. It is unmapped and unlinked when dropped.
+#[cfg(unix)]
+pub struct SharedMemory {
+    key: String,
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+// The mapping is owned by this value, and within this process only written
+// through `&mut self`. The server writes outputs into it from another
+// process at any time, so the safe accessors copy bytes in and out instead of
+// handing out slices that could change underneath their borrow.
+#[cfg(unix)]
+unsafe impl Send for SharedMemory {}
+#[cfg(unix)]
+unsafe impl Sync for SharedMemory {}
+
+#[cfg(unix)]
+impl SharedMemory {
+    /// Creates the object `key`, e.g. `/input_data`, with `byte_size` zeroed
+    /// bytes. Fails if it already exists.
+    pub fn create(key: &str, byte_size: usize) -> Result<Self> {
+        if byte_size == 0 {
+            bail!("Shared memory '{key}' must not be empty");
+        }
+        let name = CString::new(key)?;
+
+        let fd = unsafe {
+            libc::shm_open(
+                name.as_ptr(),
+                libc::O_CREAT | libc::O_EXCL | libc::O_RDWR,
+                0o600,
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error())
+                .with_context(|| format!("Cannot create shared memory '{key}'"));
+        }
+        // The mapping stays valid after the descriptor is closed.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        match map(&fd, byte_size) {
+            Ok(ptr) => Ok(Self {
+                key: key.to_string(),
+                ptr,
+                len: byte_size,
+            }),
+            Err(error) => {
+                unsafe { libc::shm_unlink(name.as_ptr()) };
+                Err(error).with_context(|| format!("Cannot map shared memory '{key}'"))
+            }
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Copies `buf.len()` bytes at `offset` into `buf`.
+    pub fn read_into(&self, offset: usize, buf: &mut [u8]) -> Result<()> {
+        let range = self.range(offset, buf.len())?;
+        // SAFETY: `range` lies within the mapping, which cannot overlap `buf`.
+        unsafe {
+            let source = self.ptr.as_ptr().add(range.start);
+            std::ptr::copy_nonoverlapping(source, buf.as_mut_ptr(), buf.len());
+        }
+        Ok(())
+    }
+
+    /// Copies `bytes` into the memory at `offset`.
+    pub fn write_from(&mut self, offset: usize, bytes: &[u8]) -> Result<()> {
+        let range = self.range(offset, bytes.len())?;
+        // SAFETY: `range` lies within the mapping, which cannot overlap `bytes`.
+        unsafe {
+            let target = self.ptr.as_ptr().add(range.start);
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), target, bytes.len());
+        }
+        Ok(())
+    }
+
+    fn to_vec(&self, offset: usize, byte_size: usize) -> Result<Vec<u8>> {
+        let mut bytes = vec![0; byte_size];
+        self.read_into(offset, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// # Safety
+    ///
+    /// The server writes outputs into the memory behind this borrow, so no
+    /// inference that uses it may run while the slice is alive.
+    pub unsafe fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// # Safety
+    ///
+    /// No inference that uses the memory may run while the slice is alive.
+    pub unsafe fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    fn range(&self, offset: usize, byte_size: usize) -> Result<std::ops::Range<usize>> {
+        offset
+            .checked_add(byte_size)
+            .filter(|&end| end <= self.len)
+            .map(|end| offset..end)
+            .ok_or_else(|| {
+                anyhow!(
+                    "{byte_size} bytes at offset {offset} exceed shared memory '{}' of {} bytes",
+                    self.key,
+                    self.len
+                )
+            })
+    }
+}
+
+#[cfg(unix)]
+fn map(fd: &OwnedFd, len: usize) -> io::Result<NonNull<u8>> {
+    let size =
+        libc::off_t::try_from(len).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+    if unsafe { libc::ftruncate(fd.as_raw_fd(), size) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let ptr = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            fd.as_raw_fd(),
+            0,
+        )
+    };
+    if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+
+    NonNull::new(ptr.cast()).ok_or_else(|| io::Error::from(io::ErrorKind::OutOfMemory))
+}
+
+#[cfg(unix)]
+impl Drop for SharedMemory {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr.as_ptr().cast(), self.len);
+            if let Ok(name) = CString::new(self.key.as_str()) {
+                libc::shm_unlink(name.as_ptr());
+            }
+        }
+    }
+}
+
+/// [`SharedMemory`] registered with the server under a name that inputs
+/// and outputs refer to, see [`InferRequest::shm_input`] and
+/// [`InferRequest::shm_output`].
+///
+/// Release it with [`SharedMemoryRegion::unregister`], which reports whether
+/// the server let go of the region. Dropping it instead is best-effort: the
+/// unregister request is spawned on the current Tokio runtime, which keeps
+/// the memory mapped until the request completes, and a failure is only
+/// logged as a `tracing` warning. Without a runtime the region stays
+/// registered with the server, pointing at memory that has been unlinked.
+///
+/// [`InferRequest::shm_input`]: crate::InferRequest::shm_input
+/// [`InferRequest::shm_output`]: crate::InferRequest::shm_output
+#[cfg(unix)]
+pub struct SharedMemoryRegion {
+    name: String,
+    // Moved out in `drop`, into the task that unregisters the region.
+    memory: ManuallyDrop<SharedMemory>,
+    client: Option<TritonClient>,
+}
+
+#[cfg(unix)]
+impl SharedMemoryRegion {
+    pub(crate) fn new(name: &str, memory: SharedMemory, client: TritonClient) -> Self {
+        Self {
+            name: name.to_string(),
+            memory: ManuallyDrop::new(memory),
+            client: Some(client),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn memory(&self) -> &SharedMemory {
+        &self.memory
+    }
+
+    pub fn memory_mut(&mut self) -> &mut SharedMemory {
+        &mut self.memory
+    }
+
+    /// Writes a tensor at `offset` and returns its size in bytes.
+    pub fn write<V: TensorValue>(&mut self, offset: usize, data: &V) -> Result<usize> {
+        let bytes = data.encode();
+        self.memory.write_from(offset, &bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Reads `byte_size` bytes at `offset` as a tensor, copying them out of
+    /// the memory first.
+    pub fn read<V: TensorValue>(&self, offset: usize, byte_size: usize) -> Result<V> {
+        let bytes = self.memory.to_vec(offset, byte_size)?;
+        Ok(V::decode(&bytes)?)
+    }
+
+    /// Decodes output `name` of `response`, which the server wrote into this
+    /// region.
+    pub fn output(&self, response: &ModelInferResponse, name: &str) -> Result<InferenceOutput> {
+        let output = response
+            .outputs
+            .iter()
+            .find(|output| output.name == name)
+            .ok_or_else(|| anyhow!("Missing output '{name}'"))?;
+        let (region, offset, byte_size) = location(&output.parameters)
+            .ok_or_else(|| anyhow!("Output '{name}' is not in shared memory"))?;
+        if region != self.name {
+            bail!(
+                "Output '{name}' is in region '{region}', not '{}'",
+                self.name
+            );
+        }
+
+        let datatype = output.datatype.parse::<DataType>()?;
+        let count = element_count(&output.shape)
+            .ok_or_else(|| anyhow!("Invalid output shape {:?}", output.shape))?;
+
+        let bytes = self.memory.to_vec(offset, byte_size)?;
+        let length = match datatype.byte_size() {
+            Some(size) => count.saturating_mul(size),
+            None => bytes_length(&bytes, count)
+                .ok_or_else(|| anyhow!("Output '{name}' has fewer than {count} elements"))?,
+        };
+        if length > bytes.len() {
+            bail!("Output '{name}' needs {length} bytes, region holds {byte_size}");
+        }
+
+        let data = OutputData::decode(datatype, &bytes[..length], count)
+            .with_context(|| format!("Invalid data for output '{name}'"))?;
+
+        Ok(InferenceOutput {
+            name: output.name.clone(),
+            datatype,
+            shape: output.shape.clone(),
+            data,
+        })
+    }
+
+    /// Unregisters the region from the server, then releases the memory.
+    /// Prefer this to dropping the region, which cannot report errors.
+    pub async fn unregister(mut self) -> Result<()> {
+        match self.client.take() {
+            Some(client) => client.unregister_system_shared_memory(&self.name).await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SharedMemoryRegion {
+    fn drop(&mut self) {
+        // SAFETY: `memory` is not used again after this.
+        let memory = unsafe { ManuallyDrop::take(&mut self.memory) };
+        let Some(client) = self.client.take() else {
+            return;
+        };
+        let name = std::mem::take(&mut self.name);
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn(async move {
+                    if let Err(error) = client.unregister_system_shared_memory(&name).await {
+                        tracing::warn!(
+                            "Cannot unregister shared memory region '{name}': {error:#}"
+                        );
+                    }
+                    drop(memory);
+                });
+            }
+            Err(_) => tracing::warn!(
+                "Shared memory region '{name}' dropped outside a Tokio runtime, \
+                 call SharedMemoryRegion::unregister to unregister it"
+            ),
+        }
+    }
+}
+
+/// Length of the first `count` length-prefixed BYTES elements.
+#[cfg(unix)]
+fn bytes_length(bytes: &[u8], count: usize) -> Option<usize> {
+    let mut length = 0usize;
+    for _ in 0..count {
+        let prefix = bytes.get(length..length + 4)?;
+        let size = u32::from_le_bytes(prefix.try_into().unwrap()) as usize;
+        length = length.checked_add(4 + size)?;
+    }
+    (length <= bytes.len()).then_some(length)
+}
+
+/// The region, offset and byte size set on a tensor in shared memory.
+#[cfg(unix)]
+pub(crate) fn location(
+    parameters: &HashMap<String, InferParameter>,
+) -> Option<(&str, usize, usize)> {
+    let value = |key| match &parameters.get(key)?.parameter_choice {
+        Some(ParameterChoice::Int64Param(value)) => usize::try_from(*value).ok(),
+        Some(ParameterChoice::Uint64Param(value)) => usize::try_from(*value).ok(),
+        _ => None,
+    };
+
+    let region = match &parameters.get(SHARED_MEMORY_REGION)?.parameter_choice {
+        Some(ParameterChoice::StringParam(region)) => region.as_str(),
+        _ => return None,
+    };
+    Some((
+        region,
+        value(SHARED_MEMORY_OFFSET).unwrap_or(0),
+        value(SHARED_MEMORY_BYTE_SIZE)?,
+    ))
+}
+
+pub(crate) fn in_shared_memory(output: &InferOutputTensor) -> bool {
+    output.parameters.contains_key(SHARED_MEMORY_REGION)
+}
+
+/// Copies the shared memory parameters of the requested outputs onto the
+/// response, which does not always repeat them, so that the outputs can be
+/// told apart and read back.
+pub(crate) fn mark_outputs(
+    requested: &[InferRequestedOutputTensor],
+    response: &mut ModelInferResponse,
+) {
+    for requested in requested {
+        if !requested.parameters.contains_key(SHARED_MEMORY_REGION) {
+            continue;
+        }
+        let Some(output) = response
+            .outputs
+            .iter_mut()
+            .find(|output| output.name == requested.name)
+        else {
+            continue;
+        };
+
+        for key in [
+            SHARED_MEMORY_REGION,
+            SHARED_MEMORY_OFFSET,
+            SHARED_MEMORY_BYTE_SIZE,
+        ] {
+            if let Some(value) = requested.parameters.get(key) {
+                output
+                    .parameters
+                    .entry(key.to_string())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
+}
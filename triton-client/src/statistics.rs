@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use triton_grpc_client::inference;
+
+/// A counter together with the total time spent across all counted events.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatisticDuration {
+    pub count: u64,
+    pub total: Duration,
+}
+
+impl StatisticDuration {
+    pub fn average(&self) -> Option<Duration> {
+        u32::try_from(self.count)
+            .ok()
+            .filter(|&count| count > 0)
+            .map(|count| self.total / count)
+    }
+}
+
+impl From<Option<inference::StatisticDuration>> for StatisticDuration {
+    fn from(duration: Option<inference::StatisticDuration>) -> Self {
+        let duration = duration.unwrap_or_default();
+        Self {
+            count: duration.count,
+            total: Duration::from_nanos(duration.ns),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InferStatistics {
+    pub success: StatisticDuration,
+    pub fail: StatisticDuration,
+    pub queue: StatisticDuration,
+    pub compute_input: StatisticDuration,
+    pub compute_infer: StatisticDuration,
+    pub compute_output: StatisticDuration,
+    pub cache_hit: StatisticDuration,
+    pub cache_miss: StatisticDuration,
+}
+
+impl From<inference::InferStatistics> for InferStatistics {
+    fn from(stats: inference::InferStatistics) -> Self {
+        Self {
+            success: stats.success.into(),
+            fail: stats.fail.into(),
+            queue: stats.queue.into(),
+            compute_input: stats.compute_input.into(),
+            compute_infer: stats.compute_infer.into(),
+            compute_output: stats.compute_output.into(),
+            cache_hit: stats.cache_hit.into(),
+            cache_miss: stats.cache_miss.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InferBatchStatistics {
+    pub batch_size: u64,
+    pub compute_input: StatisticDuration,
+    pub compute_infer: StatisticDuration,
+    pub compute_output: StatisticDuration,
+}
+
+impl From<inference::InferBatchStatistics> for InferBatchStatistics {
+    fn from(stats: inference::InferBatchStatistics) -> Self {
+        Self {
+            batch_size: stats.batch_size,
+            compute_input: stats.compute_input.into(),
+            compute_infer: stats.compute_infer.into(),
+            compute_output: stats.compute_output.into(),
+        }
+    }
+}
+
+/// Per-response statistics of decoupled models.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InferResponseStatistics {
+    pub compute_infer: StatisticDuration,
+    pub compute_output: StatisticDuration,
+    pub success: StatisticDuration,
+    pub fail: StatisticDuration,
+    pub empty_response: StatisticDuration,
+    pub cancel: StatisticDuration,
+}
+
+impl From<inference::InferResponseStatistics> for InferResponseStatistics {
+    fn from(stats: inference::InferResponseStatistics) -> Self {
+        Self {
+            compute_infer: stats.compute_infer.into(),
+            compute_output: stats.compute_output.into(),
+            success: stats.success.into(),
+            fail: stats.fail.into(),
+            empty_response: stats.empty_response.into(),
+            cancel: stats.cancel.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// `CPU`, `CPU_PINNED` or `GPU`.
+    pub kind: String,
+    pub id: i64,
+    pub byte_size: u64,
+}
+
+impl From<inference::MemoryUsage> for MemoryUsage {
+    fn from(usage: inference::MemoryUsage) -> Self {
+        Self {
+            kind: usage.r#type,
+            id: usage.id,
+            byte_size: usage.byte_size,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelStatistics {
+    pub name: String,
+    pub version: String,
+    /// `None` if the model has not served a request yet.
+    pub last_inference: Option<SystemTime>,
+    /// Inferences performed, counting every batch element separately.
+    pub inference_count: u64,
+    /// Model executions, where one execution may cover a whole batch.
+    pub execution_count: u64,
+    pub inference_stats: InferStatistics,
+    pub batch_stats: Vec<InferBatchStatistics>,
+    pub memory_usage: Vec<MemoryUsage>,
+    pub response_stats: HashMap<String, InferResponseStatistics>,
+}
+
+impl From<inference::ModelStatistics> for ModelStatistics {
+    fn from(stats: inference::ModelStatistics) -> Self {
+        Self {
+            name: stats.name,
+            version: stats.version,
+            last_inference: (stats.last_inference > 0)
+                .then(|| SystemTime::UNIX_EPOCH + Duration::from_millis(stats.last_inference)),
+            inference_count: stats.inference_count,
+            execution_count: stats.execution_count,
+            inference_stats: stats.inference_stats.unwrap_or_default().into(),
+            batch_stats: stats.batch_stats.into_iter().map(Into::into).collect(),
+            memory_usage: stats.memory_usage.into_iter().map(Into::into).collect(),
+            response_stats: stats
+                .response_stats
+                .into_iter()
+                .map(|(key, stats)| (key, stats.into()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn duration(count: u64, ns: u64) -> Option<inference::StatisticDuration> {
+        Some(inference::StatisticDuration { count, ns })
+    }
+
+    #[test]
+    fn average() {
+        let duration = StatisticDuration::from(duration(4, 2_000));
+        assert_eq!(duration.total, Duration::from_micros(2));
+        assert_eq!(duration.average(), Some(Duration::from_nanos(500)));
+        assert_eq!(StatisticDuration::from(None).average(), None);
+
+        let huge = StatisticDuration {
+            count: u64::MAX,
+            total: Duration::from_secs(1),
+        };
+        assert_eq!(huge.average(), None);
+    }
+
+    #[test]
+    fn model_statistics_from_proto() {
+        let stats = ModelStatistics::from(inference::ModelStatistics {
+            name: "mnist".to_string(),
+            version: "1".to_string(),
+            last_inference: 1_500,
+            inference_count: 16,
+            execution_count: 2,
+            inference_stats: Some(inference::InferStatistics {
+                success: duration(2, 100),
+                queue: duration(2, 40),
+                ..Default::default()
+            }),
+            batch_stats: vec![inference::InferBatchStatistics {
+                batch_size: 8,
+                compute_infer: duration(2, 60),
+                ..Default::default()
+            }],
+            memory_usage: vec![inference::MemoryUsage {
+                r#type: "GPU".to_string(),
+                id: 1,
+                byte_size: 1024,
+            }],
+            response_stats: HashMap::from([(
+                "0".to_string(),
+                inference::InferResponseStatistics {
+                    empty_response: duration(1, 5),
+                    ..Default::default()
+                },
+            )]),
+        });
+
+        assert_eq!(stats.name, "mnist");
+        assert_eq!(
+            stats.last_inference,
+            Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1_500))
+        );
+        assert_eq!((stats.inference_count, stats.execution_count), (16, 2));
+        assert_eq!(
+            stats.inference_stats.success.total,
+            Duration::from_nanos(100)
+        );
+        assert_eq!(stats.inference_stats.queue.count, 2);
+        assert_eq!(stats.inference_stats.fail, StatisticDuration::default());
+        assert_eq!(stats.batch_stats[0].batch_size, 8);
+        assert_eq!(stats.batch_stats[0].compute_infer.count, 2);
+        assert_eq!(
+            stats.memory_usage,
+            [MemoryUsage {
+                kind: "GPU".to_string(),
+                id: 1,
+                byte_size: 1024,
+            }]
+        );
+        assert_eq!(stats.response_stats["0"].empty_response.count, 1);
+    }
+
+    #[test]
+    fn never_used_model() {
+        let stats = ModelStatistics::from(inference::ModelStatistics::default());
+        assert_eq!(stats.last_inference, None);
+        assert_eq!(stats.inference_stats, InferStatistics::default());
+    }
+}
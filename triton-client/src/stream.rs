@@ -0,0 +1,342 @@
+use crate::config::GrpcClient;
+use crate::{InferRequest, InferenceOutput, typed_outputs};
+use futures::channel::mpsc;
+use futures::{Sink, SinkExt, Stream};
+use std::collections::HashSet;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use tonic::Streaming;
+use triton_grpc_client::inference::infer_parameter::ParameterChoice;
+use triton_grpc_client::inference::{
+    InferParameter, ModelInferRequest, ModelInferResponse, ModelStreamInferResponse,
+};
+use triton_types::TritonOutputs;
+
+const FINAL_RESPONSE: &str = "triton_final_response";
+const EMPTY_FINAL_RESPONSE: &str = "triton_enable_empty_final_response";
+
+/// Requests that can be queued before sending waits for the server.
+const BUFFER: usize = 16;
+
+#[derive(Debug)]
+pub enum StreamError {
+    /// The server reported a failed request. Triton keeps the stream open
+    /// after such an error.
+    Inference {
+        request_id: Option<String>,
+        message: String,
+    },
+    /// The stream itself failed and yields nothing more.
+    Transport(tonic::Status),
+    /// Requests cannot be sent after the stream was closed.
+    Closed,
+    /// A request could not be built.
+    Request(anyhow::Error),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Inference {
+                request_id: Some(id),
+                message,
+            } => write!(f, "Request '{id}' failed: {message}"),
+            StreamError::Inference {
+                request_id: None,
+                message,
+            } => write!(f, "Streaming inference failed: {message}"),
+            StreamError::Transport(status) => write!(f, "Stream failed: {status}"),
+            StreamError::Closed => write!(f, "The request stream is closed"),
+            StreamError::Request(error) => write!(f, "Invalid request: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StreamError::Transport(status) => Some(status),
+            StreamError::Request(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// One response of a streaming request. Decoupled models may send any
+/// number of responses per request; the last one has `is_final` set and may
+/// have no outputs.
+#[derive(Debug)]
+pub struct InferResponse {
+    /// The id of the request this responds to.
+    pub id: String,
+    pub is_final: bool,
+    pub response: ModelInferResponse,
+}
+
+impl InferResponse {
+    pub fn outputs(&self) -> anyhow::Result<Vec<InferenceOutput>> {
+        InferenceOutput::from_response(&self.response)
+    }
+
+    pub fn typed<O: TritonOutputs>(&self) -> anyhow::Result<O> {
+        typed_outputs(&self.response)
+    }
+
+    fn new(response: ModelInferResponse) -> Self {
+        // Responses of models that are not decoupled carry no flag.
+        let is_final = match response.parameters.get(FINAL_RESPONSE) {
+            Some(InferParameter {
+                parameter_choice: Some(ParameterChoice::BoolParam(is_final)),
+            }) => *is_final,
+            _ => true,
+        };
+
+        Self {
+            id: response.id.clone(),
+            is_final,
+            response,
+        }
+    }
+}
+
+/// Ids of requests that have not received their final response yet.
+type Pending = Arc<Mutex<HashSet<String>>>;
+
+/// A bidirectional inference stream opened by [`TritonClient::stream`].
+/// Send requests through the [`Sink`] and read responses from the
+/// [`Stream`], or [`split`](InferStream::split) it to do both concurrently.
+///
+/// [`TritonClient::stream`]: crate::TritonClient::stream
+pub struct InferStream {
+    sender: InferSender,
+    responses: InferResponses,
+}
+
+impl InferStream {
+    pub(crate) async fn open(mut client: GrpcClient) -> anyhow::Result<Self> {
+        let (sender, receiver) = mpsc::channel(BUFFER);
+        let inner = client.model_stream_infer(receiver).await?.into_inner();
+        let pending = Pending::default();
+
+        Ok(Self {
+            sender: InferSender {
+                sender,
+                next_id: 0,
+                pending: pending.clone(),
+            },
+            responses: InferResponses {
+                inner,
+                pending,
+                done: false,
+            },
+        })
+    }
+
+    /// Sends a request and returns its id. See [`InferSender::send`].
+    pub async fn send(&mut self, request: ModelInferRequest) -> Result<String, StreamError> {
+        self.sender.send(request).await
+    }
+
+    /// Builds and sends a request, returning its id.
+    pub async fn send_request(&mut self, request: InferRequest) -> Result<String, StreamError> {
+        self.sender.send_request(request).await
+    }
+
+    /// Half-closes the stream. Responses to requests already sent keep
+    /// arriving until the server ends the stream.
+    pub fn close(&mut self) {
+        self.sender.close();
+    }
+
+    /// Number of requests still waiting for their final response.
+    pub fn pending(&self) -> usize {
+        self.responses.pending()
+    }
+
+    pub fn split(self) -> (InferSender, InferResponses) {
+        (self.sender, self.responses)
+    }
+}
+
+impl Sink<ModelInferRequest> for InferStream {
+    type Error = StreamError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), StreamError>> {
+        Pin::new(&mut self.sender).poll_ready(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, request: ModelInferRequest) -> Result<(), StreamError> {
+        Pin::new(&mut self.sender).start_send(request)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), StreamError>> {
+        Pin::new(&mut self.sender).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), StreamError>> {
+        Pin::new(&mut self.sender).poll_close(cx)
+    }
+}
+
+impl Stream for InferStream {
+    type Item = Result<InferResponse, StreamError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.responses).poll_next(cx)
+    }
+}
+
+/// The sending half of an [`InferStream`]. Dropping it half-closes the
+/// stream.
+pub struct InferSender {
+    sender: mpsc::Sender<ModelInferRequest>,
+    next_id: u64,
+    pending: Pending,
+}
+
+impl InferSender {
+    /// Sends a request and returns its id. Requests without an id get a
+    /// unique one so their responses can be told apart, and ask for an empty
+    /// final response so that decoupled models signal when they are done.
+    pub async fn send(&mut self, request: ModelInferRequest) -> Result<String, StreamError> {
+        let request = self.prepare(request);
+        let id = request.id.clone();
+        SinkExt::send(self, request).await?;
+        Ok(id)
+    }
+
+    pub async fn send_request(&mut self, request: InferRequest) -> Result<String, StreamError> {
+        let request = request.build().map_err(StreamError::Request)?;
+        self.send(request).await
+    }
+
+    /// Half-closes the stream. See [`InferStream::close`].
+    pub fn close(&mut self) {
+        self.sender.close_channel();
+    }
+
+    fn prepare(&mut self, mut request: ModelInferRequest) -> ModelInferRequest {
+        if request.id.is_empty() {
+            self.next_id += 1;
+            request.id = format!("stream-{}", self.next_id);
+        }
+        request
+            .parameters
+            .entry(EMPTY_FINAL_RESPONSE.to_string())
+            .or_insert(InferParameter {
+                parameter_choice: Some(ParameterChoice::BoolParam(true)),
+            });
+        request
+    }
+}
+
+impl Sink<ModelInferRequest> for InferSender {
+    type Error = StreamError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), StreamError>> {
+        self.sender.poll_ready(cx).map_err(|_| StreamError::Closed)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, request: ModelInferRequest) -> Result<(), StreamError> {
+        let request = self.prepare(request);
+        let id = request.id.clone();
+        self.sender
+            .start_send(request)
+            .map_err(|_| StreamError::Closed)?;
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id);
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), StreamError>> {
+        Pin::new(&mut self.sender)
+            .poll_flush(cx)
+            .map_err(|_| StreamError::Closed)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), StreamError>> {
+        Pin::new(&mut self.sender)
+            .poll_close(cx)
+            .map_err(|_| StreamError::Closed)
+    }
+}
+
+/// The receiving half of an [`InferStream`]. It ends once the sender is
+/// closed and the server has answered every request.
+pub struct InferResponses {
+    inner: Streaming<ModelStreamInferResponse>,
+    pending: Pending,
+    done: bool,
+}
+
+impl InferResponses {
+    /// Number of requests still waiting for their final response.
+    pub fn pending(&self) -> usize {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    fn complete(&self, id: &str) {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id);
+    }
+}
+
+impl Stream for InferResponses {
+    type Item = Result<InferResponse, StreamError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        let message = match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(None) => {
+                self.done = true;
+                return Poll::Ready(None);
+            }
+            Poll::Ready(Some(Err(status))) => {
+                self.done = true;
+                return Poll::Ready(Some(Err(StreamError::Transport(status))));
+            }
+            Poll::Ready(Some(Ok(message))) => message,
+        };
+
+        let response = message.infer_response.map(InferResponse::new);
+
+        if !message.error_message.is_empty() {
+            // A failed request gets no further responses.
+            let request_id = response
+                .map(|response| response.id)
+                .filter(|id| !id.is_empty());
+            if let Some(id) = &request_id {
+                self.complete(id);
+            }
+            return Poll::Ready(Some(Err(StreamError::Inference {
+                request_id,
+                message: message.error_message,
+            })));
+        }
+
+        let Some(response) = response else {
+            return Poll::Ready(Some(Err(StreamError::Inference {
+                request_id: None,
+                message: "Server sent neither a response nor an error".to_string(),
+            })));
+        };
+
+        if response.is_final {
+            self.complete(&response.id);
+        }
+        Poll::Ready(Some(Ok(response)))
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum Error {
+    Config(triton_model_config::Error),
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The config parsed but cannot be turned into Rust types.
+    Invalid {
+        path: PathBuf,
+        message: String,
+    },
+    OutDirNotSet,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Config(error) => write!(f, "{error}"),
+            Error::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::Invalid { path, message } => write!(f, "{}: {}", path.display(), message),
+            Error::OutDirNotSet => write!(f, "OUT_DIR is not set; call compile() from build.rs"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Config(error) => Some(error),
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<triton_model_config::Error> for Error {
+    fn from(error: triton_model_config::Error) -> Self {
+        Error::Config(error)
+    }
+}
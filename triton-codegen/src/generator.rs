@@ -0,0 +1,365 @@
+use crate::error::Error;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use triton_grpc_client::inference::{DataType as ConfigDataType, ModelConfig};
+use triton_types::DataType;
+
+const CONFIG_FILE: &str = "config.pbtxt";
+
+/// Starts configuring the generator, typically from `build.rs`:
+///
+/// ```ignore
+/// triton_codegen::configure()
+///     .repository("../models")
+///     .compile("models.rs")?;
+/// ```
+///
+/// and then `include!(concat!(env!("OUT_DIR"), "/models.rs"));`.
+pub fn configure() -> Builder {
+    Builder::default()
+}
+
+/// Emits one module per model holding its name, tensor name constants and
+/// `#[derive(TritonInputs)]`/`#[derive(TritonOutputs)]` structs, which work
+/// with both `triton_ng` backends and `triton_client`.
+pub struct Builder {
+    configs: Vec<PathBuf>,
+    repositories: Vec<PathBuf>,
+    types_crate: String,
+    half: bool,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            configs: vec![],
+            repositories: vec![],
+            types_crate: "::triton_types".to_string(),
+            half: false,
+        }
+    }
+}
+
+impl Builder {
+    pub fn config(mut self, path: impl AsRef<Path>) -> Self {
+        self.configs.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds every `<model>/config.pbtxt` of a model repository.
+    pub fn repository(mut self, path: impl AsRef<Path>) -> Self {
+        self.repositories.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Path the generated code uses to reach `triton_types`, e.g.
+    /// `triton_ng::types` for crates that only depend on `triton_ng`.
+    pub fn types_crate(mut self, path: impl Into<String>) -> Self {
+        self.types_crate = path.into();
+        self
+    }
+
+    /// Maps FP16 and BF16 tensors to `Vec<f16>` and `Vec<bf16>`. These types
+    /// only exist with the `half` feature of `triton_types`, so enable it
+    /// (e.g. through `triton-ng/half` or `triton-client/half`) in the crate
+    /// that includes the generated code. Without this, such models fail to
+    /// generate.
+    pub fn half(mut self, enabled: bool) -> Self {
+        self.half = enabled;
+        self
+    }
+
+    pub fn generate(&self) -> Result<String, Error> {
+        let mut out = String::from("// @generated by triton-codegen. Do not edit.\n");
+        let mut modules = Vec::new();
+
+        for path in self.config_paths()? {
+            let config = triton_model_config::load(&path)?;
+            let module = identifier(&config.name);
+            if modules.contains(&module) {
+                return Err(Error::Invalid {
+                    path,
+                    message: format!("Model module '{module}' is generated twice"),
+                });
+            }
+
+            out.push('\n');
+            emit_model(&mut out, &path, &config, &module, self)?;
+            modules.push(module);
+        }
+
+        Ok(out)
+    }
+
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        std::fs::write(path, self.generate()?).map_err(|source| Error::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Writes the generated code to `$OUT_DIR/<file_name>` and asks cargo to
+    /// rerun the build script whenever a config changes.
+    pub fn compile(&self, file_name: &str) -> Result<(), Error> {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or(Error::OutDirNotSet)?;
+
+        for path in self.repositories.iter().chain(&self.configs) {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+        for path in self.config_paths()? {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+
+        self.write_to(Path::new(&out_dir).join(file_name))
+    }
+
+    fn config_paths(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut paths = self.configs.clone();
+
+        for repository in &self.repositories {
+            let entries = std::fs::read_dir(repository).map_err(|source| Error::Io {
+                path: repository.clone(),
+                source,
+            })?;
+
+            let mut models = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path().join(CONFIG_FILE))
+                .filter(|path| path.is_file())
+                .collect::<Vec<_>>();
+            models.sort();
+            paths.extend(models);
+        }
+
+        Ok(paths)
+    }
+}
+
+struct Tensor<'a> {
+    name: &'a str,
+    datatype: i32,
+    shape: Vec<i64>,
+}
+
+fn emit_model(
+    out: &mut String,
+    path: &Path,
+    config: &ModelConfig,
+    module: &str,
+    builder: &Builder,
+) -> Result<(), Error> {
+    // The structs are shared by clients, which see `dims`, and backends,
+    // which see the reshaped tensor, so there is no single shape to emit.
+    let reshaped = config
+        .input
+        .iter()
+        .filter(|input| input.reshape.is_some())
+        .map(|input| &input.name)
+        .chain(
+            config
+                .output
+                .iter()
+                .filter(|output| output.reshape.is_some())
+                .map(|output| &output.name),
+        )
+        .next();
+    if let Some(name) = reshaped {
+        return Err(Error::Invalid {
+            path: path.to_path_buf(),
+            message: format!("Tensor '{name}' sets `reshape`, which is not supported"),
+        });
+    }
+
+    // Batching models take an extra leading batch dimension on the wire.
+    let batch = |dims: &[i64]| {
+        let mut shape = Vec::with_capacity(dims.len() + 1);
+        if config.max_batch_size > 0 {
+            shape.push(-1);
+        }
+        shape.extend_from_slice(dims);
+        shape
+    };
+    let inputs: Vec<_> = config
+        .input
+        .iter()
+        .map(|input| Tensor {
+            name: &input.name,
+            datatype: input.data_type,
+            shape: batch(&input.dims),
+        })
+        .collect();
+    let outputs: Vec<_> = config
+        .output
+        .iter()
+        .map(|output| Tensor {
+            name: &output.name,
+            datatype: output.data_type,
+            shape: batch(&output.dims),
+        })
+        .collect();
+
+    writeln!(out, "/// `{}` from `{}`.", config.name, path.display()).unwrap();
+    writeln!(out, "#[allow(dead_code)]").unwrap();
+    writeln!(out, "pub mod {module} {{").unwrap();
+    writeln!(out, "    pub const NAME: &str = {:?};", config.name).unwrap();
+    writeln!(
+        out,
+        "    pub const MAX_BATCH_SIZE: i32 = {};",
+        config.max_batch_size
+    )
+    .unwrap();
+
+    emit_names(out, "input_names", &inputs);
+    emit_names(out, "output_names", &outputs);
+    emit_struct(out, path, "Inputs", "TritonInputs", &inputs, builder)?;
+    emit_struct(out, path, "Outputs", "TritonOutputs", &outputs, builder)?;
+
+    writeln!(out, "}}").unwrap();
+    Ok(())
+}
+
+fn emit_names(out: &mut String, module: &str, tensors: &[Tensor]) {
+    writeln!(out).unwrap();
+    writeln!(out, "    pub mod {module} {{").unwrap();
+    for tensor in tensors {
+        writeln!(
+            out,
+            "        pub const {}: &str = {:?};",
+            identifier(tensor.name)
+                .trim_start_matches("r#")
+                .to_uppercase(),
+            tensor.name
+        )
+        .unwrap();
+    }
+    writeln!(out, "    }}").unwrap();
+}
+
+fn emit_struct(
+    out: &mut String,
+    path: &Path,
+    name: &str,
+    derive: &str,
+    tensors: &[Tensor],
+    builder: &Builder,
+) -> Result<(), Error> {
+    let types = &builder.types_crate;
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    #[derive(Debug, Clone, PartialEq, {types}::{derive})]"
+    )
+    .unwrap();
+    writeln!(out, "    #[triton(crate = {types:?})]").unwrap();
+    writeln!(out, "    pub struct {name} {{").unwrap();
+
+    let mut fields = Vec::new();
+    for tensor in tensors {
+        let field = identifier(tensor.name);
+        if fields.contains(&field) {
+            return Err(Error::Invalid {
+                path: path.to_path_buf(),
+                message: format!(
+                    "Tensor '{}' maps to a duplicate field '{field}'",
+                    tensor.name
+                ),
+            });
+        }
+
+        let invalid = |message| Error::Invalid {
+            path: path.to_path_buf(),
+            message,
+        };
+        let datatype = ConfigDataType::try_from(tensor.datatype)
+            .map(DataType::from)
+            .unwrap_or(DataType::Invalid);
+        if matches!(datatype, DataType::Fp16 | DataType::Bf16) && !builder.half {
+            return Err(invalid(format!(
+                "Tensor '{}' is {datatype}, which needs the `half` feature of triton-types; \
+                 enable it and call `.half(true)` on the generator",
+                tensor.name
+            )));
+        }
+        let ty = rust_type(datatype, types).ok_or_else(|| {
+            invalid(format!(
+                "Tensor '{}' has no supported data_type",
+                tensor.name
+            ))
+        })?;
+        let shape = tensor
+            .shape
+            .iter()
+            .map(|dim| dim.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        writeln!(
+            out,
+            "        #[triton(name = {:?}, shape = [{shape}])]",
+            tensor.name
+        )
+        .unwrap();
+        writeln!(out, "        pub {field}: {ty},").unwrap();
+        fields.push(field);
+    }
+
+    writeln!(out, "    }}").unwrap();
+    Ok(())
+}
+
+fn rust_type(datatype: DataType, types: &str) -> Option<String> {
+    let element = match datatype {
+        DataType::Invalid => return None,
+        DataType::Bool => "bool",
+        DataType::Uint8 => "u8",
+        DataType::Uint16 => "u16",
+        DataType::Uint32 => "u32",
+        DataType::Uint64 => "u64",
+        DataType::Int8 => "i8",
+        DataType::Int16 => "i16",
+        DataType::Int32 => "i32",
+        DataType::Int64 => "i64",
+        DataType::Fp16 => return Some(format!("Vec<{types}::f16>")),
+        DataType::Fp32 => "f32",
+        DataType::Fp64 => "f64",
+        DataType::Bytes => "String",
+        DataType::Bf16 => return Some(format!("Vec<{types}::bf16>")),
+    };
+
+    Some(format!("Vec<{element}>"))
+}
+
+/// Turns a model or tensor name into a snake_case Rust identifier.
+fn identifier(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    if ["self", "super", "crate"].contains(&ident.as_str()) {
+        ident.push('_');
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        ident.insert_str(0, "r#");
+    }
+
+    ident
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+];
@@ -0,0 +1,7 @@
+#[path = "error.rs"]
+pub mod error;
+#[path = "generator.rs"]
+pub mod generator;
+
+pub use error::*;
+pub use generator::*;
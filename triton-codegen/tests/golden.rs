@@ -0,0 +1,58 @@
+use std::path::Path;
+
+/// Compiles the checked-in output so a stale golden file also fails here.
+mod models {
+    include!("golden/models.rs");
+}
+
+#[test]
+fn mnist_models() {
+    let generated = triton_codegen::configure()
+        .repository("../models")
+        .generate()
+        .unwrap();
+    let golden = std::fs::read_to_string("tests/golden/models.rs").unwrap();
+
+    assert_eq!(generated, golden, "regenerate tests/golden/models.rs");
+}
+
+#[test]
+fn golden_code_compiles() {
+    use triton_types::TritonInputs;
+
+    assert_eq!(models::mnist::NAME, "mnist");
+    assert_eq!(models::mnist_onnx::input_names::INPUT3, "Input3");
+    assert_eq!(models::mnist::Inputs::INPUTS[0].shape, [1, 1, 28, 28]);
+}
+
+#[test]
+fn reshape_is_rejected() {
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("reshape.pbtxt");
+    std::fs::write(
+        &path,
+        r#"
+name: "reshaped"
+max_batch_size: 8
+input [
+  {
+    name: "INPUT"
+    data_type: TYPE_FP32
+    dims: [ 4 ]
+    reshape: { shape: [ 2, 2 ] }
+  }
+]
+"#,
+    )
+    .unwrap();
+
+    let error = triton_codegen::configure()
+        .config(&path)
+        .generate()
+        .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .ends_with("Tensor 'INPUT' sets `reshape`, which is not supported"),
+        "{error}"
+    );
+}
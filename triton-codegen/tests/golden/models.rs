@@ -0,0 +1,59 @@
+// @generated by triton-codegen. Do not edit.
+
+/// `mnist` from `../models/mnist/config.pbtxt`.
+#[allow(dead_code)]
+pub mod mnist {
+    pub const NAME: &str = "mnist";
+    pub const MAX_BATCH_SIZE: i32 = 0;
+
+    pub mod input_names {
+        pub const INPUT3: &str = "Input3";
+    }
+
+    pub mod output_names {
+        pub const PLUS214_OUTPUT_0: &str = "Plus214_Output_0";
+    }
+
+    #[derive(Debug, Clone, PartialEq, ::triton_types::TritonInputs)]
+    #[triton(crate = "::triton_types")]
+    pub struct Inputs {
+        #[triton(name = "Input3", shape = [1, 1, 28, 28])]
+        pub input3: Vec<f32>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, ::triton_types::TritonOutputs)]
+    #[triton(crate = "::triton_types")]
+    pub struct Outputs {
+        #[triton(name = "Plus214_Output_0", shape = [1, 10])]
+        pub plus214_output_0: Vec<f32>,
+    }
+}
+
+/// `mnist_onnx` from `../models/mnist_onnx/config.pbtxt`.
+#[allow(dead_code)]
+pub mod mnist_onnx {
+    pub const NAME: &str = "mnist_onnx";
+    pub const MAX_BATCH_SIZE: i32 = 0;
+
+    pub mod input_names {
+        pub const INPUT3: &str = "Input3";
+    }
+
+    pub mod output_names {
+        pub const PLUS214_OUTPUT_0: &str = "Plus214_Output_0";
+    }
+
+    #[derive(Debug, Clone, PartialEq, ::triton_types::TritonInputs)]
+    #[triton(crate = "::triton_types")]
+    pub struct Inputs {
+        #[triton(name = "Input3", shape = [1, 1, 28, 28])]
+        pub input3: Vec<f32>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, ::triton_types::TritonOutputs)]
+    #[triton(crate = "::triton_types")]
+    pub struct Outputs {
+        #[triton(name = "Plus214_Output_0", shape = [1, 10])]
+        pub plus214_output_0: Vec<f32>,
+    }
+}
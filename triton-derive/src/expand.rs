@@ -0,0 +1,171 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Expr, Field, Fields, Ident, LitStr, Path, Token, Type, parse_quote};
+
+pub enum Kind {
+    Inputs,
+    Outputs,
+}
+
+struct Tensor {
+    field: Ident,
+    ty: Type,
+    name: String,
+    shape: Vec<Expr>,
+}
+
+pub fn derive(input: &DeriveInput, kind: Kind) -> syn::Result<TokenStream> {
+    let krate = crate_path(input)?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "Triton tensors can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "Triton tensors can only be derived for structs with named fields",
+        ));
+    };
+    let tensors = fields
+        .named
+        .iter()
+        .map(tensor)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let (trait_name, specs, read_fn, write_fn) = match kind {
+        Kind::Inputs => (
+            format_ident!("TritonInputs"),
+            format_ident!("INPUTS"),
+            format_ident!("read_inputs"),
+            format_ident!("write_inputs"),
+        ),
+        Kind::Outputs => (
+            format_ident!("TritonOutputs"),
+            format_ident!("OUTPUTS"),
+            format_ident!("read_outputs"),
+            format_ident!("write_outputs"),
+        ),
+    };
+
+    let spec_values = tensors.iter().map(|tensor| {
+        let Tensor {
+            ty, name, shape, ..
+        } = tensor;
+        quote! {
+            #krate::TensorSpec {
+                name: #name,
+                datatype: <#ty as #krate::TensorValue>::DATATYPE,
+                shape: &[#(#shape),*],
+            }
+        }
+    });
+    let reads = tensors.iter().enumerate().map(|(index, tensor)| {
+        let field = &tensor.field;
+        quote! {
+            #field: <Self as #krate::#trait_name>::#specs[#index].read(source)?
+        }
+    });
+    let writes = tensors.iter().enumerate().map(|(index, tensor)| {
+        let field = &tensor.field;
+        quote! {
+            <Self as #krate::#trait_name>::#specs[#index].write(sink, &self.#field)?;
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::#trait_name for #ident #ty_generics #where_clause {
+            const #specs: &'static [#krate::TensorSpec] = &[#(#spec_values),*];
+
+            fn #read_fn<S: #krate::TensorSource + ?Sized>(
+                source: &S,
+            ) -> ::core::result::Result<Self, #krate::TensorError> {
+                ::core::result::Result::Ok(Self { #(#reads),* })
+            }
+
+            fn #write_fn<S: #krate::TensorSink + ?Sized>(
+                &self,
+                sink: &mut S,
+            ) -> ::core::result::Result<(), #krate::TensorError> {
+                #(#writes)*
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+/// `#[triton(crate = "...")]` on the struct, for callers that reach
+/// `triton_types` through a re-export.
+fn crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    let mut path = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("triton"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                if path.is_some() {
+                    return Err(meta.error("duplicate `crate` attribute"));
+                }
+                path = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `crate`"))
+            }
+        })?;
+    }
+
+    Ok(path.unwrap_or_else(|| parse_quote!(::triton_types)))
+}
+
+/// Reads `#[triton(name = "...", shape = [...])]`. The name defaults to the
+/// field name and the shape to a single dynamic dimension.
+fn tensor(field: &Field) -> syn::Result<Tensor> {
+    let ident = field.ident.clone().expect("named field");
+    let mut name = None;
+    let mut shape = None;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("triton"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                if name.is_some() {
+                    return Err(meta.error("duplicate `name` attribute"));
+                }
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("shape") {
+                if shape.is_some() {
+                    return Err(meta.error("duplicate `shape` attribute"));
+                }
+                let value = meta.value()?;
+                let content;
+                syn::bracketed!(content in value);
+                let dims = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+                shape = Some(dims.into_iter().collect());
+                Ok(())
+            } else {
+                Err(meta.error("expected `name` or `shape`"))
+            }
+        })?;
+    }
+
+    Ok(Tensor {
+        name: name.unwrap_or_else(|| ident.unraw().to_string()),
+        shape: shape.unwrap_or_else(|| vec![parse_quote!(-1)]),
+        ty: field.ty.clone(),
+        field: ident,
+    })
+}
@@ -0,0 +1,29 @@
+#[path = "expand.rs"]
+mod expand;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+/// Implements `triton_types::TritonInputs` for a struct whose fields are the
+/// model inputs, e.g.
+/// `#[triton(name = "Input3", shape = [1, 1, 28, 28])] image: Vec<f32>`.
+///
+/// The generated code refers to `::triton_types`; add
+/// `#[triton(crate = "triton_ng::types")]` to the struct to go through a
+/// re-export instead.
+#[proc_macro_derive(TritonInputs, attributes(triton))]
+pub fn derive_triton_inputs(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::derive(&input, expand::Kind::Inputs)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `triton_types::TritonOutputs`, see [`TritonInputs`](derive@TritonInputs).
+#[proc_macro_derive(TritonOutputs, attributes(triton))]
+pub fn derive_triton_outputs(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand::derive(&input, expand::Kind::Outputs)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,22 @@
+use triton_types::{TritonInputs, TritonOutputs};
+
+#[derive(TritonInputs)]
+struct Inputs {
+    #[triton(name = "image")]
+    #[triton(name = "Input3")]
+    image: Vec<f32>,
+}
+
+#[derive(TritonOutputs)]
+struct Outputs {
+    #[triton(shape = [1, -1], shape = [-1])]
+    scores: Vec<f32>,
+}
+
+#[derive(TritonOutputs)]
+#[triton(crate = "triton_types", crate = "triton_types")]
+struct Renamed {
+    scores: Vec<f32>,
+}
+
+fn main() {}
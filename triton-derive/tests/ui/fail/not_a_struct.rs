@@ -0,0 +1,11 @@
+use triton_types::{TritonInputs, TritonOutputs};
+
+#[derive(TritonInputs)]
+enum Inputs {
+    Image(Vec<f32>),
+}
+
+#[derive(TritonOutputs)]
+struct Outputs(Vec<f32>);
+
+fn main() {}
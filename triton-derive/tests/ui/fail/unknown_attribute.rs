@@ -0,0 +1,15 @@
+use triton_types::{TritonInputs, TritonOutputs};
+
+#[derive(TritonInputs)]
+struct Inputs {
+    #[triton(name = "image", dims = [1, 28, 28])]
+    image: Vec<f32>,
+}
+
+#[derive(TritonOutputs)]
+#[triton(krate = "triton_types")]
+struct Outputs {
+    scores: Vec<f32>,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+mod types {
+    pub use triton_types::*;
+}
+
+extern crate triton_types as renamed;
+
+#[derive(types::TritonInputs)]
+#[triton(crate = "crate::types")]
+struct Inputs {
+    image: Vec<f32>,
+}
+
+#[derive(renamed::TritonOutputs)]
+#[triton(crate = "::renamed")]
+struct Outputs {
+    scores: Vec<f32>,
+}
+
+fn main() {
+    assert_eq!(<Inputs as types::TritonInputs>::INPUTS[0].name, "image");
+    assert_eq!(<Outputs as renamed::TritonOutputs>::OUTPUTS[0].name, "scores");
+}
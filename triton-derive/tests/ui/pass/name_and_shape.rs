@@ -0,0 +1,31 @@
+use triton_types::{TensorSpec, TritonInputs, TritonOutputs};
+
+#[derive(TritonInputs)]
+struct Inputs {
+    #[triton(name = "Input3", shape = [1, 1, 28, 28])]
+    image: Vec<f32>,
+    r#type: Vec<i32>,
+}
+
+#[derive(TritonOutputs)]
+struct Outputs {
+    #[triton(shape = [1, -1])]
+    #[triton(name = "Plus214_Output_0")]
+    scores: Vec<f32>,
+}
+
+fn main() {
+    let [image, kind] = Inputs::INPUTS else {
+        panic!("expected two inputs");
+    };
+    assert_eq!(image.name, "Input3");
+    assert_eq!(image.shape, [1, 1, 28, 28]);
+    assert_eq!(kind.name, "type");
+    assert_eq!(kind.shape, [-1]);
+
+    let [TensorSpec { name, shape, .. }] = Outputs::OUTPUTS else {
+        panic!("expected one output");
+    };
+    assert_eq!(*name, "Plus214_Output_0");
+    assert_eq!(*shape, [1, -1]);
+}
@@ -1,7 +1,9 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_prost_build::configure()
-        .build_server(false)
+        .build_server(true)
         .build_client(true)
+        // Lets retries share the tensor data of a request instead of copying it.
+        .bytes(".inference.ModelInferRequest.raw_input_contents")
         .out_dir("src/")
         .compile_protos(
             &[
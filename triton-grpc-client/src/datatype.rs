@@ -0,0 +1,33 @@
+use crate::inference::DataType as ConfigDataType;
+use triton_types::DataType;
+
+impl From<DataType> for ConfigDataType {
+    fn from(datatype: DataType) -> Self {
+        ConfigDataType::try_from(datatype.as_raw() as i32).unwrap_or(ConfigDataType::TypeInvalid)
+    }
+}
+
+impl From<ConfigDataType> for DataType {
+    fn from(datatype: ConfigDataType) -> Self {
+        DataType::from_raw(datatype as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_model_config_proto() {
+        for datatype in DataType::ALL {
+            let config = ConfigDataType::from(datatype);
+            assert_eq!(config.as_str_name(), datatype.config_name());
+            assert_eq!(config as u32, datatype.as_raw());
+            assert_eq!(DataType::from(config), datatype);
+        }
+        assert_eq!(
+            ConfigDataType::from(DataType::Invalid),
+            ConfigDataType::TypeInvalid
+        );
+    }
+}
@@ -1,3 +1,5 @@
+#[path = "datatype.rs"]
+pub mod datatype;
 #[path = "grpc.health.v1.rs"]
 pub mod healthcheck;
 #[path = "inference.rs"]
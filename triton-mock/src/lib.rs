@@ -0,0 +1,12 @@
+#[path = "model.rs"]
+pub mod model;
+#[path = "server.rs"]
+pub mod server;
+#[path = "service.rs"]
+mod service;
+#[path = "shm.rs"]
+mod shm;
+
+pub use model::*;
+pub use server::*;
+pub use tonic::{Code, Status};
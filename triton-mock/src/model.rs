@@ -0,0 +1,333 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tonic::Status;
+use triton_grpc_client::inference::model_infer_response::InferOutputTensor;
+use triton_grpc_client::inference::model_metadata_response::TensorMetadata;
+use triton_grpc_client::inference::{
+    DataType as ConfigDataType, ModelConfig, ModelInferRequest, ModelInferResponse, ModelInput,
+    ModelMetadataResponse, ModelOutput, ModelTransactionPolicy,
+};
+use triton_types::{
+    DataType, TensorError, TensorSink, TensorSource, TensorSpec, TritonInputs, TritonOutputs,
+};
+
+type Handler = Arc<dyn Fn(&ModelInferRequest) -> Result<ModelInferResponse, Status> + Send + Sync>;
+type StreamHandler =
+    Arc<dyn Fn(&ModelInferRequest) -> Vec<Result<ModelInferResponse, Status>> + Send + Sync>;
+
+/// Produces the responses to one request, outside the server's lock.
+pub(crate) type Reply =
+    Box<dyn FnOnce(&ModelInferRequest) -> Vec<Result<ModelInferResponse, Status>> + Send>;
+
+#[derive(Clone)]
+struct Tensor {
+    name: String,
+    datatype: DataType,
+    shape: Vec<i64>,
+}
+
+/// A model served by [`MockServer`](crate::MockServer):
+///
+/// ```ignore
+/// let model = MockModel::new("add")
+///     .typed_handler(|inputs: AddInputs| AddOutputs { sum: inputs.a + inputs.b })
+///     .latency(Duration::from_millis(5));
+/// ```
+///
+/// Inputs reach handlers in `raw_input_contents`, one entry per input, also
+/// when the client placed them in shared memory or sent typed `contents`.
+#[derive(Clone)]
+pub struct MockModel {
+    pub(crate) name: String,
+    pub(crate) versions: Vec<String>,
+    platform: String,
+    inputs: Vec<Tensor>,
+    outputs: Vec<Tensor>,
+    config: Option<ModelConfig>,
+    pub(crate) ready: bool,
+    pub(crate) latency: Duration,
+    handler: Option<Handler>,
+    stream_handler: Option<StreamHandler>,
+    script: VecDeque<Result<ModelInferResponse, Status>>,
+}
+
+impl MockModel {
+    /// A ready model with version `1` that fails every request until given
+    /// a handler or scripted responses.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            versions: vec!["1".to_string()],
+            platform: "mock".to_string(),
+            inputs: vec![],
+            outputs: vec![],
+            config: None,
+            ready: true,
+            latency: Duration::ZERO,
+            handler: None,
+            stream_handler: None,
+            script: VecDeque::new(),
+        }
+    }
+
+    pub fn versions<V: Into<String>>(mut self, versions: impl IntoIterator<Item = V>) -> Self {
+        self.versions = versions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn platform(mut self, platform: impl Into<String>) -> Self {
+        self.platform = platform.into();
+        self
+    }
+
+    /// Declares an input in the model's metadata and default config.
+    pub fn input(mut self, name: impl Into<String>, datatype: DataType, shape: &[i64]) -> Self {
+        self.inputs.push(Tensor {
+            name: name.into(),
+            datatype,
+            shape: shape.to_vec(),
+        });
+        self
+    }
+
+    /// Declares an output in the model's metadata and default config.
+    pub fn output(mut self, name: impl Into<String>, datatype: DataType, shape: &[i64]) -> Self {
+        self.outputs.push(Tensor {
+            name: name.into(),
+            datatype,
+            shape: shape.to_vec(),
+        });
+        self
+    }
+
+    /// Replaces the config derived from the declared inputs and outputs.
+    pub fn config(mut self, config: ModelConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Whether the model starts out ready. Requests to a model that is not
+    /// ready fail with `UNAVAILABLE`.
+    pub fn ready(mut self, ready: bool) -> Self {
+        self.ready = ready;
+        self
+    }
+
+    /// Delays every inference request to this model.
+    pub fn latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Answers requests that no scripted response is left for.
+    pub fn handler(
+        mut self,
+        handler: impl Fn(&ModelInferRequest) -> Result<ModelInferResponse, Status>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// A handler on `#[derive(TritonInputs)]` and `#[derive(TritonOutputs)]`
+    /// structs. Their tensors are declared unless already present.
+    pub fn typed_handler<I, O>(mut self, handler: impl Fn(I) -> O + Send + Sync + 'static) -> Self
+    where
+        I: TritonInputs,
+        O: TritonOutputs,
+    {
+        for spec in I::INPUTS {
+            if !self.inputs.iter().any(|input| input.name == spec.name) {
+                self = self.input(spec.name, spec.datatype, spec.shape);
+            }
+        }
+        for spec in O::OUTPUTS {
+            if !self.outputs.iter().any(|output| output.name == spec.name) {
+                self = self.output(spec.name, spec.datatype, spec.shape);
+            }
+        }
+
+        self.handler(move |request| {
+            let inputs = I::read_inputs(&RequestSource(request))
+                .map_err(|error| Status::invalid_argument(error.to_string()))?;
+            let mut response = ModelInferResponse::default();
+            handler(inputs)
+                .write_outputs(&mut ResponseSink(&mut response))
+                .map_err(|error| Status::internal(error.to_string()))?;
+            Ok(response)
+        })
+    }
+
+    /// Makes the model decoupled: on `ModelStreamInfer` each request gets
+    /// every response the handler returns, and `ModelInfer` is refused.
+    pub fn stream_handler(
+        mut self,
+        handler: impl Fn(&ModelInferRequest) -> Vec<Result<ModelInferResponse, Status>>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.stream_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Queues a response for the next request, before the handler is used.
+    pub fn respond(mut self, response: ModelInferResponse) -> Self {
+        self.script.push_back(Ok(response));
+        self
+    }
+
+    /// Queues an error for the next request, before the handler is used.
+    pub fn fail(mut self, status: Status) -> Self {
+        self.script.push_back(Err(status));
+        self
+    }
+
+    pub(crate) fn metadata(&self) -> ModelMetadataResponse {
+        let metadata = |tensors: &[Tensor]| {
+            tensors
+                .iter()
+                .map(|tensor| TensorMetadata {
+                    name: tensor.name.clone(),
+                    datatype: tensor.datatype.to_string(),
+                    shape: tensor.shape.clone(),
+                })
+                .collect()
+        };
+
+        ModelMetadataResponse {
+            name: self.name.clone(),
+            versions: self.versions.clone(),
+            platform: self.platform.clone(),
+            inputs: metadata(&self.inputs),
+            outputs: metadata(&self.outputs),
+        }
+    }
+
+    /// Whether `version` is served, an empty one selecting the latest.
+    pub(crate) fn has_version(&self, version: &str) -> bool {
+        version.is_empty() || self.versions.iter().any(|served| served == version)
+    }
+
+    pub(crate) fn latest_version(&self) -> &str {
+        self.versions.last().map_or("", String::as_str)
+    }
+
+    pub(crate) fn model_config(&self) -> ModelConfig {
+        if let Some(config) = &self.config {
+            return config.clone();
+        }
+
+        ModelConfig {
+            name: self.name.clone(),
+            platform: self.platform.clone(),
+            input: self
+                .inputs
+                .iter()
+                .map(|input| ModelInput {
+                    name: input.name.clone(),
+                    data_type: ConfigDataType::from(input.datatype).into(),
+                    dims: input.shape.clone(),
+                    ..Default::default()
+                })
+                .collect(),
+            output: self
+                .outputs
+                .iter()
+                .map(|output| ModelOutput {
+                    name: output.name.clone(),
+                    data_type: ConfigDataType::from(output.datatype).into(),
+                    dims: output.shape.clone(),
+                    ..Default::default()
+                })
+                .collect(),
+            model_transaction_policy: self
+                .stream_handler
+                .as_ref()
+                .map(|_| ModelTransactionPolicy { decoupled: true }),
+            ..Default::default()
+        }
+    }
+
+    /// Takes the next scripted response or the handler that answers a
+    /// request sent on a stream or, with `stream` unset, with `ModelInfer`.
+    pub(crate) fn reply(&mut self, stream: bool) -> Reply {
+        if let Some(scripted) = self.script.pop_front() {
+            return Box::new(move |_| vec![scripted]);
+        }
+
+        let name = self.name.clone();
+        match (&self.stream_handler, &self.handler) {
+            (Some(handler), _) if stream => {
+                let handler = handler.clone();
+                Box::new(move |request| handler(request))
+            }
+            (_, Some(handler)) => {
+                let handler = handler.clone();
+                Box::new(move |request| vec![handler(request)])
+            }
+            (Some(_), None) => Box::new(move |_| {
+                vec![Err(Status::invalid_argument(format!(
+                    "Model '{name}' is decoupled and only serves ModelStreamInfer"
+                )))]
+            }),
+            (None, None) => Box::new(move |_| {
+                vec![Err(Status::unimplemented(format!(
+                    "Mock model '{name}' has no handler"
+                )))]
+            }),
+        }
+    }
+}
+
+struct RequestSource<'a>(&'a ModelInferRequest);
+
+impl TensorSource for RequestSource<'_> {
+    fn tensor_bytes(&self, spec: &TensorSpec) -> Result<Cow<'_, [u8]>, TensorError> {
+        let request = self.0;
+        let index = request
+            .inputs
+            .iter()
+            .position(|input| input.name == spec.name)
+            .ok_or_else(|| TensorError::new(format!("Missing input '{}'", spec.name)))?;
+
+        let datatype = &request.inputs[index].datatype;
+        if *datatype != spec.datatype.as_str() {
+            return Err(TensorError::new(format!(
+                "Input '{}' is {}, not {}",
+                spec.name, datatype, spec.datatype
+            )));
+        }
+
+        request
+            .raw_input_contents
+            .get(index)
+            .map(|bytes| Cow::Borrowed(&bytes[..]))
+            .ok_or_else(|| TensorError::new(format!("Input '{}' has no data", spec.name)))
+    }
+}
+
+struct ResponseSink<'a>(&'a mut ModelInferResponse);
+
+impl TensorSink for ResponseSink<'_> {
+    fn write_tensor(
+        &mut self,
+        spec: &TensorSpec,
+        shape: &[i64],
+        bytes: Vec<u8>,
+    ) -> Result<(), TensorError> {
+        self.0.outputs.push(InferOutputTensor {
+            name: spec.name.to_string(),
+            datatype: spec.datatype.to_string(),
+            shape: shape.to_vec(),
+            parameters: Default::default(),
+            contents: None,
+        });
+        self.0.raw_output_contents.push(bytes);
+        Ok(())
+    }
+}
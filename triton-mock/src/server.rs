@@ -0,0 +1,194 @@
+use crate::MockModel;
+use crate::service::MockService;
+use crate::shm::Region;
+use anyhow::Result;
+use futures::channel::oneshot;
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tonic::Status;
+use tonic::transport::Server;
+use tonic::transport::server::TcpIncoming;
+use triton_grpc_client::healthcheck::health_server::HealthServer;
+use triton_grpc_client::inference::ModelInferRequest;
+use triton_grpc_client::inference::grpc_inference_service_server::GrpcInferenceServiceServer;
+use triton_grpc_client::inference::log_settings_response::SettingValue as LogSetting;
+
+#[derive(Default)]
+pub(crate) struct State {
+    pub(crate) live: bool,
+    pub(crate) ready: bool,
+    pub(crate) models: HashMap<String, MockModel>,
+    pub(crate) latency: Duration,
+    pub(crate) errors: HashMap<String, VecDeque<Status>>,
+    pub(crate) calls: HashMap<String, usize>,
+    pub(crate) inferences: HashMap<String, u64>,
+    pub(crate) requests: Vec<ModelInferRequest>,
+    pub(crate) regions: HashMap<String, Region>,
+    /// Trace settings by model name, `""` holding the global ones.
+    pub(crate) trace: HashMap<String, HashMap<String, Vec<String>>>,
+    pub(crate) log: HashMap<String, LogSetting>,
+}
+
+pub(crate) type SharedState = Arc<Mutex<State>>;
+
+pub(crate) fn lock(state: &SharedState) -> MutexGuard<'_, State> {
+    state
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// An in-process server speaking Triton's gRPC protocol on a free local
+/// port, so that clients can be tested without a real server:
+///
+/// ```ignore
+/// let server = MockServer::start([MockModel::new("add").typed_handler(add)]).await?;
+/// let client = TritonClient::new(&server.url()).await?;
+///
+/// server.fail_next("model_infer", Status::unavailable("reloading"));
+/// ```
+///
+/// RPCs are named as in the generated client, e.g. `server_live` or
+/// `model_infer`. The server shuts down when dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: SharedState,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<Result<(), tonic::transport::Error>>>,
+}
+
+impl MockServer {
+    /// Starts a live and ready server on `127.0.0.1` with `models`.
+    pub async fn start(models: impl IntoIterator<Item = MockModel>) -> Result<Self> {
+        let state = State {
+            live: true,
+            ready: true,
+            models: models
+                .into_iter()
+                .map(|model| (model.name.clone(), model))
+                .collect(),
+            ..Default::default()
+        };
+        let state = Arc::new(Mutex::new(state));
+
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let service = MockService::new(state.clone());
+        let (shutdown, signal) = oneshot::channel::<()>();
+
+        let router = Server::builder()
+            .add_service(
+                GrpcInferenceServiceServer::new(service.clone())
+                    .max_decoding_message_size(usize::MAX)
+                    .max_encoding_message_size(usize::MAX),
+            )
+            .add_service(HealthServer::new(service));
+        let task = tokio::spawn(router.serve_with_incoming_shutdown(
+            TcpIncoming::from(listener),
+            async {
+                let _ = signal.await;
+            },
+        ));
+
+        Ok(Self {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+            task: Some(task),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The URL to connect a client to, e.g. `http://127.0.0.1:40123`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+
+    pub fn set_live(&self, live: bool) {
+        self.state().live = live;
+    }
+
+    pub fn set_ready(&self, ready: bool) {
+        self.state().ready = ready;
+    }
+
+    /// Returns `false` if there is no such model.
+    pub fn set_model_ready(&self, model_name: &str, ready: bool) -> bool {
+        match self.state().models.get_mut(model_name) {
+            Some(model) => {
+                model.ready = ready;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a model, replacing any of the same name.
+    pub fn add_model(&self, model: MockModel) {
+        self.state().models.insert(model.name.clone(), model);
+    }
+
+    pub fn remove_model(&self, model_name: &str) {
+        self.state().models.remove(model_name);
+    }
+
+    /// Delays every RPC, on top of a model's own latency.
+    pub fn set_latency(&self, latency: Duration) {
+        self.state().latency = latency;
+    }
+
+    /// Fails the next call of `rpc` with `status`. Errors queued for the
+    /// same RPC are returned in order.
+    pub fn fail_next(&self, rpc: &str, status: Status) {
+        self.state()
+            .errors
+            .entry(rpc.to_string())
+            .or_default()
+            .push_back(status);
+    }
+
+    /// How often `rpc` was called, including failed calls. Each stream
+    /// counts once as `model_stream_infer`.
+    pub fn calls(&self, rpc: &str) -> usize {
+        self.state().calls.get(rpc).copied().unwrap_or(0)
+    }
+
+    /// Every inference request received so far, as the client sent it.
+    pub fn requests(&self) -> Vec<ModelInferRequest> {
+        self.state().requests.clone()
+    }
+
+    /// Names of the registered system shared memory regions.
+    pub fn shared_memory_regions(&self) -> Vec<String> {
+        self.state().regions.keys().cloned().collect()
+    }
+
+    /// Stops accepting requests and waits for open connections to finish.
+    pub async fn shutdown(mut self) -> Result<()> {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        match self.task.take() {
+            Some(task) => Ok(task.await??),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
@@ -0,0 +1,830 @@
+use crate::model::Reply;
+use crate::server::{SharedState, State, lock};
+use crate::shm::Region;
+use futures::channel::mpsc;
+use futures::{SinkExt, StreamExt};
+use std::collections::{HashMap, VecDeque};
+use std::sync::MutexGuard;
+use std::time::Duration;
+use tonic::{Request, Response, Status, Streaming};
+use triton_grpc_client::healthcheck::health_check_response::ServingStatus;
+use triton_grpc_client::healthcheck::health_server::Health;
+use triton_grpc_client::healthcheck::{HealthCheckRequest, HealthCheckResponse};
+use triton_grpc_client::inference::grpc_inference_service_server::GrpcInferenceService;
+use triton_grpc_client::inference::infer_parameter::ParameterChoice;
+use triton_grpc_client::inference::log_settings_request::setting_value::ParameterChoice as LogRequestChoice;
+use triton_grpc_client::inference::log_settings_response::setting_value::ParameterChoice as LogChoice;
+use triton_grpc_client::inference::model_infer_request::InferInputTensor;
+use triton_grpc_client::inference::repository_index_response::ModelIndex;
+use triton_grpc_client::inference::system_shared_memory_status_response::RegionStatus;
+use triton_grpc_client::inference::{
+    CudaSharedMemoryRegisterRequest, CudaSharedMemoryRegisterResponse,
+    CudaSharedMemoryStatusRequest, CudaSharedMemoryStatusResponse,
+    CudaSharedMemoryUnregisterRequest, CudaSharedMemoryUnregisterResponse, InferParameter,
+    LogSettingsRequest, LogSettingsResponse, ModelConfigRequest, ModelConfigResponse,
+    ModelInferRequest, ModelInferResponse, ModelMetadataRequest, ModelMetadataResponse,
+    ModelReadyRequest, ModelReadyResponse, ModelStatistics, ModelStatisticsRequest,
+    ModelStatisticsResponse, ModelStreamInferResponse, RepositoryIndexRequest,
+    RepositoryIndexResponse, RepositoryModelLoadRequest, RepositoryModelLoadResponse,
+    RepositoryModelUnloadRequest, RepositoryModelUnloadResponse, ServerLiveRequest,
+    ServerLiveResponse, ServerMetadataRequest, ServerMetadataResponse, ServerReadyRequest,
+    ServerReadyResponse, SystemSharedMemoryRegisterRequest, SystemSharedMemoryRegisterResponse,
+    SystemSharedMemoryStatusRequest, SystemSharedMemoryStatusResponse,
+    SystemSharedMemoryUnregisterRequest, SystemSharedMemoryUnregisterResponse, TraceSettingRequest,
+    TraceSettingResponse, log_settings_response, trace_setting_response,
+};
+use triton_types::DataType;
+
+const SHARED_MEMORY_REGION: &str = "shared_memory_region";
+const SHARED_MEMORY_OFFSET: &str = "shared_memory_offset";
+const SHARED_MEMORY_BYTE_SIZE: &str = "shared_memory_byte_size";
+const FINAL_RESPONSE: &str = "triton_final_response";
+const EMPTY_FINAL_RESPONSE: &str = "triton_enable_empty_final_response";
+
+const EXTENSIONS: &[&str] = &[
+    "classification",
+    "sequence",
+    "model_repository",
+    "model_repository(unload_dependents)",
+    "schedule_policy",
+    "model_configuration",
+    "system_shared_memory",
+    "binary_tensor_data",
+    "parameters",
+    "statistics",
+    "trace",
+    "logging",
+];
+
+/// An inference request that passed its model's checks.
+struct Job {
+    request: ModelInferRequest,
+    version: String,
+    latency: Duration,
+}
+
+#[derive(Clone)]
+pub(crate) struct MockService {
+    state: SharedState,
+}
+
+impl MockService {
+    pub(crate) fn new(state: SharedState) -> Self {
+        Self { state }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+
+    /// Counts a call of `rpc`, waits out the server's latency and fails with
+    /// the next error injected for it, if any.
+    async fn enter(&self, rpc: &str) -> Result<(), Status> {
+        let (latency, error) = {
+            let mut state = self.state();
+            *state.calls.entry(rpc.to_string()).or_default() += 1;
+            let error = state.errors.get_mut(rpc).and_then(VecDeque::pop_front);
+            (state.latency, error)
+        };
+
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+        error.map_or(Ok(()), Err)
+    }
+
+    /// Runs one request through its model, which answers with one response
+    /// or, on a stream, with any number.
+    async fn infer(
+        &self,
+        request: ModelInferRequest,
+        stream: bool,
+    ) -> Vec<Result<ModelInferResponse, Status>> {
+        let (job, reply) = match self.prepare(request, stream) {
+            Ok(prepared) => prepared,
+            Err(status) => return vec![Err(status)],
+        };
+        if !job.latency.is_zero() {
+            tokio::time::sleep(job.latency).await;
+        }
+
+        reply(&job.request)
+            .into_iter()
+            .map(|response| response.and_then(|response| self.finish(&job, response)))
+            .collect()
+    }
+
+    fn prepare(
+        &self,
+        mut request: ModelInferRequest,
+        stream: bool,
+    ) -> Result<(Job, Reply), Status> {
+        let mut state = self.state();
+        state.requests.push(request.clone());
+        let State {
+            models,
+            regions,
+            inferences,
+            ..
+        } = &mut *state;
+
+        let model = models
+            .get_mut(&request.model_name)
+            .filter(|model| model.has_version(&request.model_version))
+            .ok_or_else(|| unknown_model(&request.model_name, &request.model_version))?;
+        if !model.ready {
+            return Err(Status::unavailable(format!(
+                "Model '{}' is not ready",
+                model.name
+            )));
+        }
+        read_inputs(&mut request, regions)?;
+        *inferences.entry(model.name.clone()).or_default() += 1;
+
+        let version = match request.model_version.as_str() {
+            "" => model.latest_version().to_string(),
+            version => version.to_string(),
+        };
+        let job = Job {
+            request,
+            version,
+            latency: model.latency,
+        };
+        Ok((job, model.reply(stream)))
+    }
+
+    /// Fills in what identifies the request, keeps only the requested
+    /// outputs and moves those requested in shared memory there.
+    fn finish(
+        &self,
+        job: &Job,
+        mut response: ModelInferResponse,
+    ) -> Result<ModelInferResponse, Status> {
+        let request = &job.request;
+        if response.model_name.is_empty() {
+            response.model_name = request.model_name.clone();
+        }
+        if response.model_version.is_empty() {
+            response.model_version = job.version.clone();
+        }
+        if response.id.is_empty() {
+            response.id = request.id.clone();
+        }
+
+        select_outputs(request, &mut response);
+        write_shared_outputs(request, &mut response, &mut self.state().regions)?;
+        Ok(response)
+    }
+
+    /// The stream messages answering one request on `ModelStreamInfer`.
+    async fn stream_messages(&self, request: ModelInferRequest) -> Vec<ModelStreamInferResponse> {
+        let empty_final = matches!(
+            request.parameters.get(EMPTY_FINAL_RESPONSE),
+            Some(InferParameter {
+                parameter_choice: Some(ParameterChoice::BoolParam(true)),
+            })
+        );
+        let identity = ModelInferResponse {
+            model_name: request.model_name.clone(),
+            model_version: request.model_version.clone(),
+            id: request.id.clone(),
+            ..Default::default()
+        };
+
+        let responses = self.infer(request, true).await;
+        let count = responses.len();
+        let mut messages = Vec::with_capacity(count + 1);
+
+        for (index, response) in responses.into_iter().enumerate() {
+            match response {
+                Ok(mut response) => {
+                    let is_final = !empty_final && index + 1 == count;
+                    response
+                        .parameters
+                        .insert(FINAL_RESPONSE.to_string(), bool_parameter(is_final));
+                    messages.push(ModelStreamInferResponse {
+                        error_message: String::new(),
+                        infer_response: Some(response),
+                    });
+                }
+                Err(status) => {
+                    // A failed request gets no further responses.
+                    messages.push(ModelStreamInferResponse {
+                        error_message: status.message().to_string(),
+                        infer_response: Some(identity),
+                    });
+                    return messages;
+                }
+            }
+        }
+
+        if empty_final {
+            let mut response = identity;
+            response
+                .parameters
+                .insert(FINAL_RESPONSE.to_string(), bool_parameter(true));
+            messages.push(ModelStreamInferResponse {
+                error_message: String::new(),
+                infer_response: Some(response),
+            });
+        }
+        messages
+    }
+}
+
+fn unknown_model(name: &str, version: &str) -> Status {
+    match version {
+        "" => Status::not_found(format!("Request for unknown model: '{name}' is not found")),
+        version => Status::not_found(format!(
+            "Request for unknown model: '{name}' version {version} is not found"
+        )),
+    }
+}
+
+fn bool_parameter(value: bool) -> InferParameter {
+    InferParameter {
+        parameter_choice: Some(ParameterChoice::BoolParam(value)),
+    }
+}
+
+/// The region, offset and byte size set on a tensor in shared memory.
+fn location(parameters: &HashMap<String, InferParameter>) -> Option<(&str, usize, usize)> {
+    let value = |key| match &parameters.get(key)?.parameter_choice {
+        Some(ParameterChoice::Int64Param(value)) => usize::try_from(*value).ok(),
+        Some(ParameterChoice::Uint64Param(value)) => usize::try_from(*value).ok(),
+        _ => None,
+    };
+
+    let region = match &parameters.get(SHARED_MEMORY_REGION)?.parameter_choice {
+        Some(ParameterChoice::StringParam(region)) => region.as_str(),
+        _ => return None,
+    };
+    Some((
+        region,
+        value(SHARED_MEMORY_OFFSET).unwrap_or(0),
+        value(SHARED_MEMORY_BYTE_SIZE)?,
+    ))
+}
+
+fn unknown_region(name: &str) -> Status {
+    Status::invalid_argument(format!(
+        "Unable to find system shared memory region: '{name}'"
+    ))
+}
+
+/// Copies inputs in shared memory or in typed `contents` into
+/// `raw_input_contents`, so that it holds one entry per input.
+fn read_inputs(
+    request: &mut ModelInferRequest,
+    regions: &HashMap<String, Region>,
+) -> Result<(), Status> {
+    if !request.inputs.iter().any(|input| {
+        input.parameters.contains_key(SHARED_MEMORY_REGION) || input.contents.is_some()
+    }) {
+        return Ok(());
+    }
+
+    let mut raw = std::mem::take(&mut request.raw_input_contents).into_iter();
+    let mut contents = Vec::with_capacity(request.inputs.len());
+    for input in &request.inputs {
+        let data = match location(&input.parameters) {
+            Some((name, offset, byte_size)) => regions
+                .get(name)
+                .ok_or_else(|| unknown_region(name))?
+                .read(offset, byte_size)
+                .ok_or_else(|| {
+                    Status::invalid_argument(format!(
+                        "Input '{}' exceeds shared memory region '{name}'",
+                        input.name
+                    ))
+                })?
+                .to_vec()
+                .into(),
+            None if input.contents.is_some() => typed_bytes(input)?.into(),
+            None => raw.next().ok_or_else(|| {
+                Status::invalid_argument(format!("Input '{}' has no raw data", input.name))
+            })?,
+        };
+        contents.push(data);
+    }
+
+    request.raw_input_contents = contents;
+    Ok(())
+}
+
+/// The little-endian bytes of an input sent in typed `contents`.
+fn typed_bytes(input: &InferInputTensor) -> Result<Vec<u8>, Status> {
+    fn narrow<S: Copy, T: TryFrom<S>, const N: usize>(
+        input: &InferInputTensor,
+        values: &[S],
+        to_le: fn(T) -> [u8; N],
+    ) -> Result<Vec<u8>, Status> {
+        let mut bytes = Vec::with_capacity(values.len() * N);
+        for &value in values {
+            let value = T::try_from(value).map_err(|_| {
+                Status::invalid_argument(format!(
+                    "Input '{}' has a value out of range for {}",
+                    input.name, input.datatype
+                ))
+            })?;
+            bytes.extend_from_slice(&to_le(value));
+        }
+        Ok(bytes)
+    }
+
+    let contents = input.contents.as_ref().expect("typed input");
+    let datatype = input.datatype.parse::<DataType>().map_err(|_| {
+        Status::invalid_argument(format!(
+            "Input '{}' has unknown datatype '{}'",
+            input.name, input.datatype
+        ))
+    })?;
+    match datatype {
+        DataType::Bool => Ok(contents
+            .bool_contents
+            .iter()
+            .map(|&b| u8::from(b))
+            .collect()),
+        DataType::Uint8 => narrow(input, &contents.uint_contents, u8::to_le_bytes),
+        DataType::Uint16 => narrow(input, &contents.uint_contents, u16::to_le_bytes),
+        DataType::Uint32 => narrow(input, &contents.uint_contents, u32::to_le_bytes),
+        DataType::Uint64 => narrow(input, &contents.uint64_contents, u64::to_le_bytes),
+        DataType::Int8 => narrow(input, &contents.int_contents, i8::to_le_bytes),
+        DataType::Int16 => narrow(input, &contents.int_contents, i16::to_le_bytes),
+        DataType::Int32 => narrow(input, &contents.int_contents, i32::to_le_bytes),
+        DataType::Int64 => narrow(input, &contents.int64_contents, i64::to_le_bytes),
+        DataType::Fp32 => narrow(input, &contents.fp32_contents, f32::to_le_bytes),
+        DataType::Fp64 => narrow(input, &contents.fp64_contents, f64::to_le_bytes),
+        DataType::Bytes => {
+            let mut bytes = Vec::new();
+            for value in &contents.bytes_contents {
+                bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(value);
+            }
+            Ok(bytes)
+        }
+        DataType::Fp16 | DataType::Bf16 | DataType::Invalid => {
+            Err(Status::invalid_argument(format!(
+                "Input '{}' is {}, which has no typed contents",
+                input.name, input.datatype
+            )))
+        }
+    }
+}
+
+/// Drops outputs the request did not ask for, unless it asked for none.
+fn select_outputs(request: &ModelInferRequest, response: &mut ModelInferResponse) {
+    if request.outputs.is_empty() {
+        return;
+    }
+
+    let raw = response.raw_output_contents.len() == response.outputs.len();
+    let mut contents = std::mem::take(&mut response.raw_output_contents).into_iter();
+    for output in std::mem::take(&mut response.outputs) {
+        let data = if raw { contents.next() } else { None };
+        if request
+            .outputs
+            .iter()
+            .any(|requested| requested.name == output.name)
+        {
+            response.outputs.push(output);
+            response.raw_output_contents.extend(data);
+        }
+    }
+    response.raw_output_contents.extend(contents);
+}
+
+/// Writes outputs requested in shared memory to their region and removes
+/// their data from the response, as Triton does.
+fn write_shared_outputs(
+    request: &ModelInferRequest,
+    response: &mut ModelInferResponse,
+    regions: &mut HashMap<String, Region>,
+) -> Result<(), Status> {
+    let mut shared = vec![false; response.outputs.len()];
+
+    for requested in &request.outputs {
+        let Some((name, offset, byte_size)) = location(&requested.parameters) else {
+            continue;
+        };
+        let Some(index) = response
+            .outputs
+            .iter()
+            .position(|output| output.name == requested.name)
+        else {
+            continue;
+        };
+
+        let data = response
+            .raw_output_contents
+            .get(index)
+            .filter(|_| response.raw_output_contents.len() == response.outputs.len())
+            .ok_or_else(|| {
+                Status::internal(format!(
+                    "Output '{}' must be in raw_output_contents to be written to shared memory",
+                    requested.name
+                ))
+            })?;
+        if data.len() > byte_size {
+            return Err(Status::invalid_argument(format!(
+                "Output '{}' needs {} bytes but {byte_size} are reserved in shared memory",
+                requested.name,
+                data.len()
+            )));
+        }
+
+        let region = regions.get_mut(name).ok_or_else(|| unknown_region(name))?;
+        if !region.write(offset, data) {
+            return Err(Status::invalid_argument(format!(
+                "Output '{}' exceeds shared memory region '{name}'",
+                requested.name
+            )));
+        }
+        shared[index] = true;
+    }
+
+    let mut index = 0;
+    response.raw_output_contents.retain(|_| {
+        index += 1;
+        !shared.get(index - 1).copied().unwrap_or(false)
+    });
+    Ok(())
+}
+
+#[tonic::async_trait]
+impl GrpcInferenceService for MockService {
+    async fn server_live(
+        &self,
+        _request: Request<ServerLiveRequest>,
+    ) -> Result<Response<ServerLiveResponse>, Status> {
+        self.enter("server_live").await?;
+        let live = self.state().live;
+        Ok(Response::new(ServerLiveResponse { live }))
+    }
+
+    async fn server_ready(
+        &self,
+        _request: Request<ServerReadyRequest>,
+    ) -> Result<Response<ServerReadyResponse>, Status> {
+        self.enter("server_ready").await?;
+        let state = self.state();
+        Ok(Response::new(ServerReadyResponse {
+            ready: state.live && state.ready,
+        }))
+    }
+
+    async fn model_ready(
+        &self,
+        request: Request<ModelReadyRequest>,
+    ) -> Result<Response<ModelReadyResponse>, Status> {
+        self.enter("model_ready").await?;
+        let request = request.into_inner();
+        let ready = self
+            .state()
+            .models
+            .get(&request.name)
+            .is_some_and(|model| model.ready && model.has_version(&request.version));
+        Ok(Response::new(ModelReadyResponse { ready }))
+    }
+
+    async fn server_metadata(
+        &self,
+        _request: Request<ServerMetadataRequest>,
+    ) -> Result<Response<ServerMetadataResponse>, Status> {
+        self.enter("server_metadata").await?;
+        Ok(Response::new(ServerMetadataResponse {
+            name: "triton-mock".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            extensions: EXTENSIONS.iter().map(|name| name.to_string()).collect(),
+        }))
+    }
+
+    async fn model_metadata(
+        &self,
+        request: Request<ModelMetadataRequest>,
+    ) -> Result<Response<ModelMetadataResponse>, Status> {
+        self.enter("model_metadata").await?;
+        let request = request.into_inner();
+        let state = self.state();
+        let model = state
+            .models
+            .get(&request.name)
+            .filter(|model| model.has_version(&request.version))
+            .ok_or_else(|| unknown_model(&request.name, &request.version))?;
+        Ok(Response::new(model.metadata()))
+    }
+
+    async fn model_infer(
+        &self,
+        request: Request<ModelInferRequest>,
+    ) -> Result<Response<ModelInferResponse>, Status> {
+        self.enter("model_infer").await?;
+        let mut responses = self.infer(request.into_inner(), false).await;
+        let response = responses
+            .drain(..)
+            .next()
+            .unwrap_or_else(|| Err(Status::internal("Mock handler returned no response")))?;
+        Ok(Response::new(response))
+    }
+
+    type ModelStreamInferStream = mpsc::Receiver<Result<ModelStreamInferResponse, Status>>;
+
+    async fn model_stream_infer(
+        &self,
+        request: Request<Streaming<ModelInferRequest>>,
+    ) -> Result<Response<Self::ModelStreamInferStream>, Status> {
+        self.enter("model_stream_infer").await?;
+        let mut requests = request.into_inner();
+        let (mut sender, receiver) = mpsc::channel(16);
+        let service = self.clone();
+
+        tokio::spawn(async move {
+            while let Some(request) = requests.next().await {
+                let request = match request {
+                    Ok(request) => request,
+                    Err(status) => {
+                        let _ = sender.send(Err(status)).await;
+                        return;
+                    }
+                };
+                for message in service.stream_messages(request).await {
+                    if sender.send(Ok(message)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(receiver))
+    }
+
+    async fn model_config(
+        &self,
+        request: Request<ModelConfigRequest>,
+    ) -> Result<Response<ModelConfigResponse>, Status> {
+        self.enter("model_config").await?;
+        let request = request.into_inner();
+        let state = self.state();
+        let model = state
+            .models
+            .get(&request.name)
+            .filter(|model| model.has_version(&request.version))
+            .ok_or_else(|| unknown_model(&request.name, &request.version))?;
+        Ok(Response::new(ModelConfigResponse {
+            config: Some(model.model_config()),
+        }))
+    }
+
+    async fn model_statistics(
+        &self,
+        request: Request<ModelStatisticsRequest>,
+    ) -> Result<Response<ModelStatisticsResponse>, Status> {
+        self.enter("model_statistics").await?;
+        let request = request.into_inner();
+        let state = self.state();
+        if !request.name.is_empty() && !state.models.contains_key(&request.name) {
+            return Err(unknown_model(&request.name, &request.version));
+        }
+
+        let mut model_stats: Vec<_> = state
+            .models
+            .values()
+            .filter(|model| request.name.is_empty() || model.name == request.name)
+            .map(|model| {
+                let count = state.inferences.get(&model.name).copied().unwrap_or(0);
+                ModelStatistics {
+                    name: model.name.clone(),
+                    version: model.latest_version().to_string(),
+                    inference_count: count,
+                    execution_count: count,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        model_stats.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Response::new(ModelStatisticsResponse { model_stats }))
+    }
+
+    async fn repository_index(
+        &self,
+        request: Request<RepositoryIndexRequest>,
+    ) -> Result<Response<RepositoryIndexResponse>, Status> {
+        self.enter("repository_index").await?;
+        let request = request.into_inner();
+        let state = self.state();
+
+        let mut models: Vec<_> = state
+            .models
+            .values()
+            .filter(|model| model.ready || !request.ready)
+            .flat_map(|model| {
+                model.versions.iter().map(|version| ModelIndex {
+                    name: model.name.clone(),
+                    version: version.clone(),
+                    state: if model.ready { "READY" } else { "UNAVAILABLE" }.to_string(),
+                    reason: if model.ready { "" } else { "unloaded" }.to_string(),
+                })
+            })
+            .collect();
+        models.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        Ok(Response::new(RepositoryIndexResponse { models }))
+    }
+
+    async fn repository_model_load(
+        &self,
+        request: Request<RepositoryModelLoadRequest>,
+    ) -> Result<Response<RepositoryModelLoadResponse>, Status> {
+        self.enter("repository_model_load").await?;
+        let request = request.into_inner();
+        match self.state().models.get_mut(&request.model_name) {
+            Some(model) => model.ready = true,
+            None => {
+                return Err(Status::internal(format!(
+                    "failed to load '{}', failed to poll from model repository",
+                    request.model_name
+                )));
+            }
+        }
+        Ok(Response::new(RepositoryModelLoadResponse {}))
+    }
+
+    async fn repository_model_unload(
+        &self,
+        request: Request<RepositoryModelUnloadRequest>,
+    ) -> Result<Response<RepositoryModelUnloadResponse>, Status> {
+        self.enter("repository_model_unload").await?;
+        let request = request.into_inner();
+        if let Some(model) = self.state().models.get_mut(&request.model_name) {
+            model.ready = false;
+        }
+        Ok(Response::new(RepositoryModelUnloadResponse {}))
+    }
+
+    async fn system_shared_memory_status(
+        &self,
+        request: Request<SystemSharedMemoryStatusRequest>,
+    ) -> Result<Response<SystemSharedMemoryStatusResponse>, Status> {
+        self.enter("system_shared_memory_status").await?;
+        let request = request.into_inner();
+        let state = self.state();
+        if !request.name.is_empty() && !state.regions.contains_key(&request.name) {
+            return Err(unknown_region(&request.name));
+        }
+
+        let regions = state
+            .regions
+            .iter()
+            .filter(|(name, _)| request.name.is_empty() || **name == request.name)
+            .map(|(name, region)| (name.clone(), region.status.clone()))
+            .collect();
+        Ok(Response::new(SystemSharedMemoryStatusResponse { regions }))
+    }
+
+    async fn system_shared_memory_register(
+        &self,
+        request: Request<SystemSharedMemoryRegisterRequest>,
+    ) -> Result<Response<SystemSharedMemoryRegisterResponse>, Status> {
+        self.enter("system_shared_memory_register").await?;
+        let request = request.into_inner();
+        let mut state = self.state();
+        if state.regions.contains_key(&request.name) {
+            return Err(Status::already_exists(format!(
+                "shared memory region '{}' already in manager",
+                request.name
+            )));
+        }
+
+        let region = Region::open(RegionStatus {
+            name: request.name.clone(),
+            key: request.key.clone(),
+            offset: request.offset,
+            byte_size: request.byte_size,
+        })
+        .map_err(|error| {
+            Status::invalid_argument(format!(
+                "Unable to open shared memory region '{}': {error}",
+                request.key
+            ))
+        })?;
+        state.regions.insert(request.name, region);
+        Ok(Response::new(SystemSharedMemoryRegisterResponse {}))
+    }
+
+    async fn system_shared_memory_unregister(
+        &self,
+        request: Request<SystemSharedMemoryUnregisterRequest>,
+    ) -> Result<Response<SystemSharedMemoryUnregisterResponse>, Status> {
+        self.enter("system_shared_memory_unregister").await?;
+        let request = request.into_inner();
+        let mut state = self.state();
+        if request.name.is_empty() {
+            state.regions.clear();
+        } else {
+            state.regions.remove(&request.name);
+        }
+        Ok(Response::new(SystemSharedMemoryUnregisterResponse {}))
+    }
+
+    async fn cuda_shared_memory_status(
+        &self,
+        _request: Request<CudaSharedMemoryStatusRequest>,
+    ) -> Result<Response<CudaSharedMemoryStatusResponse>, Status> {
+        self.enter("cuda_shared_memory_status").await?;
+        Err(no_cuda())
+    }
+
+    async fn cuda_shared_memory_register(
+        &self,
+        _request: Request<CudaSharedMemoryRegisterRequest>,
+    ) -> Result<Response<CudaSharedMemoryRegisterResponse>, Status> {
+        self.enter("cuda_shared_memory_register").await?;
+        Err(no_cuda())
+    }
+
+    async fn cuda_shared_memory_unregister(
+        &self,
+        _request: Request<CudaSharedMemoryUnregisterRequest>,
+    ) -> Result<Response<CudaSharedMemoryUnregisterResponse>, Status> {
+        self.enter("cuda_shared_memory_unregister").await?;
+        Err(no_cuda())
+    }
+
+    async fn trace_setting(
+        &self,
+        request: Request<TraceSettingRequest>,
+    ) -> Result<Response<TraceSettingResponse>, Status> {
+        self.enter("trace_setting").await?;
+        let request = request.into_inner();
+        let mut state = self.state();
+
+        let settings = state.trace.entry(request.model_name.clone()).or_default();
+        for (key, value) in request.settings {
+            if value.value.is_empty() {
+                settings.remove(&key);
+            } else {
+                settings.insert(key, value.value);
+            }
+        }
+
+        // Model settings override the global ones.
+        let mut merged = state.trace.get("").cloned().unwrap_or_default();
+        if let Some(model) = state.trace.get(&request.model_name) {
+            merged.extend(model.clone());
+        }
+        let settings = merged
+            .into_iter()
+            .map(|(key, value)| (key, trace_setting_response::SettingValue { value }))
+            .collect();
+        Ok(Response::new(TraceSettingResponse { settings }))
+    }
+
+    async fn log_settings(
+        &self,
+        request: Request<LogSettingsRequest>,
+    ) -> Result<Response<LogSettingsResponse>, Status> {
+        self.enter("log_settings").await?;
+        let request = request.into_inner();
+        let mut state = self.state();
+
+        for (key, value) in request.settings {
+            let choice = match value.parameter_choice {
+                Some(LogRequestChoice::BoolParam(value)) => LogChoice::BoolParam(value),
+                Some(LogRequestChoice::Uint32Param(value)) => LogChoice::Uint32Param(value),
+                Some(LogRequestChoice::StringParam(value)) => LogChoice::StringParam(value),
+                None => continue,
+            };
+            state.log.insert(
+                key,
+                log_settings_response::SettingValue {
+                    parameter_choice: Some(choice),
+                },
+            );
+        }
+        Ok(Response::new(LogSettingsResponse {
+            settings: state.log.clone(),
+        }))
+    }
+}
+
+fn no_cuda() -> Status {
+    Status::unimplemented("CUDA shared memory is not supported by the mock server")
+}
+
+#[tonic::async_trait]
+impl Health for MockService {
+    async fn check(
+        &self,
+        _request: Request<HealthCheckRequest>,
+    ) -> Result<Response<HealthCheckResponse>, Status> {
+        self.enter("check").await?;
+        let state = self.state();
+        let status = if state.live && state.ready {
+            ServingStatus::Serving
+        } else {
+            ServingStatus::NotServing
+        };
+        Ok(Response::new(HealthCheckResponse {
+            status: status.into(),
+        }))
+    }
+}
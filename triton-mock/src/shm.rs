@@ -0,0 +1,140 @@
+use std::io;
+use triton_grpc_client::inference::system_shared_memory_status_response::RegionStatus;
+
+#[cfg(unix)]
+use unix::Mapping;
+#[cfg(not(unix))]
+use unsupported::Mapping;
+
+/// A system shared memory region a client registered, mapped the way Triton
+/// maps it. The object itself belongs to the client and is never unlinked.
+pub(crate) struct Region {
+    pub(crate) status: RegionStatus,
+    mapping: Mapping,
+}
+
+impl Region {
+    pub(crate) fn open(status: RegionStatus) -> io::Result<Self> {
+        let offset = usize::try_from(status.offset).map_err(|_| invalid("offset"))?;
+        let byte_size = usize::try_from(status.byte_size).map_err(|_| invalid("byte size"))?;
+        let mapping = Mapping::open(&status.key, offset, byte_size)?;
+        Ok(Self { status, mapping })
+    }
+
+    /// `byte_size` bytes at `offset` into the region.
+    pub(crate) fn read(&self, offset: usize, byte_size: usize) -> Option<&[u8]> {
+        let end = offset.checked_add(byte_size)?;
+        self.mapping.bytes().get(offset..end)
+    }
+
+    pub(crate) fn write(&mut self, offset: usize, data: &[u8]) -> bool {
+        let Some(end) = offset.checked_add(data.len()) else {
+            return false;
+        };
+        match self.mapping.bytes_mut().get_mut(offset..end) {
+            Some(target) => {
+                target.copy_from_slice(data);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn invalid(what: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid {what}"))
+}
+
+#[cfg(not(unix))]
+mod unsupported {
+    use std::io;
+
+    pub(crate) struct Mapping;
+
+    impl Mapping {
+        pub(crate) fn open(_key: &str, _offset: usize, _byte_size: usize) -> io::Result<Self> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "System shared memory needs a Unix host",
+            ))
+        }
+
+        pub(crate) fn bytes(&self) -> &[u8] {
+            &[]
+        }
+
+        pub(crate) fn bytes_mut(&mut self) -> &mut [u8] {
+            &mut []
+        }
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::ffi::CString;
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::ptr::NonNull;
+
+    pub(crate) struct Mapping {
+        ptr: NonNull<u8>,
+        len: usize,
+        offset: usize,
+    }
+
+    // Only the server's state, behind its mutex, touches the mapping.
+    unsafe impl Send for Mapping {}
+    unsafe impl Sync for Mapping {}
+
+    impl Mapping {
+        pub(crate) fn open(key: &str, offset: usize, byte_size: usize) -> io::Result<Self> {
+            let name = CString::new(key).map_err(|_| super::invalid("key"))?;
+            let len = offset
+                .checked_add(byte_size)
+                .filter(|&len| len > 0)
+                .ok_or_else(|| super::invalid("byte size"))?;
+
+            let fd = unsafe { libc::shm_open(name.as_ptr(), libc::O_RDWR, 0) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+            let ptr = unsafe {
+                libc::mmap(
+                    std::ptr::null_mut(),
+                    len,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_SHARED,
+                    fd.as_raw_fd(),
+                    0,
+                )
+            };
+            if ptr == libc::MAP_FAILED {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Self {
+                ptr: NonNull::new(ptr.cast()).ok_or_else(|| io::Error::other("mmap failed"))?,
+                len,
+                offset,
+            })
+        }
+
+        pub(crate) fn bytes(&self) -> &[u8] {
+            let all = unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) };
+            &all[self.offset..]
+        }
+
+        pub(crate) fn bytes_mut(&mut self) -> &mut [u8] {
+            let all = unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) };
+            &mut all[self.offset..]
+        }
+    }
+
+    impl Drop for Mapping {
+        fn drop(&mut self) {
+            unsafe { libc::munmap(self.ptr.as_ptr().cast(), self.len) };
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use futures::StreamExt;
+use std::time::Duration;
+use triton_client::{
+    ClientConfig, DataType, InferRequest, RetryPolicy, TritonClient, TritonInputs, TritonOutputs,
+};
+use triton_grpc_client::inference::ModelInferResponse;
+use triton_grpc_client::inference::model_infer_response::InferOutputTensor;
+use triton_mock::{MockModel, MockServer, Status};
+
+#[derive(TritonInputs)]
+struct AddInputs {
+    #[triton(shape = [-1])]
+    a: Vec<i32>,
+    #[triton(shape = [-1])]
+    b: Vec<i32>,
+}
+
+#[derive(TritonOutputs, Debug, PartialEq)]
+struct AddOutputs {
+    #[triton(shape = [-1])]
+    sum: Vec<i32>,
+}
+
+fn add(inputs: AddInputs) -> AddOutputs {
+    let sum = inputs.a.iter().zip(&inputs.b).map(|(a, b)| a + b).collect();
+    AddOutputs { sum }
+}
+
+async fn start() -> (MockServer, TritonClient) {
+    let server = MockServer::start([MockModel::new("add").typed_handler(add)])
+        .await
+        .unwrap();
+    let client = TritonClient::new(&server.url()).await.unwrap();
+    (server, client)
+}
+
+fn counter(count: i32) -> ModelInferResponse {
+    ModelInferResponse {
+        outputs: vec![InferOutputTensor {
+            name: "count".to_string(),
+            datatype: "INT32".to_string(),
+            shape: vec![1],
+            ..Default::default()
+        }],
+        raw_output_contents: vec![count.to_le_bytes().to_vec()],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn health_and_metadata() {
+    let (server, client) = start().await;
+    assert!(client.server_live().await.unwrap());
+    assert!(client.server_ready().await.unwrap());
+    assert!(client.model_ready("add", "").await.unwrap());
+    assert!(!client.model_ready("missing", "").await.unwrap());
+
+    let metadata = client.model_metadata("add", "").await.unwrap();
+    assert_eq!(metadata.inputs.len(), 2);
+    assert_eq!(metadata.outputs[0].name, "sum");
+
+    server.set_ready(false);
+    assert!(!client.server_ready().await.unwrap());
+    assert!(client.model_metadata("missing", "").await.is_err());
+}
+
+#[tokio::test]
+async fn typed_inference() {
+    let (server, client) = start().await;
+    let inputs = AddInputs {
+        a: vec![1, 2, 3],
+        b: vec![10, 20, 30],
+    };
+    let outputs: AddOutputs = client.infer_typed("add", "", &inputs).await.unwrap();
+    assert_eq!(outputs.sum, [11, 22, 33]);
+
+    let request = InferRequest::new("add")
+        .input("a", &[1], &vec![4])
+        .input("b", &[1], &vec![5]);
+    let outputs = client.infer_request(request).await.unwrap();
+    assert_eq!(outputs[0].as_slice::<i32>(), Some(&[9][..]));
+    assert_eq!(server.requests().len(), 2);
+
+    let statistics = client.model_statistics("add", "").await.unwrap();
+    assert_eq!(statistics[0].inference_count, 2);
+}
+
+#[tokio::test]
+async fn typed_contents_inference() {
+    let (server, client) = start().await;
+    let request = InferRequest::new("add")
+        .input("a", &[2], &vec![1, -2])
+        .input("b", &[2], &vec![10, 20])
+        .typed_contents(true);
+    let outputs = client.infer_request(request).await.unwrap();
+    assert_eq!(outputs[0].as_slice::<i32>(), Some(&[11, 18][..]));
+
+    let sent = &server.requests()[0];
+    assert!(sent.raw_input_contents.is_empty());
+    assert_eq!(
+        sent.inputs[0].contents.as_ref().unwrap().int_contents,
+        [1, -2]
+    );
+}
+
+#[tokio::test]
+async fn injected_errors_are_retried() {
+    let server = MockServer::start([MockModel::new("add").typed_handler(add)])
+        .await
+        .unwrap();
+    let retry = RetryPolicy::new()
+        .max_attempts(3)
+        .backoff(Duration::from_millis(1), Duration::from_millis(5))
+        .inference(true);
+    let client = ClientConfig::new(server.url())
+        .retry(retry)
+        .connect()
+        .await
+        .unwrap();
+
+    server.fail_next("model_infer", Status::unavailable("reloading"));
+    let inputs = AddInputs {
+        a: vec![1],
+        b: vec![2],
+    };
+    let outputs: AddOutputs = client.infer_typed("add", "", &inputs).await.unwrap();
+    assert_eq!(outputs.sum, [3]);
+    assert_eq!(server.calls("model_infer"), 2);
+
+    server.fail_next("server_live", Status::invalid_argument("no"));
+    assert!(client.server_live().await.is_err());
+    assert_eq!(server.calls("server_live"), 1);
+}
+
+#[tokio::test]
+async fn scripted_responses_and_readiness() {
+    let model = MockModel::new("counter")
+        .output("count", DataType::Int32, &[1])
+        .respond(counter(1))
+        .fail(Status::internal("boom"));
+    let server = MockServer::start([model]).await.unwrap();
+    let client = TritonClient::new(&server.url()).await.unwrap();
+
+    let outputs = client
+        .infer_request(InferRequest::new("counter"))
+        .await
+        .unwrap();
+    assert_eq!(outputs[0].as_slice::<i32>(), Some(&[1][..]));
+    let error = client.infer_request(InferRequest::new("counter")).await;
+    assert!(error.unwrap_err().to_string().contains("boom"));
+
+    client.unload_model("counter", false).await.unwrap();
+    assert!(!client.model_ready("counter", "").await.unwrap());
+    assert!(
+        client
+            .infer_request(InferRequest::new("counter"))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn decoupled_stream() {
+    let model = MockModel::new("countdown")
+        .stream_handler(|_| (1..=3).map(|count| Ok(counter(count))).collect());
+    let server = MockServer::start([model]).await.unwrap();
+    let client = TritonClient::new(&server.url()).await.unwrap();
+    assert!(
+        client
+            .infer_request(InferRequest::new("countdown"))
+            .await
+            .is_err()
+    );
+
+    let mut stream = client.stream().await.unwrap();
+    let id = stream
+        .send_request(InferRequest::new("countdown"))
+        .await
+        .unwrap();
+
+    let mut counts = vec![];
+    while let Some(response) = stream.next().await {
+        let response = response.unwrap();
+        assert_eq!(response.id, id);
+        if let Some(output) = response.outputs().unwrap().first() {
+            counts.extend_from_slice(output.as_slice::<i32>().unwrap());
+        }
+        if response.is_final {
+            break;
+        }
+    }
+    assert_eq!(counts, [1, 2, 3]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn shared_memory_round_trip() {
+    use triton_client::SharedMemory;
+
+    let (server, client) = start().await;
+    let key = format!("/triton-mock-{}", std::process::id());
+    let memory = SharedMemory::create(&key, 64).unwrap();
+    let mut region = client
+        .register_system_shared_memory("io", memory)
+        .await
+        .unwrap();
+    assert_eq!(server.shared_memory_regions(), ["io"]);
+
+    let a = region.write(0, &vec![1i32, 2]).unwrap();
+    let b = region.write(a, &vec![3i32, 4]).unwrap();
+    let request = InferRequest::new("add")
+        .shm_input("a", DataType::Int32, &[2], "io", 0, a)
+        .shm_input("b", DataType::Int32, &[2], "io", a, b)
+        .shm_output("sum", "io", 32, 8)
+        .build()
+        .unwrap();
+    let response = client.infer(request).await.unwrap();
+    assert!(response.raw_output_contents.is_empty());
+
+    let sum = region.output(&response, "sum").unwrap();
+    assert_eq!(sum.as_slice::<i32>(), Some(&[4, 6][..]));
+
+    region.unregister().await.unwrap();
+    assert!(server.shared_memory_regions().is_empty());
+}
@@ -0,0 +1,51 @@
+/// Just enough of a protobuf descriptor to map field names onto tags.
+pub(crate) struct Message {
+    pub name: &'static str,
+    pub fields: &'static [Field],
+    /// The `key`/`value` entry type of a map field.
+    pub map_entry: bool,
+}
+
+impl Message {
+    pub fn field(&self, name: &str) -> Option<&'static Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+pub(crate) struct Field {
+    pub name: &'static str,
+    pub number: u32,
+    pub kind: Kind,
+    pub repeated: bool,
+    /// The oneof this field is a member of, of which at most one member may
+    /// be set.
+    pub oneof: Option<&'static str>,
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Kind {
+    Double,
+    Float,
+    Int64,
+    Uint64,
+    Int32,
+    Uint32,
+    Bool,
+    String,
+    Enum(&'static Enum),
+    Message(&'static Message),
+}
+
+pub(crate) struct Enum {
+    pub name: &'static str,
+    pub values: &'static [(&'static str, i32)],
+}
+
+impl Enum {
+    pub fn value(&self, name: &str) -> Option<i32> {
+        self.values
+            .iter()
+            .find(|(value_name, _)| *value_name == name)
+            .map(|(_, value)| *value)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::descriptor::{Field, Kind, Message};
+use crate::error::JsonError;
+use crate::wire::{FIXED32, FIXED64, VARINT, put_key, put_len, put_varint};
+use serde_json::Value;
+
+/// Converts the protobuf JSON mapping of `message` into wire format. Field
+/// names may be in `snake_case` or `lowerCamelCase`, 64-bit integers may be
+/// numbers or strings and enums may be names or numbers.
+pub(crate) fn encode(value: &Value, message: &'static Message) -> Result<Vec<u8>, JsonError> {
+    encode_message(value, message, "$")
+}
+
+fn error(path: &str, message: impl Into<String>) -> JsonError {
+    JsonError {
+        path: path.to_string(),
+        message: message.into(),
+    }
+}
+
+fn encode_message(
+    value: &Value,
+    message: &'static Message,
+    path: &str,
+) -> Result<Vec<u8>, JsonError> {
+    let Value::Object(object) = value else {
+        return Err(error(
+            path,
+            format!("Expected object for message \"{}\"", message.name),
+        ));
+    };
+    let mut buf = Vec::new();
+
+    for (key, value) in object {
+        let path = format!("{path}.{key}");
+        let field = message
+            .field(key)
+            .or_else(|| message.field(&snake_case(key)))
+            .ok_or_else(|| {
+                error(
+                    &path,
+                    format!(
+                        "Message type \"{}\" has no field named \"{key}\"",
+                        message.name
+                    ),
+                )
+            })?;
+
+        match (field.kind, value) {
+            (_, Value::Null) => {}
+            (Kind::Message(entry), Value::Object(map)) if entry.map_entry => {
+                for (key, value) in map {
+                    let path = format!("{path}.{key}");
+                    let mut bytes = Vec::new();
+                    encode_value(
+                        &entry.fields[0],
+                        &Value::String(key.clone()),
+                        &mut bytes,
+                        &path,
+                    )?;
+                    encode_value(&entry.fields[1], value, &mut bytes, &path)?;
+                    put_len(&mut buf, field.number, &bytes);
+                }
+            }
+            (_, Value::Array(items)) if field.repeated => {
+                for (index, item) in items.iter().enumerate() {
+                    encode_value(field, item, &mut buf, &format!("{path}[{index}]"))?;
+                }
+            }
+            _ if field.repeated => {
+                return Err(error(
+                    &path,
+                    format!("Field \"{}\" expects an array", field.name),
+                ));
+            }
+            _ => encode_value(field, value, &mut buf, &path)?,
+        }
+    }
+
+    Ok(buf)
+}
+
+fn encode_value(
+    field: &Field,
+    value: &Value,
+    buf: &mut Vec<u8>,
+    path: &str,
+) -> Result<(), JsonError> {
+    match field.kind {
+        Kind::Message(message) => {
+            let bytes = encode_message(value, message, path)?;
+            put_len(buf, field.number, &bytes);
+        }
+        Kind::String => {
+            let Value::String(text) = value else {
+                return Err(error(path, "Expected string"));
+            };
+            put_len(buf, field.number, text.as_bytes());
+        }
+        Kind::Bool => {
+            let Value::Bool(value) = value else {
+                return Err(error(path, "Expected boolean"));
+            };
+            put_key(buf, field.number, VARINT);
+            put_varint(buf, u64::from(*value));
+        }
+        Kind::Int32 | Kind::Int64 | Kind::Uint32 | Kind::Uint64 => {
+            let value = integer(value).ok_or_else(|| error(path, "Expected integer"))?;
+            let (min, max, type_name) = match field.kind {
+                Kind::Int32 => (i32::MIN.into(), i32::MAX.into(), "int32"),
+                Kind::Int64 => (i64::MIN.into(), i64::MAX.into(), "int64"),
+                Kind::Uint32 => (0, u32::MAX.into(), "uint32"),
+                _ => (0, u64::MAX.into(), "uint64"),
+            };
+            if value < min || value > max {
+                return Err(error(path, format!("Integer out of range for {type_name}")));
+            }
+
+            put_key(buf, field.number, VARINT);
+            put_varint(buf, value as i64 as u64);
+        }
+        Kind::Float => {
+            let value = float(value).ok_or_else(|| error(path, "Expected number"))?;
+            put_key(buf, field.number, FIXED32);
+            buf.extend_from_slice(&(value as f32).to_le_bytes());
+        }
+        Kind::Double => {
+            let value = float(value).ok_or_else(|| error(path, "Expected number"))?;
+            put_key(buf, field.number, FIXED64);
+            buf.extend_from_slice(&value.to_le_bytes());
+        }
+        Kind::Enum(enumeration) => {
+            let value = match value {
+                Value::String(name) => enumeration.value(name).ok_or_else(|| {
+                    error(
+                        path,
+                        format!(
+                            "Unknown enumeration value \"{name}\" for enum \"{}\"",
+                            enumeration.name
+                        ),
+                    )
+                })?,
+                _ => integer(value)
+                    .and_then(|value| i32::try_from(value).ok())
+                    .ok_or_else(|| error(path, "Expected enumeration name or number"))?,
+            };
+
+            put_key(buf, field.number, VARINT);
+            put_varint(buf, i64::from(value) as u64);
+        }
+    }
+
+    Ok(())
+}
+
+fn integer(value: &Value) -> Option<i128> {
+    match value {
+        Value::Number(number) => number
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| number.as_u64().map(i128::from)),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => match text.as_str() {
+            "NaN" => Some(f64::NAN),
+            "Infinity" => Some(f64::INFINITY),
+            "-Infinity" => Some(f64::NEG_INFINITY),
+            _ => text.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
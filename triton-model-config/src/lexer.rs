@@ -0,0 +1,212 @@
+use crate::error::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub fn error(self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TokenKind {
+    Identifier(String),
+    /// The literal as written; interpreted once the field type is known.
+    Number(String),
+    String(Vec<u8>),
+    Symbol(char),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub position: Position,
+}
+
+/// Splits protobuf text format into tokens, dropping whitespace and `#`
+/// comments.
+pub(crate) fn tokenize(text: &str) -> Result<Vec<Token>, ParseError> {
+    let mut lexer = Lexer {
+        chars: text.chars().collect(),
+        index: 0,
+        position: Position { line: 1, column: 1 },
+    };
+
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token()? {
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    index: usize,
+    position: Position,
+}
+
+impl Lexer {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += 1;
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, ParseError> {
+        self.skip_whitespace();
+
+        let position = self.position;
+        let Some(c) = self.peek() else {
+            return Ok(None);
+        };
+
+        let kind = if c.is_ascii_alphabetic() || c == '_' {
+            TokenKind::Identifier(self.take_while(|c| c.is_ascii_alphanumeric() || c == '_'))
+        } else if c.is_ascii_digit()
+            || (c == '.' && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            TokenKind::Number(self.number())
+        } else if c == '"' || c == '\'' {
+            TokenKind::String(self.string()?)
+        } else if "{}[]<>:,;-".contains(c) {
+            self.bump();
+            TokenKind::Symbol(c)
+        } else {
+            return Err(position.error(format!("Unexpected character '{c}'")));
+        };
+
+        Ok(Some(Token { kind, position }))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.bump();
+                }
+            } else if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn take_while(&mut self, accept: impl Fn(char) -> bool) -> String {
+        let mut value = String::new();
+        while let Some(c) = self.peek().filter(|&c| accept(c)) {
+            value.push(c);
+            self.bump();
+        }
+        value
+    }
+
+    /// Decimal, hex, octal and floating point literals, including exponents
+    /// and an `f` suffix. Validation happens in the parser.
+    fn number(&mut self) -> String {
+        let mut value = String::new();
+        while let Some(c) = self.peek() {
+            let exponent_sign = (c == '-' || c == '+')
+                && value
+                    .chars()
+                    .last()
+                    .is_some_and(|last| last == 'e' || last == 'E')
+                && !value.starts_with("0x")
+                && !value.starts_with("0X");
+            if c.is_ascii_alphanumeric() || c == '.' || exponent_sign {
+                value.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        value
+    }
+
+    fn string(&mut self) -> Result<Vec<u8>, ParseError> {
+        let start = self.position;
+        let quote = self.bump().expect("opening quote");
+        let mut bytes = Vec::new();
+
+        loop {
+            let position = self.position;
+            match self.bump() {
+                None | Some('\n') => return Err(start.error("Unterminated string literal")),
+                Some(c) if c == quote => return Ok(bytes),
+                Some('\\') => self.escape(position, &mut bytes)?,
+                Some(c) => {
+                    let mut buffer = [0u8; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                }
+            }
+        }
+    }
+
+    fn escape(&mut self, position: Position, bytes: &mut Vec<u8>) -> Result<(), ParseError> {
+        let byte = match self.bump() {
+            Some('n') => b'\n',
+            Some('r') => b'\r',
+            Some('t') => b'\t',
+            Some('a') => 0x07,
+            Some('b') => 0x08,
+            Some('f') => 0x0c,
+            Some('v') => 0x0b,
+            Some('?') => b'?',
+            Some('\\') => b'\\',
+            Some('\'') => b'\'',
+            Some('"') => b'"',
+            Some('x') | Some('X') => {
+                let digits = self.take_digits(16, 2);
+                if digits.is_empty() {
+                    return Err(position.error("Expected hex digits after \\x"));
+                }
+                u8::from_str_radix(&digits, 16).expect("at most two hex digits")
+            }
+            Some(c) if c.is_digit(8) => {
+                let digits = format!("{c}{}", self.take_digits(8, 2));
+                u8::try_from(u32::from_str_radix(&digits, 8).expect("octal digits")).map_err(
+                    |_| position.error(format!("Octal escape \\{digits} is out of range")),
+                )?
+            }
+            Some(c) => return Err(position.error(format!("Invalid escape sequence \\{c}"))),
+            None => return Err(position.error("Unterminated string literal")),
+        };
+
+        bytes.push(byte);
+        Ok(())
+    }
+
+    fn take_digits(&mut self, radix: u32, max: usize) -> String {
+        let mut digits = String::new();
+        while digits.len() < max
+            && let Some(c) = self.peek().filter(|c| c.is_digit(radix))
+        {
+            digits.push(c);
+            self.bump();
+        }
+        digits
+    }
+}
@@ -0,0 +1,73 @@
+#[path = "descriptor.rs"]
+mod descriptor;
+#[path = "error.rs"]
+pub mod error;
+#[cfg(feature = "json")]
+#[path = "json.rs"]
+mod json;
+#[path = "lexer.rs"]
+mod lexer;
+#[path = "parser.rs"]
+mod parser;
+#[path = "printer.rs"]
+mod printer;
+#[path = "schema.rs"]
+mod schema;
+#[path = "wire.rs"]
+mod wire;
+
+pub use error::*;
+pub use triton_grpc_client::inference::ModelConfig;
+
+use prost::Message;
+use std::path::Path;
+
+/// Parses a `config.pbtxt` in protobuf text format.
+pub fn parse(text: &str) -> Result<ModelConfig, ParseError> {
+    let bytes = parser::parse(text, &schema::MODEL_CONFIG)?;
+
+    ModelConfig::decode(bytes.as_slice()).map_err(|error| ParseError {
+        line: 1,
+        column: 1,
+        message: format!("Invalid model config: {error}"),
+    })
+}
+
+/// Reads a model config in the protobuf JSON mapping, as returned by
+/// Triton's HTTP endpoint.
+#[cfg(feature = "json")]
+pub fn from_json(json: &serde_json::Value) -> Result<ModelConfig, JsonError> {
+    let bytes = json::encode(json, &schema::MODEL_CONFIG)?;
+
+    ModelConfig::decode(bytes.as_slice()).map_err(|error| JsonError {
+        path: "$".to_string(),
+        message: format!("Invalid model config: {error}"),
+    })
+}
+
+pub fn load(path: impl AsRef<Path>) -> Result<ModelConfig, Error> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    parse(&text).map_err(|source| Error::Parse {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Serializes `config` as canonical text format, which [`parse`] reads back
+/// into an equal `ModelConfig`.
+pub fn to_string(config: &ModelConfig) -> String {
+    printer::print(&config.encode_to_vec(), &schema::MODEL_CONFIG)
+}
+
+pub fn save(path: impl AsRef<Path>, config: &ModelConfig) -> Result<(), Error> {
+    let path = path.as_ref();
+    std::fs::write(path, to_string(config)).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
@@ -0,0 +1,481 @@
+use crate::descriptor::{Field, Kind, Message};
+use crate::error::ParseError;
+use crate::lexer::{Position, Token, TokenKind, tokenize};
+use crate::wire::{FIXED32, FIXED64, VARINT, put_key, put_len, put_varint};
+
+/// Parses text format for `message` straight into protobuf wire format,
+/// which prost then decodes into the generated type.
+pub(crate) fn parse(text: &str, message: &'static Message) -> Result<Vec<u8>, ParseError> {
+    let tokens = tokenize(text)?;
+    let end = end_position(text);
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        end,
+    };
+
+    parser.message(message, None)
+}
+
+fn end_position(text: &str) -> Position {
+    let line = text.matches('\n').count() + 1;
+    let column = text
+        .rsplit('\n')
+        .next()
+        .map_or(0, |last| last.chars().count())
+        + 1;
+    Position { line, column }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    end: Position,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn position(&self) -> Position {
+        self.peek().map_or(self.end, |token| token.position)
+    }
+
+    fn next(&mut self) -> Result<Token, ParseError> {
+        let token = self
+            .tokens
+            .get(self.index)
+            .cloned()
+            .ok_or_else(|| self.end.error("Unexpected end of input"))?;
+        self.index += 1;
+        Ok(token)
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.peek()
+            .is_some_and(|token| token.kind == TokenKind::Symbol(symbol))
+    }
+
+    fn eat_symbol(&mut self, symbol: char) -> bool {
+        let found = self.is_symbol(symbol);
+        if found {
+            self.index += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), ParseError> {
+        if self.eat_symbol(symbol) {
+            return Ok(());
+        }
+
+        Err(self.position().error(format!(
+            "Expected '{symbol}', found {}",
+            self.describe_next()
+        )))
+    }
+
+    fn describe_next(&self) -> String {
+        match self.peek().map(|token| &token.kind) {
+            None => "end of input".to_string(),
+            Some(TokenKind::Identifier(name)) => format!("\"{name}\""),
+            Some(TokenKind::Number(number)) => number.clone(),
+            Some(TokenKind::String(_)) => "string literal".to_string(),
+            Some(TokenKind::Symbol(symbol)) => format!("'{symbol}'"),
+        }
+    }
+
+    fn message(
+        &mut self,
+        message: &'static Message,
+        close: Option<char>,
+    ) -> Result<Vec<u8>, ParseError> {
+        let mut buf = Vec::new();
+        let mut seen = Vec::new();
+        let mut oneofs: Vec<(&str, &str)> = Vec::new();
+
+        loop {
+            match close {
+                None if self.peek().is_none() => break,
+                Some(close) if self.eat_symbol(close) => break,
+                Some(close) if self.peek().is_none() => {
+                    return Err(self
+                        .end
+                        .error(format!("Expected '{close}', found end of input")));
+                }
+                _ => {}
+            }
+
+            let position = self.position();
+            let name = match self.next()?.kind {
+                TokenKind::Identifier(name) => name,
+                _ => {
+                    self.index -= 1;
+                    return Err(position.error(format!(
+                        "Expected field name, found {}",
+                        self.describe_next()
+                    )));
+                }
+            };
+
+            let field = message.field(&name).ok_or_else(|| {
+                position.error(format!(
+                    "Message type \"{}\" has no field named \"{}\"",
+                    message.name, name
+                ))
+            })?;
+
+            if !field.repeated {
+                if seen.contains(&field.number) {
+                    return Err(position.error(format!(
+                        "Non-repeated field \"{}\" is specified multiple times",
+                        field.name
+                    )));
+                }
+                seen.push(field.number);
+            }
+
+            if let Some(oneof) = field.oneof {
+                if let Some((_, other)) = oneofs.iter().find(|(group, _)| *group == oneof) {
+                    return Err(position.error(format!(
+                        "Field \"{}\" is specified along with field \"{other}\", \
+                         another member of oneof \"{oneof}\"",
+                        field.name
+                    )));
+                }
+                oneofs.push((oneof, field.name));
+            }
+
+            match field.kind {
+                Kind::Message(_) => {
+                    self.eat_symbol(':');
+                }
+                _ => self.expect_symbol(':')?,
+            }
+
+            if self.is_symbol('[') {
+                self.list(field, &mut buf)?;
+            } else {
+                self.value(field, &mut buf)?;
+            }
+
+            if !self.eat_symbol(',') {
+                self.eat_symbol(';');
+            }
+        }
+
+        Ok(buf)
+    }
+
+    fn list(&mut self, field: &Field, buf: &mut Vec<u8>) -> Result<(), ParseError> {
+        if !field.repeated {
+            return Err(self
+                .position()
+                .error(format!("Field \"{}\" is not repeated", field.name)));
+        }
+
+        self.expect_symbol('[')?;
+        if self.eat_symbol(']') {
+            return Ok(());
+        }
+
+        loop {
+            self.value(field, buf)?;
+            if self.eat_symbol(']') {
+                return Ok(());
+            }
+            self.expect_symbol(',')?;
+        }
+    }
+
+    fn value(&mut self, field: &Field, buf: &mut Vec<u8>) -> Result<(), ParseError> {
+        let position = self.position();
+        let negative = self.eat_symbol('-');
+
+        match field.kind {
+            Kind::Message(message) => {
+                if negative {
+                    return Err(position.error("Expected '{', found '-'"));
+                }
+
+                let close = if self.eat_symbol('{') {
+                    '}'
+                } else if self.eat_symbol('<') {
+                    '>'
+                } else {
+                    return Err(self
+                        .position()
+                        .error(format!("Expected '{{', found {}", self.describe_next())));
+                };
+
+                let bytes = self.message(message, Some(close))?;
+                put_len(buf, field.number, &bytes);
+            }
+            Kind::String => {
+                if negative {
+                    return Err(position.error("Expected string, found '-'"));
+                }
+
+                let bytes = self.string()?;
+                if std::str::from_utf8(&bytes).is_err() {
+                    return Err(position.error("String is not valid UTF-8"));
+                }
+                put_len(buf, field.number, &bytes);
+            }
+            Kind::Bool => {
+                let value = match self.next()?.kind {
+                    TokenKind::Identifier(name) if !negative => match name.as_str() {
+                        "true" | "True" | "t" => true,
+                        "false" | "False" | "f" => false,
+                        _ => {
+                            return Err(position.error(format!(
+                                "Invalid value for boolean field \"{}\": {}",
+                                field.name, name
+                            )));
+                        }
+                    },
+                    TokenKind::Number(number) if !negative && (number == "0" || number == "1") => {
+                        number == "1"
+                    }
+                    _ => {
+                        return Err(position.error(format!(
+                            "Invalid value for boolean field \"{}\"",
+                            field.name
+                        )));
+                    }
+                };
+                put_key(buf, field.number, VARINT);
+                put_varint(buf, u64::from(value));
+            }
+            Kind::Int32 | Kind::Int64 | Kind::Uint32 | Kind::Uint64 => {
+                let value = self.integer(position, negative)?;
+                let (min, max, type_name) = match field.kind {
+                    Kind::Int32 => (i32::MIN.into(), i32::MAX.into(), "int32"),
+                    Kind::Int64 => (i64::MIN.into(), i64::MAX.into(), "int64"),
+                    Kind::Uint32 => (0, u32::MAX.into(), "uint32"),
+                    _ => (0, u64::MAX.into(), "uint64"),
+                };
+                if value < min || value > max {
+                    return Err(position.error(format!(
+                        "Integer out of range for {} field \"{}\"",
+                        type_name, field.name
+                    )));
+                }
+
+                put_key(buf, field.number, VARINT);
+                put_varint(buf, value as i64 as u64);
+            }
+            Kind::Float => {
+                let value = self.float(position, negative)?;
+                put_key(buf, field.number, FIXED32);
+                buf.extend_from_slice(&(value as f32).to_le_bytes());
+            }
+            Kind::Double => {
+                let value = self.float(position, negative)?;
+                put_key(buf, field.number, FIXED64);
+                buf.extend_from_slice(&value.to_le_bytes());
+            }
+            Kind::Enum(enumeration) => {
+                let value = if !negative
+                    && let Some(Token {
+                        kind: TokenKind::Identifier(name),
+                        ..
+                    }) = self.peek()
+                {
+                    let value = enumeration.value(name).ok_or_else(|| {
+                        position.error(format!(
+                            "Unknown enumeration value \"{}\" for enum \"{}\"",
+                            name, enumeration.name
+                        ))
+                    })?;
+                    self.index += 1;
+                    value
+                } else {
+                    let value = self.integer(position, negative)?;
+                    i32::try_from(value).map_err(|_| {
+                        position.error(format!(
+                            "Integer out of range for enum \"{}\"",
+                            enumeration.name
+                        ))
+                    })?
+                };
+
+                put_key(buf, field.number, VARINT);
+                put_varint(buf, i64::from(value) as u64);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// One or more adjacent string literals, which are concatenated.
+    fn string(&mut self) -> Result<Vec<u8>, ParseError> {
+        let mut bytes = match self.peek() {
+            Some(Token {
+                kind: TokenKind::String(bytes),
+                ..
+            }) => bytes.clone(),
+            _ => {
+                return Err(self
+                    .position()
+                    .error(format!("Expected string, found {}", self.describe_next())));
+            }
+        };
+        self.index += 1;
+
+        while let Some(Token {
+            kind: TokenKind::String(more),
+            ..
+        }) = self.peek()
+        {
+            bytes.extend_from_slice(more);
+            self.index += 1;
+        }
+
+        Ok(bytes)
+    }
+
+    fn integer(&mut self, position: Position, negative: bool) -> Result<i128, ParseError> {
+        let number = match self.peek() {
+            Some(Token {
+                kind: TokenKind::Number(number),
+                ..
+            }) => number.clone(),
+            _ => {
+                return Err(self
+                    .position()
+                    .error(format!("Expected integer, found {}", self.describe_next())));
+            }
+        };
+        self.index += 1;
+
+        let parsed = if let Some(hex) = number
+            .strip_prefix("0x")
+            .or_else(|| number.strip_prefix("0X"))
+        {
+            u64::from_str_radix(hex, 16)
+        } else if number.len() > 1 && number.starts_with('0') {
+            u64::from_str_radix(&number[1..], 8)
+        } else {
+            number.parse::<u64>()
+        };
+        let value =
+            parsed.map_err(|_| position.error(format!("Expected integer, found {number}")))?;
+
+        Ok(if negative {
+            -i128::from(value)
+        } else {
+            i128::from(value)
+        })
+    }
+
+    fn float(&mut self, position: Position, negative: bool) -> Result<f64, ParseError> {
+        let token = self.next()?;
+        let value = match &token.kind {
+            TokenKind::Number(number) if !number.starts_with("0x") && !number.starts_with("0X") => {
+                number
+                    .strip_suffix(['f', 'F'])
+                    .unwrap_or(number)
+                    .parse::<f64>()
+                    .map_err(|_| position.error(format!("Expected number, found {number}")))?
+            }
+            TokenKind::Identifier(name) => match name.to_ascii_lowercase().as_str() {
+                "inf" | "infinity" => f64::INFINITY,
+                "nan" => f64::NAN,
+                _ => return Err(position.error(format!("Expected number, found \"{name}\""))),
+            },
+            _ => {
+                self.index -= 1;
+                return Err(self
+                    .position()
+                    .error(format!("Expected number, found {}", self.describe_next())));
+            }
+        };
+
+        Ok(if negative { -value } else { value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParseError, parse};
+
+    fn parse_error(text: &str) -> ParseError {
+        parse(text).expect_err("config should not parse")
+    }
+
+    fn position(error: &ParseError) -> (usize, usize) {
+        (error.line, error.column)
+    }
+
+    #[test]
+    fn unknown_field() {
+        let error = parse_error("name: \"mnist\"\nbogus: 1\n");
+        assert_eq!(position(&error), (2, 1));
+        assert_eq!(
+            error.message,
+            "Message type \"inference.ModelConfig\" has no field named \"bogus\""
+        );
+    }
+
+    #[test]
+    fn int32_out_of_range() {
+        let error = parse_error("max_batch_size: 2147483648");
+        assert_eq!(position(&error), (1, 17));
+        assert_eq!(
+            error.message,
+            "Integer out of range for int32 field \"max_batch_size\""
+        );
+    }
+
+    #[test]
+    fn unknown_enum_value() {
+        let error = parse_error("input {\n  name: \"x\"\n  data_type: TYPE_FLOAT32\n}\n");
+        assert_eq!(position(&error), (3, 14));
+        assert_eq!(
+            error.message,
+            "Unknown enumeration value \"TYPE_FLOAT32\" for enum \"inference.DataType\""
+        );
+    }
+
+    #[test]
+    fn duplicate_field() {
+        let error = parse_error("name: \"a\"\nplatform: \"p\"\n  name: \"b\"\n");
+        assert_eq!(position(&error), (3, 3));
+        assert_eq!(
+            error.message,
+            "Non-repeated field \"name\" is specified multiple times"
+        );
+    }
+
+    #[test]
+    fn unterminated_list() {
+        let error = parse_error("input {\n  dims: [ 1, 2\n}\n");
+        assert_eq!(position(&error), (3, 1));
+        assert_eq!(error.message, "Expected ',', found '}'");
+
+        let error = parse_error("output [\n  { name: \"y\" }\n");
+        assert_eq!(position(&error), (3, 1));
+        assert_eq!(error.message, "Expected ',', found end of input");
+    }
+
+    #[test]
+    fn oneof_members() {
+        let error = parse_error("dynamic_batching {}\nsequence_batching {}\n");
+        assert_eq!(position(&error), (2, 1));
+        assert_eq!(
+            error.message,
+            "Field \"sequence_batching\" is specified along with field \"dynamic_batching\", \
+             another member of oneof \"scheduling_choice\""
+        );
+
+        let error = parse_error(
+            "sequence_batching {\n  oldest { max_candidate_sequences: 4 }\n  direct {}\n}\n",
+        );
+        assert_eq!(position(&error), (3, 3));
+        assert!(error.message.contains("oneof \"strategy_choice\""));
+
+        assert!(parse("sequence_batching {\n  direct {}\n}\n").is_ok());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::descriptor::{Field, Kind, Message};
+use crate::wire::{Value, fields, get_varint};
+use std::fmt::Write;
+
+/// Prints wire format for `message` as canonical text format: fields in
+/// field number order, one value per line, two space indentation and map
+/// entries sorted by key.
+pub(crate) fn print(bytes: &[u8], message: &'static Message) -> String {
+    let mut out = String::new();
+    print_message(&mut out, bytes, message, 0);
+    out
+}
+
+fn print_message(out: &mut String, bytes: &[u8], message: &'static Message, depth: usize) {
+    let values = fields(bytes);
+
+    let mut ordered: Vec<&Field> = message.fields.iter().collect();
+    ordered.sort_by_key(|field| field.number);
+
+    for field in ordered {
+        let mut items: Vec<Value> = values
+            .iter()
+            .filter(|(number, _)| *number == field.number)
+            .flat_map(|(_, value)| unpack(field.kind, *value))
+            .collect();
+
+        if !field.repeated && items.len() > 1 {
+            items.drain(..items.len() - 1);
+        }
+        if let Kind::Message(entry) = field.kind
+            && entry.map_entry
+        {
+            items.sort_by_cached_key(|value| map_key(entry, *value));
+        }
+
+        for value in items {
+            print_field(out, field, value, depth);
+        }
+    }
+}
+
+fn print_field(out: &mut String, field: &Field, value: Value, depth: usize) {
+    let indent = "  ".repeat(depth);
+
+    if let (Kind::Message(message), Value::Len(bytes)) = (field.kind, value) {
+        writeln!(out, "{indent}{} {{", field.name).unwrap();
+        print_message(out, bytes, message, depth + 1);
+        writeln!(out, "{indent}}}").unwrap();
+    } else if let Some(text) = scalar(field.kind, value) {
+        writeln!(out, "{indent}{}: {text}", field.name).unwrap();
+    }
+}
+
+/// Expands packed repeated scalars into one value per element.
+fn unpack(kind: Kind, value: Value) -> Vec<Value> {
+    let Value::Len(mut bytes) = value else {
+        return vec![value];
+    };
+
+    match kind {
+        Kind::String | Kind::Message(_) => vec![value],
+        Kind::Float => bytes
+            .chunks_exact(4)
+            .map(|chunk| Value::Fixed32(u32::from_le_bytes(chunk.try_into().unwrap())))
+            .collect(),
+        Kind::Double => bytes
+            .chunks_exact(8)
+            .map(|chunk| Value::Fixed64(u64::from_le_bytes(chunk.try_into().unwrap())))
+            .collect(),
+        _ => std::iter::from_fn(|| get_varint(&mut bytes))
+            .map(Value::Varint)
+            .collect(),
+    }
+}
+
+fn scalar(kind: Kind, value: Value) -> Option<String> {
+    let text = match (kind, value) {
+        (Kind::Int32, Value::Varint(value)) => (value as i32).to_string(),
+        (Kind::Int64, Value::Varint(value)) => (value as i64).to_string(),
+        (Kind::Uint32, Value::Varint(value)) => (value as u32).to_string(),
+        (Kind::Uint64, Value::Varint(value)) => value.to_string(),
+        (Kind::Bool, Value::Varint(value)) => (value != 0).to_string(),
+        (Kind::Enum(enumeration), Value::Varint(value)) => {
+            let value = value as i32;
+            enumeration
+                .values
+                .iter()
+                .find(|(_, number)| *number == value)
+                .map_or_else(|| value.to_string(), |(name, _)| name.to_string())
+        }
+        (Kind::Float, Value::Fixed32(bits)) => float(f32::from_bits(bits)),
+        (Kind::Double, Value::Fixed64(bits)) => float(f64::from_bits(bits)),
+        (Kind::String, Value::Len(bytes)) => quote(bytes),
+        _ => return None,
+    };
+
+    Some(text)
+}
+
+/// Shortest text that parses back to the same `f32`/`f64`.
+fn float<T: Into<f64> + ToString + Copy>(value: T) -> String {
+    let wide: f64 = value.into();
+    if wide.is_nan() {
+        "nan".to_string()
+    } else if wide.is_infinite() {
+        if wide > 0.0 { "inf" } else { "-inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn quote(bytes: &[u8]) -> String {
+    let mut out = String::from("\"");
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                '"' => out.push_str("\\\""),
+                '\'' => out.push_str("\\'"),
+                '\\' => out.push_str("\\\\"),
+                c if c.is_ascii_control() => write!(out, "\\{:03o}", c as u32).unwrap(),
+                c => out.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            write!(out, "\\{byte:03o}").unwrap();
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum MapKey<'a> {
+    Signed(i64),
+    Unsigned(u64),
+    String(&'a [u8]),
+}
+
+fn map_key<'a>(entry: &Message, value: Value<'a>) -> MapKey<'a> {
+    let Value::Len(bytes) = value else {
+        return MapKey::Unsigned(0);
+    };
+    let key = fields(bytes)
+        .into_iter()
+        .rev()
+        .find(|(number, _)| *number == 1)
+        .map(|(_, key)| key);
+
+    match (entry.fields[0].kind, key) {
+        (Kind::String, Some(Value::Len(key))) => MapKey::String(key),
+        (Kind::String, _) => MapKey::String(&[]),
+        (Kind::Int32 | Kind::Int64, Some(Value::Varint(key))) => MapKey::Signed(key as i64),
+        (Kind::Int32 | Kind::Int64, _) => MapKey::Signed(0),
+        (_, Some(Value::Varint(key))) => MapKey::Unsigned(key),
+        _ => MapKey::Unsigned(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, to_string};
+
+    fn round_trip(text: &str) {
+        let config = parse(text).unwrap();
+        let printed = to_string(&config);
+        let reparsed = parse(&printed).unwrap();
+
+        assert_eq!(reparsed, config);
+        assert_eq!(to_string(&reparsed), printed);
+    }
+
+    #[test]
+    fn round_trips_mnist() {
+        round_trip(include_str!("../../models/mnist/config.pbtxt"));
+    }
+
+    #[test]
+    fn round_trips_mnist_onnx() {
+        round_trip(include_str!("../../models/mnist_onnx/config.pbtxt"));
+    }
+
+    #[test]
+    fn canonical_form() {
+        let config = parse(
+            "output [{ name: \"y\", data_type: TYPE_FP32, dims: [ 10 ] }]\n\
+             max_batch_size: 8 name: 'm' # comment\n\
+             input < name: \"x\" data_type: TYPE_STRING dims: -1 >\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            to_string(&config),
+            "name: \"m\"\n\
+             max_batch_size: 8\n\
+             input {\n  name: \"x\"\n  data_type: TYPE_STRING\n  dims: -1\n}\n\
+             output {\n  name: \"y\"\n  data_type: TYPE_FP32\n  dims: 10\n}\n"
+        );
+    }
+}
@@ -0,0 +1,1754 @@
+// Field tables for `inference.ModelConfig` and every message it contains,
+// mirroring the prost types in triton-grpc-client. Keep in sync with
+// model_config.proto when the generated code is refreshed.
+
+use crate::descriptor::{Enum, Field, Kind, Message};
+
+pub(crate) static MODEL_CONFIG: Message = Message {
+    name: "inference.ModelConfig",
+    fields: &[
+        Field {
+            name: "name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "platform",
+            number: 2,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "backend",
+            number: 17,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "runtime",
+            number: 25,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "version_policy",
+            number: 3,
+            kind: Kind::Message(&MODEL_VERSION_POLICY),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "max_batch_size",
+            number: 4,
+            kind: Kind::Int32,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "input",
+            number: 5,
+            kind: Kind::Message(&MODEL_INPUT),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "output",
+            number: 6,
+            kind: Kind::Message(&MODEL_OUTPUT),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "batch_input",
+            number: 20,
+            kind: Kind::Message(&BATCH_INPUT),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "batch_output",
+            number: 21,
+            kind: Kind::Message(&BATCH_OUTPUT),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "optimization",
+            number: 12,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "instance_group",
+            number: 7,
+            kind: Kind::Message(&MODEL_INSTANCE_GROUP),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "default_model_filename",
+            number: 8,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "cc_model_filenames",
+            number: 9,
+            kind: Kind::Message(&MODEL_CONFIG_CC_MODEL_FILENAMES_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "metric_tags",
+            number: 10,
+            kind: Kind::Message(&MODEL_CONFIG_METRIC_TAGS_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "parameters",
+            number: 14,
+            kind: Kind::Message(&MODEL_CONFIG_PARAMETERS_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "model_warmup",
+            number: 16,
+            kind: Kind::Message(&MODEL_WARMUP),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "model_operations",
+            number: 18,
+            kind: Kind::Message(&MODEL_OPERATIONS),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "model_transaction_policy",
+            number: 19,
+            kind: Kind::Message(&MODEL_TRANSACTION_POLICY),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "model_repository_agents",
+            number: 23,
+            kind: Kind::Message(&MODEL_REPOSITORY_AGENTS),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "response_cache",
+            number: 24,
+            kind: Kind::Message(&MODEL_RESPONSE_CACHE),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "model_metrics",
+            number: 26,
+            kind: Kind::Message(&MODEL_METRICS),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "dynamic_batching",
+            number: 11,
+            kind: Kind::Message(&MODEL_DYNAMIC_BATCHING),
+            repeated: false,
+            oneof: Some("scheduling_choice"),
+        },
+        Field {
+            name: "sequence_batching",
+            number: 13,
+            kind: Kind::Message(&MODEL_SEQUENCE_BATCHING),
+            repeated: false,
+            oneof: Some("scheduling_choice"),
+        },
+        Field {
+            name: "ensemble_scheduling",
+            number: 15,
+            kind: Kind::Message(&MODEL_ENSEMBLING),
+            repeated: false,
+            oneof: Some("scheduling_choice"),
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_VERSION_POLICY: Message = Message {
+    name: "inference.ModelVersionPolicy",
+    fields: &[
+        Field {
+            name: "latest",
+            number: 1,
+            kind: Kind::Message(&MODEL_VERSION_POLICY_LATEST),
+            repeated: false,
+            oneof: Some("policy_choice"),
+        },
+        Field {
+            name: "all",
+            number: 2,
+            kind: Kind::Message(&MODEL_VERSION_POLICY_ALL),
+            repeated: false,
+            oneof: Some("policy_choice"),
+        },
+        Field {
+            name: "specific",
+            number: 3,
+            kind: Kind::Message(&MODEL_VERSION_POLICY_SPECIFIC),
+            repeated: false,
+            oneof: Some("policy_choice"),
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_VERSION_POLICY_LATEST: Message = Message {
+    name: "inference.ModelVersionPolicy.Latest",
+    fields: &[Field {
+        name: "num_versions",
+        number: 1,
+        kind: Kind::Uint32,
+        repeated: false,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_VERSION_POLICY_ALL: Message = Message {
+    name: "inference.ModelVersionPolicy.All",
+    fields: &[],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_VERSION_POLICY_SPECIFIC: Message = Message {
+    name: "inference.ModelVersionPolicy.Specific",
+    fields: &[Field {
+        name: "versions",
+        number: 1,
+        kind: Kind::Int64,
+        repeated: true,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_INPUT: Message = Message {
+    name: "inference.ModelInput",
+    fields: &[
+        Field {
+            name: "name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "data_type",
+            number: 2,
+            kind: Kind::Enum(&DATA_TYPE),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "format",
+            number: 3,
+            kind: Kind::Enum(&MODEL_INPUT_FORMAT),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "dims",
+            number: 4,
+            kind: Kind::Int64,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "reshape",
+            number: 5,
+            kind: Kind::Message(&MODEL_TENSOR_RESHAPE),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "is_shape_tensor",
+            number: 6,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "allow_ragged_batch",
+            number: 7,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "optional",
+            number: 8,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "is_non_linear_format_io",
+            number: 9,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_TENSOR_RESHAPE: Message = Message {
+    name: "inference.ModelTensorReshape",
+    fields: &[Field {
+        name: "shape",
+        number: 1,
+        kind: Kind::Int64,
+        repeated: true,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OUTPUT: Message = Message {
+    name: "inference.ModelOutput",
+    fields: &[
+        Field {
+            name: "name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "data_type",
+            number: 2,
+            kind: Kind::Enum(&DATA_TYPE),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "dims",
+            number: 3,
+            kind: Kind::Int64,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "reshape",
+            number: 5,
+            kind: Kind::Message(&MODEL_TENSOR_RESHAPE),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "label_filename",
+            number: 4,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "is_shape_tensor",
+            number: 6,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "is_non_linear_format_io",
+            number: 7,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static BATCH_INPUT: Message = Message {
+    name: "inference.BatchInput",
+    fields: &[
+        Field {
+            name: "kind",
+            number: 1,
+            kind: Kind::Enum(&BATCH_INPUT_KIND),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "target_name",
+            number: 2,
+            kind: Kind::String,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "data_type",
+            number: 3,
+            kind: Kind::Enum(&DATA_TYPE),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "source_input",
+            number: 4,
+            kind: Kind::String,
+            repeated: true,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static BATCH_OUTPUT: Message = Message {
+    name: "inference.BatchOutput",
+    fields: &[
+        Field {
+            name: "target_name",
+            number: 1,
+            kind: Kind::String,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "kind",
+            number: 2,
+            kind: Kind::Enum(&BATCH_OUTPUT_KIND),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "source_input",
+            number: 3,
+            kind: Kind::String,
+            repeated: true,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY: Message = Message {
+    name: "inference.ModelOptimizationPolicy",
+    fields: &[
+        Field {
+            name: "graph",
+            number: 1,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_GRAPH),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "priority",
+            number: 2,
+            kind: Kind::Enum(&MODEL_OPTIMIZATION_POLICY_MODEL_PRIORITY),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "cuda",
+            number: 3,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_CUDA),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "execution_accelerators",
+            number: 4,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_EXECUTION_ACCELERATORS),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "input_pinned_memory",
+            number: 5,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_PINNED_MEMORY_BUFFER),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "output_pinned_memory",
+            number: 6,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_PINNED_MEMORY_BUFFER),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "gather_kernel_buffer_threshold",
+            number: 7,
+            kind: Kind::Uint32,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "eager_batching",
+            number: 8,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_GRAPH: Message = Message {
+    name: "inference.ModelOptimizationPolicy.Graph",
+    fields: &[Field {
+        name: "level",
+        number: 1,
+        kind: Kind::Int32,
+        repeated: false,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_CUDA: Message = Message {
+    name: "inference.ModelOptimizationPolicy.Cuda",
+    fields: &[
+        Field {
+            name: "graphs",
+            number: 1,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "busy_wait_events",
+            number: 2,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "graph_spec",
+            number: 3,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "output_copy_stream",
+            number: 4,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC: Message = Message {
+    name: "inference.ModelOptimizationPolicy.Cuda.GraphSpec",
+    fields: &[
+        Field {
+            name: "batch_size",
+            number: 1,
+            kind: Kind::Int32,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "input",
+            number: 2,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC_INPUT_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "graph_lower_bound",
+            number: 3,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC_LOWER_BOUND),
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC_INPUT_ENTRY: Message = Message {
+    name: "inference.ModelOptimizationPolicy.Cuda.GraphSpec.InputEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC_SHAPE),
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC_SHAPE: Message = Message {
+    name: "inference.ModelOptimizationPolicy.Cuda.GraphSpec.Shape",
+    fields: &[Field {
+        name: "dim",
+        number: 1,
+        kind: Kind::Int64,
+        repeated: true,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC_LOWER_BOUND: Message = Message {
+    name: "inference.ModelOptimizationPolicy.Cuda.GraphSpec.LowerBound",
+    fields: &[
+        Field {
+            name: "batch_size",
+            number: 1,
+            kind: Kind::Int32,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "input",
+            number: 2,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC_LOWER_BOUND_INPUT_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC_LOWER_BOUND_INPUT_ENTRY: Message =
+    Message {
+        name: "inference.ModelOptimizationPolicy.Cuda.GraphSpec.LowerBound.InputEntry",
+        fields: &[
+            Field {
+                name: "key",
+                number: 1,
+                kind: Kind::String,
+                repeated: false,
+                oneof: None,
+            },
+            Field {
+                name: "value",
+                number: 2,
+                kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_CUDA_GRAPH_SPEC_SHAPE),
+                repeated: false,
+                oneof: None,
+            },
+        ],
+        map_entry: true,
+    };
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_EXECUTION_ACCELERATORS: Message = Message {
+    name: "inference.ModelOptimizationPolicy.ExecutionAccelerators",
+    fields: &[
+        Field {
+            name: "gpu_execution_accelerator",
+            number: 1,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_EXECUTION_ACCELERATORS_ACCELERATOR),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "cpu_execution_accelerator",
+            number: 2,
+            kind: Kind::Message(&MODEL_OPTIMIZATION_POLICY_EXECUTION_ACCELERATORS_ACCELERATOR),
+            repeated: true,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_EXECUTION_ACCELERATORS_ACCELERATOR: Message = Message {
+    name: "inference.ModelOptimizationPolicy.ExecutionAccelerators.Accelerator",
+    fields: &[
+        Field {
+            name: "name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "parameters",
+            number: 2,
+            kind: Kind::Message(
+                &MODEL_OPTIMIZATION_POLICY_EXECUTION_ACCELERATORS_ACCELERATOR_PARAMETERS_ENTRY,
+            ),
+            repeated: true,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_EXECUTION_ACCELERATORS_ACCELERATOR_PARAMETERS_ENTRY:
+    Message = Message {
+    name: "inference.ModelOptimizationPolicy.ExecutionAccelerators.Accelerator.ParametersEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_PINNED_MEMORY_BUFFER: Message = Message {
+    name: "inference.ModelOptimizationPolicy.PinnedMemoryBuffer",
+    fields: &[Field {
+        name: "enable",
+        number: 1,
+        kind: Kind::Bool,
+        repeated: false,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_INSTANCE_GROUP: Message = Message {
+    name: "inference.ModelInstanceGroup",
+    fields: &[
+        Field {
+            name: "name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "kind",
+            number: 4,
+            kind: Kind::Enum(&MODEL_INSTANCE_GROUP_KIND),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "count",
+            number: 2,
+            kind: Kind::Int32,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "rate_limiter",
+            number: 6,
+            kind: Kind::Message(&MODEL_RATE_LIMITER),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "gpus",
+            number: 3,
+            kind: Kind::Int32,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "secondary_devices",
+            number: 8,
+            kind: Kind::Message(&MODEL_INSTANCE_GROUP_SECONDARY_DEVICE),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "profile",
+            number: 5,
+            kind: Kind::String,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "passive",
+            number: 7,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "host_policy",
+            number: 9,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_RATE_LIMITER: Message = Message {
+    name: "inference.ModelRateLimiter",
+    fields: &[
+        Field {
+            name: "resources",
+            number: 1,
+            kind: Kind::Message(&MODEL_RATE_LIMITER_RESOURCE),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "priority",
+            number: 2,
+            kind: Kind::Uint32,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_RATE_LIMITER_RESOURCE: Message = Message {
+    name: "inference.ModelRateLimiter.Resource",
+    fields: &[
+        Field {
+            name: "name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "global",
+            number: 2,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "count",
+            number: 3,
+            kind: Kind::Uint32,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_INSTANCE_GROUP_SECONDARY_DEVICE: Message = Message {
+    name: "inference.ModelInstanceGroup.SecondaryDevice",
+    fields: &[
+        Field {
+            name: "kind",
+            number: 1,
+            kind: Kind::Enum(&MODEL_INSTANCE_GROUP_SECONDARY_DEVICE_SECONDARY_DEVICE_KIND),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "device_id",
+            number: 2,
+            kind: Kind::Int64,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_CONFIG_CC_MODEL_FILENAMES_ENTRY: Message = Message {
+    name: "inference.ModelConfig.CcModelFilenamesEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static MODEL_CONFIG_METRIC_TAGS_ENTRY: Message = Message {
+    name: "inference.ModelConfig.MetricTagsEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static MODEL_CONFIG_PARAMETERS_ENTRY: Message = Message {
+    name: "inference.ModelConfig.ParametersEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::Message(&MODEL_PARAMETER),
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static MODEL_PARAMETER: Message = Message {
+    name: "inference.ModelParameter",
+    fields: &[Field {
+        name: "string_value",
+        number: 1,
+        kind: Kind::String,
+        repeated: false,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_WARMUP: Message = Message {
+    name: "inference.ModelWarmup",
+    fields: &[
+        Field {
+            name: "name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "batch_size",
+            number: 2,
+            kind: Kind::Uint32,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "inputs",
+            number: 3,
+            kind: Kind::Message(&MODEL_WARMUP_INPUTS_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "count",
+            number: 4,
+            kind: Kind::Uint32,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_WARMUP_INPUTS_ENTRY: Message = Message {
+    name: "inference.ModelWarmup.InputsEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::Message(&MODEL_WARMUP_INPUT),
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static MODEL_WARMUP_INPUT: Message = Message {
+    name: "inference.ModelWarmup.Input",
+    fields: &[
+        Field {
+            name: "data_type",
+            number: 1,
+            kind: Kind::Enum(&DATA_TYPE),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "dims",
+            number: 2,
+            kind: Kind::Int64,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "zero_data",
+            number: 3,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: Some("input_data_type"),
+        },
+        Field {
+            name: "random_data",
+            number: 4,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: Some("input_data_type"),
+        },
+        Field {
+            name: "input_data_file",
+            number: 5,
+            kind: Kind::String,
+            repeated: false,
+            oneof: Some("input_data_type"),
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_OPERATIONS: Message = Message {
+    name: "inference.ModelOperations",
+    fields: &[Field {
+        name: "op_library_filename",
+        number: 1,
+        kind: Kind::String,
+        repeated: true,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_TRANSACTION_POLICY: Message = Message {
+    name: "inference.ModelTransactionPolicy",
+    fields: &[Field {
+        name: "decoupled",
+        number: 1,
+        kind: Kind::Bool,
+        repeated: false,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_REPOSITORY_AGENTS: Message = Message {
+    name: "inference.ModelRepositoryAgents",
+    fields: &[Field {
+        name: "agents",
+        number: 1,
+        kind: Kind::Message(&MODEL_REPOSITORY_AGENTS_AGENT),
+        repeated: true,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_REPOSITORY_AGENTS_AGENT: Message = Message {
+    name: "inference.ModelRepositoryAgents.Agent",
+    fields: &[
+        Field {
+            name: "name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "parameters",
+            number: 2,
+            kind: Kind::Message(&MODEL_REPOSITORY_AGENTS_AGENT_PARAMETERS_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_REPOSITORY_AGENTS_AGENT_PARAMETERS_ENTRY: Message = Message {
+    name: "inference.ModelRepositoryAgents.Agent.ParametersEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static MODEL_RESPONSE_CACHE: Message = Message {
+    name: "inference.ModelResponseCache",
+    fields: &[Field {
+        name: "enable",
+        number: 1,
+        kind: Kind::Bool,
+        repeated: false,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_METRICS: Message = Message {
+    name: "inference.ModelMetrics",
+    fields: &[Field {
+        name: "metric_control",
+        number: 1,
+        kind: Kind::Message(&MODEL_METRICS_METRIC_CONTROL),
+        repeated: true,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_METRICS_METRIC_CONTROL: Message = Message {
+    name: "inference.ModelMetrics.MetricControl",
+    fields: &[
+        Field {
+            name: "metric_identifier",
+            number: 1,
+            kind: Kind::Message(&MODEL_METRICS_METRIC_CONTROL_METRIC_IDENTIFIER),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "histogram_options",
+            number: 2,
+            kind: Kind::Message(&MODEL_METRICS_METRIC_CONTROL_HISTOGRAM_OPTIONS),
+            repeated: false,
+            oneof: Some("metric_options"),
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_METRICS_METRIC_CONTROL_METRIC_IDENTIFIER: Message = Message {
+    name: "inference.ModelMetrics.MetricControl.MetricIdentifier",
+    fields: &[Field {
+        name: "family",
+        number: 1,
+        kind: Kind::String,
+        repeated: false,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_METRICS_METRIC_CONTROL_HISTOGRAM_OPTIONS: Message = Message {
+    name: "inference.ModelMetrics.MetricControl.HistogramOptions",
+    fields: &[Field {
+        name: "buckets",
+        number: 1,
+        kind: Kind::Double,
+        repeated: true,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_DYNAMIC_BATCHING: Message = Message {
+    name: "inference.ModelDynamicBatching",
+    fields: &[
+        Field {
+            name: "preferred_batch_size",
+            number: 1,
+            kind: Kind::Int32,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "max_queue_delay_microseconds",
+            number: 2,
+            kind: Kind::Uint64,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "preserve_ordering",
+            number: 3,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "priority_levels",
+            number: 4,
+            kind: Kind::Uint64,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "default_priority_level",
+            number: 5,
+            kind: Kind::Uint64,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "default_queue_policy",
+            number: 6,
+            kind: Kind::Message(&MODEL_QUEUE_POLICY),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "priority_queue_policy",
+            number: 7,
+            kind: Kind::Message(&MODEL_DYNAMIC_BATCHING_PRIORITY_QUEUE_POLICY_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_QUEUE_POLICY: Message = Message {
+    name: "inference.ModelQueuePolicy",
+    fields: &[
+        Field {
+            name: "timeout_action",
+            number: 1,
+            kind: Kind::Enum(&MODEL_QUEUE_POLICY_TIMEOUT_ACTION),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "default_timeout_microseconds",
+            number: 2,
+            kind: Kind::Uint64,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "allow_timeout_override",
+            number: 3,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "max_queue_size",
+            number: 4,
+            kind: Kind::Uint32,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_DYNAMIC_BATCHING_PRIORITY_QUEUE_POLICY_ENTRY: Message = Message {
+    name: "inference.ModelDynamicBatching.PriorityQueuePolicyEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::Uint64,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::Message(&MODEL_QUEUE_POLICY),
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static MODEL_SEQUENCE_BATCHING: Message = Message {
+    name: "inference.ModelSequenceBatching",
+    fields: &[
+        Field {
+            name: "max_sequence_idle_microseconds",
+            number: 1,
+            kind: Kind::Uint64,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "control_input",
+            number: 2,
+            kind: Kind::Message(&MODEL_SEQUENCE_BATCHING_CONTROL_INPUT),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "state",
+            number: 5,
+            kind: Kind::Message(&MODEL_SEQUENCE_BATCHING_STATE),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "iterative_sequence",
+            number: 6,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "direct",
+            number: 3,
+            kind: Kind::Message(&MODEL_SEQUENCE_BATCHING_STRATEGY_DIRECT),
+            repeated: false,
+            oneof: Some("strategy_choice"),
+        },
+        Field {
+            name: "oldest",
+            number: 4,
+            kind: Kind::Message(&MODEL_SEQUENCE_BATCHING_STRATEGY_OLDEST),
+            repeated: false,
+            oneof: Some("strategy_choice"),
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_SEQUENCE_BATCHING_CONTROL_INPUT: Message = Message {
+    name: "inference.ModelSequenceBatching.ControlInput",
+    fields: &[
+        Field {
+            name: "name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "control",
+            number: 2,
+            kind: Kind::Message(&MODEL_SEQUENCE_BATCHING_CONTROL),
+            repeated: true,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_SEQUENCE_BATCHING_CONTROL: Message = Message {
+    name: "inference.ModelSequenceBatching.Control",
+    fields: &[
+        Field {
+            name: "kind",
+            number: 1,
+            kind: Kind::Enum(&MODEL_SEQUENCE_BATCHING_CONTROL_KIND),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "int32_false_true",
+            number: 2,
+            kind: Kind::Int32,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "fp32_false_true",
+            number: 3,
+            kind: Kind::Float,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "bool_false_true",
+            number: 5,
+            kind: Kind::Bool,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "data_type",
+            number: 4,
+            kind: Kind::Enum(&DATA_TYPE),
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_SEQUENCE_BATCHING_STATE: Message = Message {
+    name: "inference.ModelSequenceBatching.State",
+    fields: &[
+        Field {
+            name: "input_name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "output_name",
+            number: 2,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "data_type",
+            number: 3,
+            kind: Kind::Enum(&DATA_TYPE),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "dims",
+            number: 4,
+            kind: Kind::Int64,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "initial_state",
+            number: 5,
+            kind: Kind::Message(&MODEL_SEQUENCE_BATCHING_INITIAL_STATE),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "use_same_buffer_for_input_output",
+            number: 6,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "use_growable_memory",
+            number: 7,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_SEQUENCE_BATCHING_INITIAL_STATE: Message = Message {
+    name: "inference.ModelSequenceBatching.InitialState",
+    fields: &[
+        Field {
+            name: "data_type",
+            number: 1,
+            kind: Kind::Enum(&DATA_TYPE),
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "dims",
+            number: 2,
+            kind: Kind::Int64,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "name",
+            number: 5,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "zero_data",
+            number: 3,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: Some("state_data"),
+        },
+        Field {
+            name: "data_file",
+            number: 4,
+            kind: Kind::String,
+            repeated: false,
+            oneof: Some("state_data"),
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_SEQUENCE_BATCHING_STRATEGY_DIRECT: Message = Message {
+    name: "inference.ModelSequenceBatching.StrategyDirect",
+    fields: &[
+        Field {
+            name: "max_queue_delay_microseconds",
+            number: 1,
+            kind: Kind::Uint64,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "minimum_slot_utilization",
+            number: 2,
+            kind: Kind::Float,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_SEQUENCE_BATCHING_STRATEGY_OLDEST: Message = Message {
+    name: "inference.ModelSequenceBatching.StrategyOldest",
+    fields: &[
+        Field {
+            name: "max_candidate_sequences",
+            number: 1,
+            kind: Kind::Int32,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "preferred_batch_size",
+            number: 2,
+            kind: Kind::Int32,
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "max_queue_delay_microseconds",
+            number: 3,
+            kind: Kind::Uint64,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "preserve_ordering",
+            number: 4,
+            kind: Kind::Bool,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_ENSEMBLING: Message = Message {
+    name: "inference.ModelEnsembling",
+    fields: &[Field {
+        name: "step",
+        number: 1,
+        kind: Kind::Message(&MODEL_ENSEMBLING_STEP),
+        repeated: true,
+        oneof: None,
+    }],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_ENSEMBLING_STEP: Message = Message {
+    name: "inference.ModelEnsembling.Step",
+    fields: &[
+        Field {
+            name: "model_name",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "model_version",
+            number: 2,
+            kind: Kind::Int64,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "input_map",
+            number: 3,
+            kind: Kind::Message(&MODEL_ENSEMBLING_STEP_INPUT_MAP_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "output_map",
+            number: 4,
+            kind: Kind::Message(&MODEL_ENSEMBLING_STEP_OUTPUT_MAP_ENTRY),
+            repeated: true,
+            oneof: None,
+        },
+        Field {
+            name: "model_namespace",
+            number: 5,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: false,
+};
+
+pub(crate) static MODEL_ENSEMBLING_STEP_INPUT_MAP_ENTRY: Message = Message {
+    name: "inference.ModelEnsembling.Step.InputMapEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static MODEL_ENSEMBLING_STEP_OUTPUT_MAP_ENTRY: Message = Message {
+    name: "inference.ModelEnsembling.Step.OutputMapEntry",
+    fields: &[
+        Field {
+            name: "key",
+            number: 1,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+        Field {
+            name: "value",
+            number: 2,
+            kind: Kind::String,
+            repeated: false,
+            oneof: None,
+        },
+    ],
+    map_entry: true,
+};
+
+pub(crate) static DATA_TYPE: Enum = Enum {
+    name: "inference.DataType",
+    values: &[
+        ("TYPE_INVALID", 0),
+        ("TYPE_BOOL", 1),
+        ("TYPE_UINT8", 2),
+        ("TYPE_UINT16", 3),
+        ("TYPE_UINT32", 4),
+        ("TYPE_UINT64", 5),
+        ("TYPE_INT8", 6),
+        ("TYPE_INT16", 7),
+        ("TYPE_INT32", 8),
+        ("TYPE_INT64", 9),
+        ("TYPE_FP16", 10),
+        ("TYPE_FP32", 11),
+        ("TYPE_FP64", 12),
+        ("TYPE_STRING", 13),
+        ("TYPE_BF16", 14),
+    ],
+};
+
+pub(crate) static MODEL_INPUT_FORMAT: Enum = Enum {
+    name: "inference.ModelInput.Format",
+    values: &[("FORMAT_NONE", 0), ("FORMAT_NHWC", 1), ("FORMAT_NCHW", 2)],
+};
+
+pub(crate) static BATCH_INPUT_KIND: Enum = Enum {
+    name: "inference.BatchInput.Kind",
+    values: &[
+        ("BATCH_ELEMENT_COUNT", 0),
+        ("BATCH_ACCUMULATED_ELEMENT_COUNT", 1),
+        ("BATCH_ACCUMULATED_ELEMENT_COUNT_WITH_ZERO", 2),
+        ("BATCH_MAX_ELEMENT_COUNT_AS_SHAPE", 3),
+        ("BATCH_ITEM_SHAPE", 4),
+        ("BATCH_ITEM_SHAPE_FLATTEN", 5),
+    ],
+};
+
+pub(crate) static BATCH_OUTPUT_KIND: Enum = Enum {
+    name: "inference.BatchOutput.Kind",
+    values: &[("BATCH_SCATTER_WITH_INPUT_SHAPE", 0)],
+};
+
+pub(crate) static MODEL_OPTIMIZATION_POLICY_MODEL_PRIORITY: Enum = Enum {
+    name: "inference.ModelOptimizationPolicy.ModelPriority",
+    values: &[
+        ("PRIORITY_DEFAULT", 0),
+        ("PRIORITY_MAX", 1),
+        ("PRIORITY_MIN", 2),
+    ],
+};
+
+pub(crate) static MODEL_INSTANCE_GROUP_KIND: Enum = Enum {
+    name: "inference.ModelInstanceGroup.Kind",
+    values: &[
+        ("KIND_AUTO", 0),
+        ("KIND_GPU", 1),
+        ("KIND_CPU", 2),
+        ("KIND_MODEL", 3),
+    ],
+};
+
+pub(crate) static MODEL_INSTANCE_GROUP_SECONDARY_DEVICE_SECONDARY_DEVICE_KIND: Enum = Enum {
+    name: "inference.ModelInstanceGroup.SecondaryDevice.SecondaryDeviceKind",
+    values: &[("KIND_NVDLA", 0)],
+};
+
+pub(crate) static MODEL_QUEUE_POLICY_TIMEOUT_ACTION: Enum = Enum {
+    name: "inference.ModelQueuePolicy.TimeoutAction",
+    values: &[("REJECT", 0), ("DELAY", 1)],
+};
+
+pub(crate) static MODEL_SEQUENCE_BATCHING_CONTROL_KIND: Enum = Enum {
+    name: "inference.ModelSequenceBatching.Control.Kind",
+    values: &[
+        ("CONTROL_SEQUENCE_START", 0),
+        ("CONTROL_SEQUENCE_READY", 1),
+        ("CONTROL_SEQUENCE_END", 2),
+        ("CONTROL_SEQUENCE_CORRID", 3),
+    ],
+};
@@ -0,0 +1,79 @@
+pub(crate) const VARINT: u32 = 0;
+pub(crate) const FIXED64: u32 = 1;
+pub(crate) const LEN: u32 = 2;
+pub(crate) const FIXED32: u32 = 5;
+
+pub(crate) fn put_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+pub(crate) fn put_key(buf: &mut Vec<u8>, number: u32, wire_type: u32) {
+    put_varint(buf, u64::from(number << 3 | wire_type));
+}
+
+pub(crate) fn put_len(buf: &mut Vec<u8>, number: u32, bytes: &[u8]) {
+    put_key(buf, number, LEN);
+    put_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Value<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Len(&'a [u8]),
+    Fixed32(u32),
+}
+
+pub(crate) fn get_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn get_fixed<const N: usize>(bytes: &mut &[u8]) -> Option<[u8; N]> {
+    let (value, rest) = bytes.split_first_chunk::<N>()?;
+    *bytes = rest;
+    Some(*value)
+}
+
+/// Splits a message into `(field number, value)` pairs in wire order,
+/// stopping at the first malformed field.
+pub(crate) fn fields(mut bytes: &[u8]) -> Vec<(u32, Value<'_>)> {
+    let mut fields = Vec::new();
+    while !bytes.is_empty() {
+        let Some(key) = get_varint(&mut bytes) else {
+            break;
+        };
+        let value = match (key & 0x7) as u32 {
+            VARINT => get_varint(&mut bytes).map(Value::Varint),
+            FIXED64 => get_fixed(&mut bytes).map(|b| Value::Fixed64(u64::from_le_bytes(b))),
+            FIXED32 => get_fixed(&mut bytes).map(|b| Value::Fixed32(u32::from_le_bytes(b))),
+            LEN => get_varint(&mut bytes)
+                .and_then(|len| usize::try_from(len).ok())
+                .filter(|&len| len <= bytes.len())
+                .map(|len| {
+                    let (value, rest) = bytes.split_at(len);
+                    bytes = rest;
+                    Value::Len(value)
+                }),
+            _ => None,
+        };
+        let Some(value) = value else {
+            break;
+        };
+        fields.push(((key >> 3) as u32, value));
+    }
+    fields
+}
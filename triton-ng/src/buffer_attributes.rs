@@ -0,0 +1,90 @@
+use crate::TritonError;
+use std::ptr;
+
+pub struct BufferAttributes {
+    ptr: *mut triton_sys::TRITONSERVER_BufferAttributes,
+}
+
+impl BufferAttributes {
+    pub fn new() -> Result<Self, TritonError> {
+        let mut attributes_ptr: *mut triton_sys::TRITONSERVER_BufferAttributes = ptr::null_mut();
+
+        ffi_call!(triton_sys::TRITONSERVER_BufferAttributesNew(
+            &mut attributes_ptr
+        ))?;
+
+        ensure_ptr!(attributes_ptr)?;
+
+        Ok(Self {
+            ptr: attributes_ptr,
+        })
+    }
+
+    pub fn from_ptr(ptr: *mut triton_sys::TRITONSERVER_BufferAttributes) -> Self {
+        Self { ptr }
+    }
+
+    pub fn as_ptr(&self) -> *mut triton_sys::TRITONSERVER_BufferAttributes {
+        self.ptr
+    }
+
+    pub fn memory_type(&self) -> Result<triton_sys::TRITONSERVER_MemoryType, TritonError> {
+        let mut memory_type: triton_sys::TRITONSERVER_MemoryType = 0;
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_BufferAttributesMemoryType(self.ptr, &mut memory_type),
+            memory_type
+        )
+    }
+
+    pub fn set_memory_type(
+        &mut self,
+        memory_type: triton_sys::TRITONSERVER_MemoryType,
+    ) -> Result<(), TritonError> {
+        ffi_call!(triton_sys::TRITONSERVER_BufferAttributesSetMemoryType(
+            self.ptr,
+            memory_type
+        ))
+    }
+
+    pub fn memory_type_id(&self) -> Result<i64, TritonError> {
+        let mut memory_type_id = 0i64;
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_BufferAttributesMemoryTypeId(self.ptr, &mut memory_type_id),
+            memory_type_id
+        )
+    }
+
+    pub fn set_memory_type_id(&mut self, memory_type_id: i64) -> Result<(), TritonError> {
+        ffi_call!(triton_sys::TRITONSERVER_BufferAttributesSetMemoryTypeId(
+            self.ptr,
+            memory_type_id
+        ))
+    }
+
+    pub fn byte_size(&self) -> Result<usize, TritonError> {
+        let mut byte_size = 0usize;
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_BufferAttributesByteSize(self.ptr, &mut byte_size),
+            byte_size
+        )
+    }
+
+    pub fn set_byte_size(&mut self, byte_size: usize) -> Result<(), TritonError> {
+        ffi_call!(triton_sys::TRITONSERVER_BufferAttributesSetByteSize(
+            self.ptr, byte_size
+        ))
+    }
+}
+
+impl Drop for BufferAttributes {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe {
+                triton_sys::TRITONSERVER_BufferAttributesDelete(self.ptr);
+            }
+        }
+    }
+}
@@ -34,6 +34,26 @@ impl TritonError {
             message: message.into(),
         }
     }
+
+    /// Hands the error over to Triton, e.g. as the return value of a
+    /// callback. Triton takes ownership of the returned pointer.
+    pub fn into_raw(self) -> *mut triton_sys::TRITONSERVER_Error {
+        let this = std::mem::ManuallyDrop::new(self);
+
+        if !this.ptr.is_null() {
+            return this.ptr;
+        }
+
+        let message =
+            std::ffi::CString::new(this.message.replace('\0', " ")).expect("CString::new failed");
+
+        unsafe {
+            triton_sys::TRITONSERVER_ErrorNew(
+                triton_sys::TRITONSERVER_errorcode_enum_TRITONSERVER_ERROR_INTERNAL,
+                message.as_ptr(),
+            )
+        }
+    }
 }
 
 impl std::fmt::Display for TritonError {
@@ -44,6 +64,10 @@ impl std::fmt::Display for TritonError {
 
 impl std::error::Error for TritonError {}
 
+// SAFETY: the wrapped TRITONSERVER_Error is exclusively owned and never
+// shared, so moving it to another thread is sound.
+unsafe impl Send for TritonError {}
+
 impl Drop for TritonError {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
@@ -60,3 +84,15 @@ impl From<*mut triton_sys::TRITONSERVER_Error> for TritonError {
         unsafe { TritonError::new(ptr) }
     }
 }
+
+impl From<triton_types::TensorError> for TritonError {
+    fn from(error: triton_types::TensorError) -> Self {
+        TritonError::from_message(error.to_string())
+    }
+}
+
+impl From<TritonError> for triton_types::TensorError {
+    fn from(error: TritonError) -> Self {
+        triton_types::TensorError::new(error.message.clone())
+    }
+}
@@ -1,13 +1,95 @@
 use crate::TritonError;
+use crate::buffer_attributes::BufferAttributes;
+use crate::request::{Input, Request};
+use crate::response_allocator::ResponseAllocator;
 use crate::server::Server;
-use crate::utils::cstring_from_str;
-use std::ffi::c_void;
+use crate::tensor::{DataType, TensorElement, as_bytes, element_count};
+use crate::utils::{cstr_to_string, cstring_from_str};
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void};
+use std::marker::PhantomData;
 use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 pub struct InferenceRequest {
+    inner: Arc<RequestInner>,
+    allocator: Option<Arc<ResponseAllocator>>,
+}
+
+/// State shared between the caller and Triton. The request is deleted once
+/// the last reference is dropped, which is either the caller's handle or the
+/// reference held until `TRITONSERVER_InferenceRequestSetReleaseCallback`
+/// fires, whichever comes last.
+struct RequestInner {
     ptr: *mut triton_sys::TRITONSERVER_InferenceRequest,
+    flight: Flight,
+    buffers: Mutex<HashMap<String, Vec<OwnedBuffer>>>,
+}
+
+/// Whether Triton holds a request, from [`InferenceRequest::acquire`] until
+/// the release callback.
+#[derive(Default)]
+struct Flight(AtomicBool);
+
+impl Flight {
+    fn is_active(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Returns false if the request is already in flight.
+    fn enter(&self) -> bool {
+        self.0
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    fn leave(&self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Owned input data. Triton reads it asynchronously, so it is kept until the
+/// input is removed or the request is deleted.
+struct OwnedBuffer {
+    _data: Box<dyn Send + Sync>,
+    base: *const c_void,
+    byte_size: usize,
+}
+
+impl OwnedBuffer {
+    /// Boxes `data` and takes its bytes exactly once. Triton keeps reading
+    /// the pointer it was given, so a later `as_ref` call that returned other
+    /// memory must not matter.
+    fn new<B: AsRef<[u8]> + Send + Sync + 'static>(data: B) -> Self {
+        let data = Box::new(data);
+        let bytes = (*data).as_ref();
+
+        Self {
+            base: bytes.as_ptr() as *const c_void,
+            byte_size: bytes.len(),
+            _data: data,
+        }
+    }
+}
+
+struct TypedBuffer<D, T> {
+    data: D,
+    _element: PhantomData<T>,
+}
+
+impl<D: AsRef<[T]>, T: TensorElement> AsRef<[u8]> for TypedBuffer<D, T> {
+    fn as_ref(&self) -> &[u8] {
+        as_bytes(self.data.as_ref())
+    }
 }
 
+// SAFETY: the request is only mutated through `&mut InferenceRequest` while
+// it is not in flight; Triton owns it exclusively in between.
+unsafe impl Send for RequestInner {}
+unsafe impl Sync for RequestInner {}
+
 impl InferenceRequest {
     pub fn new(server: &Server, model_name: &str, model_version: i64) -> Result<Self, TritonError> {
         let mut request_ptr: *mut triton_sys::TRITONSERVER_InferenceRequest = ptr::null_mut();
@@ -22,54 +104,588 @@ impl InferenceRequest {
 
         ensure_ptr!(request_ptr)?;
 
-        Ok(Self { ptr: request_ptr })
+        Ok(Self {
+            inner: Arc::new(RequestInner {
+                ptr: request_ptr,
+                flight: Flight::default(),
+                buffers: Mutex::new(HashMap::new()),
+            }),
+            allocator: None,
+        })
+    }
+
+    /// Builds a request for `model_name` whose inputs point at the buffers of
+    /// the backend `request` instead of copying them, which makes proxy and
+    /// router backends cheap. Only the named `inputs` are forwarded.
+    ///
+    /// # Safety
+    ///
+    /// The forwarded data is owned by Triton and stays valid only until the
+    /// backend `request` is released. The returned request must be dropped,
+    /// or have the forwarded inputs removed, before that happens, and any
+    /// inference started with it must have completed.
+    pub unsafe fn from_backend_request(
+        server: &Server,
+        request: &Request,
+        model_name: &str,
+        model_version: i64,
+        inputs: &[&str],
+    ) -> Result<Self, TritonError> {
+        let mut forwarded = Self::new(server, model_name, model_version)?;
+
+        let id = request.id()?;
+        if !id.is_empty() {
+            forwarded.set_id(&id)?;
+        }
+
+        for name in inputs {
+            // SAFETY: our caller upholds the same contract for `request`.
+            unsafe { forwarded.forward_input(&request.get_input(name)?, name)? };
+        }
+
+        Ok(forwarded)
+    }
+
+    /// Adds `input` of a backend request under `name`, referencing its
+    /// buffers without copying.
+    ///
+    /// # Safety
+    ///
+    /// As for [`InferenceRequest::from_backend_request`]: this request must
+    /// not be used after the backend request that `input` belongs to is
+    /// released.
+    pub unsafe fn forward_input(&mut self, input: &Input, name: &str) -> Result<(), TritonError> {
+        let properties = input.properties()?;
+
+        self.add_input(name, properties.datatype, &properties.shape)?;
+
+        for index in 0..properties.buffer_count {
+            let buffer = input.buffer_at(index)?;
+
+            // SAFETY: the buffer belongs to the backend request, which our
+            // caller guarantees outlives this request's use of it.
+            unsafe {
+                self.append_raw_input_data(
+                    name,
+                    buffer.base,
+                    buffer.byte_size as usize,
+                    buffer.memory_type,
+                    buffer.memory_type_id,
+                )?;
+            }
+        }
+
+        Ok(())
     }
 
     pub fn as_ptr(&self) -> *mut triton_sys::TRITONSERVER_InferenceRequest {
-        self.ptr
+        self.inner.ptr
+    }
+
+    /// Uses `allocator` for the outputs of this request instead of the
+    /// shared heap allocator.
+    pub fn set_response_allocator(&mut self, allocator: Arc<ResponseAllocator>) {
+        self.allocator = Some(allocator);
+    }
+
+    pub(crate) fn response_allocator(&self) -> Result<Arc<ResponseAllocator>, TritonError> {
+        match &self.allocator {
+            Some(allocator) => Ok(Arc::clone(allocator)),
+            None => ResponseAllocator::shared(),
+        }
+    }
+
+    /// Returns true while Triton holds the request, i.e. between submission
+    /// and the release callback.
+    pub fn is_in_flight(&self) -> bool {
+        self.inner.flight.is_active()
     }
 
     pub fn add_input(
         &mut self,
         name: &str,
-        datatype: triton_sys::TRITONSERVER_DataType,
+        datatype: DataType,
         shape: &[i64],
     ) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
         let name_cstr = cstring_from_str(name);
 
         ffi_call!(triton_sys::TRITONSERVER_InferenceRequestAddInput(
-            self.ptr,
+            ptr,
             name_cstr.as_ptr(),
-            datatype,
+            datatype.as_raw(),
             shape.as_ptr(),
             shape.len() as u64,
         ))
     }
 
-    pub fn append_input_data(&mut self, name: &str, data: &[u8]) -> Result<(), TritonError> {
+    pub fn id(&self) -> Result<String, TritonError> {
+        let mut id: *const c_char = ptr::null();
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestId(
+            self.inner.ptr,
+            &mut id
+        ))?;
+
+        Ok(unsafe { cstr_to_string(id) })
+    }
+
+    pub fn set_id(&mut self, id: &str) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let id_cstr = cstring_from_str(id);
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestSetId(
+            ptr,
+            id_cstr.as_ptr()
+        ))
+    }
+
+    pub fn flags(&self) -> Result<u32, TritonError> {
+        let mut flags = 0u32;
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestFlags(self.inner.ptr, &mut flags),
+            flags
+        )
+    }
+
+    /// Sets a bitwise-or of `TRITONSERVER_RequestFlag` values, e.g. the
+    /// sequence start/end flags.
+    pub fn set_flags(&mut self, flags: u32) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestSetFlags(
+            ptr, flags
+        ))
+    }
+
+    pub fn correlation_id(&self) -> Result<u64, TritonError> {
+        let mut correlation_id = 0u64;
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestCorrelationId(
+                self.inner.ptr,
+                &mut correlation_id
+            ),
+            correlation_id
+        )
+    }
+
+    pub fn set_correlation_id(&mut self, correlation_id: u64) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestSetCorrelationId(
+            ptr,
+            correlation_id
+        ))
+    }
+
+    pub fn correlation_id_string(&self) -> Result<String, TritonError> {
+        let mut correlation_id: *const c_char = ptr::null();
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestCorrelationIdString(
+                self.inner.ptr,
+                &mut correlation_id
+            )
+        )?;
+
+        Ok(unsafe { cstr_to_string(correlation_id) })
+    }
+
+    pub fn set_correlation_id_string(&mut self, correlation_id: &str) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let correlation_id_cstr = cstring_from_str(correlation_id);
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestSetCorrelationIdString(
+                ptr,
+                correlation_id_cstr.as_ptr()
+            )
+        )
+    }
+
+    pub fn priority(&self) -> Result<u64, TritonError> {
+        let mut priority = 0u64;
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestPriorityUInt64(self.inner.ptr, &mut priority),
+            priority
+        )
+    }
+
+    /// Lower values mean higher priority; zero selects the model's default.
+    pub fn set_priority(&mut self, priority: u64) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestSetPriorityUInt64(
+            ptr, priority
+        ))
+    }
+
+    pub fn timeout(&self) -> Result<Duration, TritonError> {
+        let mut timeout_us = 0u64;
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestTimeoutMicroseconds(
+                self.inner.ptr,
+                &mut timeout_us
+            ),
+            Duration::from_micros(timeout_us)
+        )
+    }
+
+    /// A zero timeout means the request never times out.
+    pub fn set_timeout(&mut self, timeout: Duration) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let timeout_us = u64::try_from(timeout.as_micros()).unwrap_or(u64::MAX);
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestSetTimeoutMicroseconds(ptr, timeout_us))
+    }
+
+    pub fn set_string_parameter(&mut self, key: &str, value: &str) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let key_cstr = cstring_from_str(key);
+        let value_cstr = cstring_from_str(value);
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestSetStringParameter(
+            ptr,
+            key_cstr.as_ptr(),
+            value_cstr.as_ptr()
+        ))
+    }
+
+    pub fn set_int_parameter(&mut self, key: &str, value: i64) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let key_cstr = cstring_from_str(key);
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestSetIntParameter(
+            ptr,
+            key_cstr.as_ptr(),
+            value
+        ))
+    }
+
+    pub fn set_bool_parameter(&mut self, key: &str, value: bool) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let key_cstr = cstring_from_str(key);
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestSetBoolParameter(
+            ptr,
+            key_cstr.as_ptr(),
+            value
+        ))
+    }
+
+    /// Adds an input whose datatype and shape are taken from the model
+    /// configuration; the data must be appended as raw bytes.
+    pub fn add_raw_input(&mut self, name: &str) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let name_cstr = cstring_from_str(name);
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestAddRawInput(
+            ptr,
+            name_cstr.as_ptr()
+        ))
+    }
+
+    /// Adds an input of `T`'s datatype and appends `data` to it, taking
+    /// ownership of the buffer.
+    pub fn add_typed_input<T, D>(
+        &mut self,
+        name: &str,
+        shape: &[i64],
+        data: D,
+    ) -> Result<(), TritonError>
+    where
+        T: TensorElement + Send + Sync,
+        D: AsRef<[T]> + Send + Sync + 'static,
+    {
+        let len = data.as_ref().len();
+
+        if element_count(shape) != Some(len) {
+            return Err(TritonError::from_message(format!(
+                "Input '{}' has {} elements, which does not match shape {:?}",
+                name, len, shape
+            )));
+        }
+
+        self.add_input(name, T::DATATYPE, shape)?;
+        let buffer = TypedBuffer {
+            data,
+            _element: PhantomData,
+        };
+        if let Err(error) = self.append_input_data(name, buffer) {
+            // Don't leave an input without data behind.
+            let _ = self.remove_input(name);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    pub fn remove_input(&mut self, name: &str) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let name_cstr = cstring_from_str(name);
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestRemoveInput(
+            ptr,
+            name_cstr.as_ptr()
+        ))?;
+
+        self.release_buffers(Some(name));
+        Ok(())
+    }
+
+    pub fn remove_all_inputs(&mut self) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestRemoveAllInputs(
+            ptr
+        ))?;
+
+        self.release_buffers(None);
+        Ok(())
+    }
+
+    /// Appends a chunk of CPU memory to the input's data. The request takes
+    /// ownership of `data` and keeps it alive for as long as Triton may
+    /// read it.
+    pub fn append_input_data<B>(&mut self, name: &str, data: B) -> Result<(), TritonError>
+    where
+        B: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        let buffer = OwnedBuffer::new(data);
+
+        // SAFETY: the buffer is kept with the request, see `OwnedBuffer`.
+        unsafe {
+            self.append_raw_input_data(
+                name,
+                buffer.base,
+                buffer.byte_size,
+                triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_CPU,
+                0,
+            )?
+        };
+
+        self.keep_buffer(name, buffer);
+        Ok(())
+    }
+
+    /// Appends a chunk of CPU memory without taking ownership of it.
+    ///
+    /// # Safety
+    ///
+    /// `data` must stay valid and unchanged until the request is deleted or
+    /// the input is removed, not just until this call returns, because
+    /// Triton reads it asynchronously.
+    pub unsafe fn append_borrowed_input_data(
+        &mut self,
+        name: &str,
+        data: &[u8],
+    ) -> Result<(), TritonError> {
+        unsafe {
+            self.append_raw_input_data(
+                name,
+                data.as_ptr() as *const c_void,
+                data.len(),
+                triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_CPU,
+                0,
+            )
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `base` must point to `byte_size` bytes of the given memory type that
+    /// outlive the request, as for [`InferenceRequest::append_borrowed_input_data`].
+    unsafe fn append_raw_input_data(
+        &mut self,
+        name: &str,
+        base: *const c_void,
+        byte_size: usize,
+        memory_type: triton_sys::TRITONSERVER_MemoryType,
+        memory_type_id: i64,
+    ) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
         let name_cstr = cstring_from_str(name);
 
         ffi_call!(triton_sys::TRITONSERVER_InferenceRequestAppendInputData(
-            self.ptr,
+            ptr,
             name_cstr.as_ptr(),
-            data.as_ptr() as *const c_void,
-            data.len(),
-            triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_CPU, // TODO: check this
-            0,                                                                // device_id
+            base,
+            byte_size,
+            memory_type,
+            memory_type_id,
         ))
     }
 
+    /// Appends a chunk of CPU memory that is only used by model instances
+    /// running under `host_policy`. Ownership is handled as in
+    /// [`InferenceRequest::append_input_data`].
+    pub fn append_input_data_with_host_policy<B>(
+        &mut self,
+        name: &str,
+        data: B,
+        host_policy: &str,
+    ) -> Result<(), TritonError>
+    where
+        B: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        let ptr = self.idle_ptr()?;
+        let name_cstr = cstring_from_str(name);
+        let host_policy_cstr = cstring_from_str(host_policy);
+        let buffer = OwnedBuffer::new(data);
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestAppendInputDataWithHostPolicy(
+                ptr,
+                name_cstr.as_ptr(),
+                buffer.base,
+                buffer.byte_size,
+                triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_CPU,
+                0,
+                host_policy_cstr.as_ptr(),
+            )
+        )?;
+
+        self.keep_buffer(name, buffer);
+        Ok(())
+    }
+
+    /// Appends a buffer whose size and location (CPU, pinned or GPU memory)
+    /// are described by `attributes`.
+    ///
+    /// # Safety
+    ///
+    /// `base` must point to `attributes.byte_size()` readable bytes in the
+    /// described memory and stay valid until the request is deleted or the
+    /// input is removed.
+    pub unsafe fn append_input_data_with_buffer_attributes(
+        &mut self,
+        name: &str,
+        base: *const c_void,
+        attributes: &BufferAttributes,
+    ) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let name_cstr = cstring_from_str(name);
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestAppendInputDataWithBufferAttributes(
+                ptr,
+                name_cstr.as_ptr(),
+                base,
+                attributes.as_ptr(),
+            )
+        )
+    }
+
+    pub fn remove_all_input_data(&mut self, name: &str) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let name_cstr = cstring_from_str(name);
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestRemoveAllInputData(
+            ptr,
+            name_cstr.as_ptr()
+        ))?;
+
+        self.release_buffers(Some(name));
+        Ok(())
+    }
+
     pub fn add_requested_output(&mut self, name: &str) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
         let name_cstr = cstring_from_str(name);
 
         ffi_call!(triton_sys::TRITONSERVER_InferenceRequestAddRequestedOutput(
-            self.ptr,
+            ptr,
             name_cstr.as_ptr(),
         ))
     }
+
+    pub fn remove_requested_output(&mut self, name: &str) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+        let name_cstr = cstring_from_str(name);
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestRemoveRequestedOutput(ptr, name_cstr.as_ptr())
+        )
+    }
+
+    pub fn remove_all_requested_outputs(&mut self) -> Result<(), TritonError> {
+        let ptr = self.idle_ptr()?;
+
+        ffi_call!(triton_sys::TRITONSERVER_InferenceRequestRemoveAllRequestedOutputs(ptr))
+    }
+
+    fn keep_buffer(&self, name: &str, buffer: OwnedBuffer) {
+        let mut buffers = self.inner.buffers.lock().unwrap_or_else(|e| e.into_inner());
+        buffers.entry(name.to_string()).or_default().push(buffer);
+    }
+
+    fn release_buffers(&self, name: Option<&str>) {
+        let mut buffers = self.inner.buffers.lock().unwrap_or_else(|e| e.into_inner());
+
+        match name {
+            Some(name) => {
+                buffers.remove(name);
+            }
+            None => buffers.clear(),
+        }
+    }
+
+    fn idle_ptr(&self) -> Result<*mut triton_sys::TRITONSERVER_InferenceRequest, TritonError> {
+        if self.is_in_flight() {
+            return Err(TritonError::from_message(
+                "Inference request is in flight and cannot be modified",
+            ));
+        }
+
+        Ok(self.inner.ptr)
+    }
+
+    /// Marks the request as in flight and registers the release callback,
+    /// which keeps the request alive until Triton is done with it. Returns
+    /// the callback's userp so that [`InferenceRequest::abandon`] can
+    /// reclaim it if submission fails.
+    pub(crate) fn acquire(&self) -> Result<*mut c_void, TritonError> {
+        if !self.inner.flight.enter() {
+            return Err(TritonError::from_message(
+                "Inference request is already in flight",
+            ));
+        }
+
+        let userp = hold(&self.inner);
+
+        let res = ffi_call!(
+            triton_sys::TRITONSERVER_InferenceRequestSetReleaseCallback(
+                self.inner.ptr,
+                Some(inference_request_release),
+                userp,
+            ),
+            userp
+        );
+
+        if res.is_err() {
+            self.abandon(userp);
+        }
+
+        res
+    }
+
+    /// Undoes [`InferenceRequest::acquire`] when Triton did not take the
+    /// request, in which case the release callback never fires.
+    pub(crate) fn abandon(&self, userp: *mut c_void) {
+        unsafe {
+            inference_request_release(
+                self.inner.ptr,
+                triton_sys::TRITONSERVER_requestreleaseflag_enum_TRITONSERVER_REQUEST_RELEASE_ALL,
+                userp,
+            );
+        }
+    }
 }
 
-impl Drop for InferenceRequest {
+impl Drop for RequestInner {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
             unsafe {
@@ -78,3 +694,110 @@ impl Drop for InferenceRequest {
         }
     }
 }
+
+/// Turns a reference to `inner` into a callback userp for Triton to hold.
+fn hold<T>(inner: &Arc<T>) -> *mut c_void {
+    Box::into_raw(Box::new(Arc::clone(inner))) as *mut c_void
+}
+
+/// Takes back the reference behind `userp` once Triton releases the request
+/// for good, i.e. `flags` include `RELEASE_ALL`.
+///
+/// # Safety
+///
+/// `userp` must come from [`hold`] with the same `T` and not have been taken
+/// back yet.
+unsafe fn release_hold<T>(userp: *mut c_void, flags: u32) -> Option<Arc<T>> {
+    if flags & triton_sys::TRITONSERVER_requestreleaseflag_enum_TRITONSERVER_REQUEST_RELEASE_ALL
+        == 0
+    {
+        return None;
+    }
+
+    Some(*unsafe { Box::from_raw(userp as *mut Arc<T>) })
+}
+
+unsafe extern "C" fn inference_request_release(
+    _request_ptr: *mut triton_sys::TRITONSERVER_InferenceRequest,
+    flags: u32,
+    userp: *mut c_void,
+) {
+    if let Some(inner) = unsafe { release_hold::<RequestInner>(userp, flags) } {
+        inner.flight.leave();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASE_ALL: u32 =
+        triton_sys::TRITONSERVER_requestreleaseflag_enum_TRITONSERVER_REQUEST_RELEASE_ALL;
+
+    #[test]
+    fn flight_is_entered_once() {
+        let flight = Flight::default();
+        assert!(!flight.is_active());
+        assert!(flight.enter());
+        assert!(flight.is_active());
+        assert!(!flight.enter());
+
+        flight.leave();
+        assert!(!flight.is_active());
+        assert!(flight.enter());
+    }
+
+    #[test]
+    fn hold_is_released_with_release_all() {
+        let flight = Arc::new(Flight::default());
+        assert!(flight.enter());
+        let userp = hold(&flight);
+        assert_eq!(Arc::strong_count(&flight), 2);
+
+        assert!(unsafe { release_hold::<Flight>(userp, 0) }.is_none());
+        assert_eq!(Arc::strong_count(&flight), 2);
+
+        let held = unsafe { release_hold::<Flight>(userp, RELEASE_ALL) }.unwrap();
+        held.leave();
+        drop(held);
+        assert_eq!(Arc::strong_count(&flight), 1);
+        assert!(!flight.is_active());
+    }
+
+    /// Returns a different slice on every call.
+    struct Shifting(std::sync::atomic::AtomicUsize, [u8; 4]);
+
+    impl AsRef<[u8]> for Shifting {
+        fn as_ref(&self) -> &[u8] {
+            let start = self.0.fetch_add(1, Ordering::Relaxed) % 4;
+            &self.1[start..]
+        }
+    }
+
+    #[test]
+    fn owned_buffer_takes_its_bytes_once() {
+        let buffer = OwnedBuffer::new(Shifting(Default::default(), [1, 2, 3, 4]));
+        assert_eq!(buffer.byte_size, 4);
+        let bytes = unsafe { std::slice::from_raw_parts(buffer.base as *const u8, 4) };
+        assert_eq!(bytes, [1, 2, 3, 4]);
+
+        let typed = OwnedBuffer::new(TypedBuffer {
+            data: vec![1u16, 2],
+            _element: PhantomData,
+        });
+        assert_eq!(typed.byte_size, 4);
+    }
+
+    #[test]
+    fn hold_outlives_the_callers_handle() {
+        let flight = Arc::new(Flight::default());
+        let userp = hold(&flight);
+        let weak = Arc::downgrade(&flight);
+
+        drop(flight);
+        assert!(weak.upgrade().is_some());
+
+        drop(unsafe { release_hold::<Flight>(userp, RELEASE_ALL) });
+        assert!(weak.upgrade().is_none());
+    }
+}
@@ -7,6 +7,8 @@ pub mod request;
 #[macro_use]
 #[path = "macros.rs"]
 pub mod macros;
+#[path = "buffer_attributes.rs"]
+pub mod buffer_attributes;
 #[path = "error.rs"]
 pub mod error;
 #[path = "inference_request.rs"]
@@ -19,14 +21,20 @@ pub mod response;
 pub mod response_allocator;
 #[path = "server.rs"]
 pub mod server;
+#[path = "tensor.rs"]
+pub mod tensor;
 #[path = "utils.rs"]
 pub mod utils;
 
 pub use backend::*;
+pub use buffer_attributes::*;
 pub use error::*;
 pub use inference_request::*;
 pub use inference_response::*;
 pub use model::*;
 pub use request::*;
 pub use response::*;
+pub use response_allocator::*;
+pub use tensor::*;
 pub use triton_sys as sys;
+pub use triton_types as types;
@@ -1,8 +1,13 @@
 use crate::error::{Error, TritonError};
+use crate::tensor::{DataType, TensorElement, read_vec};
+#[cfg(feature = "ndarray")]
+use crate::tensor::{array_dim, cast_slice, shape_error};
 use crate::utils::{cstr_to_string, cstring_from_str, decode_string};
 use crate::{ensure_ptr, ffi_call};
 use libc::c_void;
+use std::borrow::Cow;
 use std::slice;
+use triton_types::{TensorError, TensorSource, TensorSpec, TritonInputs};
 
 pub struct Request {
     ptr: *mut triton_sys::TRITONBACKEND_Request,
@@ -30,6 +35,68 @@ impl Request {
 
         Ok(Input::from_ptr(input))
     }
+
+    pub fn id(&self) -> Result<String, TritonError> {
+        let mut id = std::ptr::null();
+        ffi_call!(triton_sys::TRITONBACKEND_RequestId(self.ptr, &mut id))?;
+
+        Ok(unsafe { cstr_to_string(id) })
+    }
+
+    pub fn input_count(&self) -> Result<u32, TritonError> {
+        let mut count = 0u32;
+        ffi_call!(
+            triton_sys::TRITONBACKEND_RequestInputCount(self.ptr, &mut count),
+            count
+        )
+    }
+
+    pub fn input_name(&self, index: u32) -> Result<String, TritonError> {
+        let mut name = std::ptr::null();
+        ffi_call!(triton_sys::TRITONBACKEND_RequestInputName(
+            self.ptr, index, &mut name
+        ))?;
+
+        Ok(unsafe { cstr_to_string(name) })
+    }
+
+    pub fn requested_output_names(&self) -> Result<Vec<String>, TritonError> {
+        let mut count = 0u32;
+        ffi_call!(triton_sys::TRITONBACKEND_RequestOutputCount(
+            self.ptr, &mut count
+        ))?;
+
+        (0..count)
+            .map(|index| {
+                let mut name = std::ptr::null();
+                ffi_call!(triton_sys::TRITONBACKEND_RequestOutputName(
+                    self.ptr, index, &mut name
+                ))?;
+
+                Ok(unsafe { cstr_to_string(name) })
+            })
+            .collect()
+    }
+
+    /// Reads every input of a `#[derive(TritonInputs)]` struct.
+    pub fn typed_inputs<T: TritonInputs>(&self) -> Result<T, TritonError> {
+        Ok(T::read_inputs(self)?)
+    }
+}
+
+impl TensorSource for Request {
+    fn tensor_bytes(&self, spec: &TensorSpec) -> Result<Cow<'_, [u8]>, TensorError> {
+        let input = self.get_input(spec.name)?;
+        let properties = input.properties()?;
+        if properties.datatype != spec.datatype {
+            return Err(TensorError::new(format!(
+                "Input '{}' is {}, not {}",
+                spec.name, properties.datatype, spec.datatype
+            )));
+        }
+
+        Ok(Cow::Owned(input.collect_bytes(&properties)?))
+    }
 }
 
 pub struct Input {
@@ -41,9 +108,15 @@ impl Input {
         Self { ptr }
     }
 
-    fn buffer(&self) -> Result<Vec<u8>, Error> {
+    pub fn as_ptr(&self) -> *mut triton_sys::TRITONBACKEND_Input {
+        self.ptr
+    }
+
+    /// Returns one of the `buffer_count` chunks that make up the input. The
+    /// memory is owned by Triton and stays valid until the request is
+    /// released.
+    pub fn buffer_at(&self, index: u32) -> Result<InputBuffer, TritonError> {
         let mut buffer: *const c_void = std::ptr::null_mut();
-        let index = 0;
         let mut memory_type: triton_sys::TRITONSERVER_MemoryType = 0;
         let mut memory_type_id = 0;
         let mut buffer_byte_size = 0;
@@ -56,8 +129,19 @@ impl Input {
             &mut memory_type_id,
         ))?;
 
+        Ok(InputBuffer {
+            base: buffer,
+            byte_size: buffer_byte_size,
+            memory_type,
+            memory_type_id,
+        })
+    }
+
+    fn buffer(&self) -> Result<Vec<u8>, Error> {
+        let buffer = self.buffer_at(0)?;
+
         let mem: &[u8] =
-            unsafe { slice::from_raw_parts(buffer as *mut u8, buffer_byte_size as usize) };
+            unsafe { slice::from_raw_parts(buffer.base as *mut u8, buffer.byte_size as usize) };
         Ok(mem.to_vec())
     }
 
@@ -97,7 +181,90 @@ impl Input {
         Ok(result)
     }
 
-    pub fn properties(&self) -> Result<InputProperties, Error> {
+    /// Copies the whole input, across all of its buffers, into a typed
+    /// vector. Fails if `T` does not match the input datatype.
+    pub fn to_vec<T: TensorElement>(&self) -> Result<Vec<T>, TritonError> {
+        let properties = self.typed_properties::<T>()?;
+        read_vec(&self.collect_bytes(&properties)?)
+    }
+
+    fn collect_bytes(&self, properties: &InputProperties) -> Result<Vec<u8>, TritonError> {
+        let mut bytes = Vec::with_capacity(properties.byte_size as usize);
+        for index in 0..properties.buffer_count {
+            let buffer = self.buffer_at(index)?;
+            if buffer.memory_type
+                == triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_GPU
+            {
+                return Err(TritonError::from_message(format!(
+                    "Input '{}' is in GPU memory",
+                    properties.name
+                )));
+            }
+
+            bytes.extend_from_slice(unsafe {
+                slice::from_raw_parts(buffer.base as *const u8, buffer.byte_size as usize)
+            });
+        }
+
+        Ok(bytes)
+    }
+
+    /// Copies the input into an n-dimensional array shaped like the input.
+    #[cfg(feature = "ndarray")]
+    pub fn to_array<T: TensorElement>(&self) -> Result<ndarray::ArrayD<T>, TritonError> {
+        let properties = self.typed_properties::<T>()?;
+        ndarray::ArrayD::from_shape_vec(array_dim(&properties.shape)?, self.to_vec()?)
+            .map_err(shape_error)
+    }
+
+    /// Borrows the input as an n-dimensional array without copying. Only
+    /// inputs held in a single, suitably aligned CPU buffer can be viewed;
+    /// use [`Input::to_array`] otherwise.
+    #[cfg(feature = "ndarray")]
+    pub fn as_array_view<T: TensorElement>(
+        &self,
+    ) -> Result<ndarray::ArrayViewD<'_, T>, TritonError> {
+        let properties = self.typed_properties::<T>()?;
+        if properties.buffer_count != 1 {
+            return Err(TritonError::from_message(format!(
+                "Input '{}' is split across {} buffers",
+                properties.name, properties.buffer_count
+            )));
+        }
+
+        let buffer = self.buffer_at(0)?;
+        if buffer.memory_type == triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_GPU {
+            return Err(TritonError::from_message(format!(
+                "Input '{}' is in GPU memory",
+                properties.name
+            )));
+        }
+
+        let bytes: &[u8] = if buffer.byte_size == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(buffer.base as *const u8, buffer.byte_size as usize) }
+        };
+
+        ndarray::ArrayViewD::from_shape(array_dim(&properties.shape)?, cast_slice(bytes)?)
+            .map_err(shape_error)
+    }
+
+    fn typed_properties<T: TensorElement>(&self) -> Result<InputProperties, TritonError> {
+        let properties = self.properties()?;
+        if properties.datatype != T::DATATYPE {
+            return Err(TritonError::from_message(format!(
+                "Input '{}' is {}, not {}",
+                properties.name,
+                properties.datatype,
+                T::DATATYPE
+            )));
+        }
+
+        Ok(properties)
+    }
+
+    pub fn properties(&self) -> Result<InputProperties, TritonError> {
         let mut name = std::ptr::null();
         let mut datatype = 0u32;
         let mut shape_ptr: *const i64 = std::ptr::null();
@@ -125,7 +292,7 @@ impl Input {
 
         Ok(InputProperties {
             name,
-            datatype,
+            datatype: DataType::from_raw(datatype),
             shape,
             dims_count,
             byte_size,
@@ -134,10 +301,18 @@ impl Input {
     }
 }
 
+#[derive(Debug)]
+pub struct InputBuffer {
+    pub base: *const c_void,
+    pub byte_size: u64,
+    pub memory_type: triton_sys::TRITONSERVER_MemoryType,
+    pub memory_type_id: i64,
+}
+
 #[derive(Debug)]
 pub struct InputProperties {
     pub name: String,
-    pub datatype: u32,
+    pub datatype: DataType,
     pub shape: Vec<i64>,
     pub dims_count: u32,
     pub byte_size: u64,
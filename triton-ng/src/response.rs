@@ -1,10 +1,13 @@
 use crate::Error;
 use crate::error::TritonError;
 use crate::request::Request;
+use crate::server::OutputTensor;
+use crate::tensor::{DataType, TensorElement, as_bytes};
 use crate::utils::{cstring_from_str, encode_string};
 use std::ffi::c_void;
 use std::ptr;
 use std::slice;
+use triton_types::{TensorError, TensorSink, TensorSpec, TritonOutputs};
 
 pub struct Response {
     ptr: *mut triton_sys::TRITONBACKEND_Response,
@@ -31,7 +34,7 @@ impl Response {
     pub fn create_output(
         &mut self,
         name: &str,
-        datatype: triton_sys::TRITONSERVER_DataType,
+        datatype: DataType,
         shape: &[i64],
     ) -> Result<Output, TritonError> {
         let mut output: *mut triton_sys::TRITONBACKEND_Output = ptr::null_mut();
@@ -41,7 +44,7 @@ impl Response {
             self.ptr,
             &mut output,
             name_cstr.as_ptr(),
-            datatype,
+            datatype.as_raw(),
             shape.as_ptr(),
             shape.len() as u32,
         ))?;
@@ -51,6 +54,25 @@ impl Response {
         Ok(Output::from_ptr(output))
     }
 
+    /// Adds every tensor of an in-process inference result to this response,
+    /// e.g. to return the outputs of a forwarded request unchanged.
+    pub fn write_outputs(&mut self, outputs: &[OutputTensor]) -> Result<(), TritonError> {
+        for tensor in outputs {
+            self.create_output(&tensor.name, tensor.datatype, &tensor.shape)?
+                .write_bytes(tensor.data()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes every output of a `#[derive(TritonOutputs)]` struct.
+    pub fn write_typed_outputs<T: TritonOutputs>(
+        &mut self,
+        outputs: &T,
+    ) -> Result<(), TritonError> {
+        Ok(outputs.write_outputs(self)?)
+    }
+
     pub fn send(self) -> Result<(), TritonError> {
         let send_flags =
             triton_sys::tritonserver_responsecompleteflag_enum_TRITONSERVER_RESPONSE_COMPLETE_FINAL;
@@ -63,6 +85,19 @@ impl Response {
     }
 }
 
+impl TensorSink for Response {
+    fn write_tensor(
+        &mut self,
+        spec: &TensorSpec,
+        shape: &[i64],
+        bytes: Vec<u8>,
+    ) -> Result<(), TensorError> {
+        self.create_output(spec.name, spec.datatype, shape)?
+            .write_bytes(&bytes)?;
+        Ok(())
+    }
+}
+
 pub struct Output {
     ptr: *mut triton_sys::TRITONBACKEND_Output,
 }
@@ -96,6 +131,11 @@ impl Output {
             &mut memory_type_id,
         ))?;
 
+        // Triton may hand out no memory at all for an empty tensor.
+        if data.is_empty() {
+            return Ok(());
+        }
+
         ensure_ptr!(buffer as *mut u8)?;
 
         let mem: &mut [u8] =
@@ -106,6 +146,28 @@ impl Output {
         Ok(())
     }
 
+    pub fn write_slice<T: TensorElement>(&mut self, data: &[T]) -> Result<(), TritonError> {
+        self.write_bytes(as_bytes(data))
+    }
+
+    /// Writes an array in row-major order, copying it first if its layout is
+    /// not already standard.
+    #[cfg(feature = "ndarray")]
+    pub fn write_array<T, S, D>(
+        &mut self,
+        array: &ndarray::ArrayBase<S, D>,
+    ) -> Result<(), TritonError>
+    where
+        T: TensorElement,
+        S: ndarray::Data<Elem = T>,
+        D: ndarray::Dimension,
+    {
+        match array.as_slice() {
+            Some(data) => self.write_slice(data),
+            None => self.write_slice(&array.iter().copied().collect::<Vec<_>>()),
+        }
+    }
+
     pub fn write_fp32_vec(&mut self, data: &[f32]) -> Result<(), TritonError> {
         let bytes: Vec<u8> = data.iter().flat_map(|&f| f.to_le_bytes()).collect();
 
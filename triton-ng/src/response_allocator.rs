@@ -1,14 +1,368 @@
 use crate::TritonError;
+use crate::buffer_attributes::BufferAttributes;
+use crate::utils::cstr_to_string;
 use std::alloc::{Layout, alloc, dealloc};
+use std::collections::HashMap;
+use std::mem::ManuallyDrop;
 use std::os::raw::{c_char, c_void};
 use std::ptr;
+use std::sync::{Arc, Mutex, OnceLock};
+
+const ALIGNMENT: usize = 8;
+
+static SHARED: OnceLock<Arc<ResponseAllocator>> = OnceLock::new();
+
+/// A buffer handed to Triton for one output tensor.
+#[derive(Debug)]
+pub struct ResponseBuffer {
+    pub base: *mut c_void,
+    pub byte_size: usize,
+    pub memory_type: triton_sys::TRITONSERVER_MemoryType,
+    pub memory_type_id: i64,
+    /// Opaque value returned to [`ResponseAllocatorImpl::release`].
+    pub userp: *mut c_void,
+}
+
+/// Memory management strategy behind a [`ResponseAllocator`].
+///
+/// Callbacks may run concurrently on Triton's threads.
+pub trait ResponseAllocatorImpl: Send + Sync {
+    /// Provides a buffer of `byte_size` bytes for `tensor_name`, ideally in
+    /// the requested memory. `byte_size` may be zero.
+    fn allocate(
+        &self,
+        tensor_name: &str,
+        byte_size: usize,
+        memory_type: triton_sys::TRITONSERVER_MemoryType,
+        memory_type_id: i64,
+    ) -> Result<ResponseBuffer, TritonError>;
+
+    /// Returns a buffer obtained from [`ResponseAllocatorImpl::allocate`]
+    /// once the response that used it is deleted.
+    fn release(&self, buffer: ResponseBuffer) -> Result<(), TritonError>;
+
+    /// Reports the memory a future allocation will use. Either argument may
+    /// be unknown. The default reports CPU memory, matching the built-in
+    /// allocators.
+    fn query(
+        &self,
+        _tensor_name: Option<&str>,
+        _byte_size: Option<usize>,
+        memory_type: &mut triton_sys::TRITONSERVER_MemoryType,
+        memory_type_id: &mut i64,
+    ) -> Result<(), TritonError> {
+        *memory_type = triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_CPU;
+        *memory_type_id = 0;
+        Ok(())
+    }
+
+    /// Fills in extra attributes, such as a CUDA IPC handle, for a buffer
+    /// returned by [`ResponseAllocatorImpl::allocate`].
+    fn buffer_attributes(
+        &self,
+        _tensor_name: &str,
+        _attributes: &mut BufferAttributes,
+        _buffer_userp: *mut c_void,
+    ) -> Result<(), TritonError> {
+        Ok(())
+    }
+}
+
+/// Lets the caller keep a handle on an implementation, e.g. to take back
+/// the buffers of a [`CallerBufferAllocator`].
+impl<T: ResponseAllocatorImpl + ?Sized> ResponseAllocatorImpl for Arc<T> {
+    fn allocate(
+        &self,
+        tensor_name: &str,
+        byte_size: usize,
+        memory_type: triton_sys::TRITONSERVER_MemoryType,
+        memory_type_id: i64,
+    ) -> Result<ResponseBuffer, TritonError> {
+        (**self).allocate(tensor_name, byte_size, memory_type, memory_type_id)
+    }
+
+    fn release(&self, buffer: ResponseBuffer) -> Result<(), TritonError> {
+        (**self).release(buffer)
+    }
+
+    fn query(
+        &self,
+        tensor_name: Option<&str>,
+        byte_size: Option<usize>,
+        memory_type: &mut triton_sys::TRITONSERVER_MemoryType,
+        memory_type_id: &mut i64,
+    ) -> Result<(), TritonError> {
+        (**self).query(tensor_name, byte_size, memory_type, memory_type_id)
+    }
+
+    fn buffer_attributes(
+        &self,
+        tensor_name: &str,
+        attributes: &mut BufferAttributes,
+        buffer_userp: *mut c_void,
+    ) -> Result<(), TritonError> {
+        (**self).buffer_attributes(tensor_name, attributes, buffer_userp)
+    }
+}
+
+/// Allocates every output from the heap and frees it on release.
+pub struct HeapAllocator;
+
+impl ResponseAllocatorImpl for HeapAllocator {
+    fn allocate(
+        &self,
+        _tensor_name: &str,
+        byte_size: usize,
+        _memory_type: triton_sys::TRITONSERVER_MemoryType,
+        _memory_type_id: i64,
+    ) -> Result<ResponseBuffer, TritonError> {
+        Ok(cpu_buffer(heap_alloc(byte_size)?, byte_size))
+    }
+
+    fn release(&self, buffer: ResponseBuffer) -> Result<(), TritonError> {
+        heap_free(buffer.base, buffer.byte_size);
+        Ok(())
+    }
+}
+
+/// Keeps released buffers and hands them out again for outputs of the same
+/// size, which avoids allocation churn when the same models are called
+/// repeatedly.
+pub struct PoolAllocator {
+    free: FreeList,
+}
+
+impl PoolAllocator {
+    /// Creates a pool that retains up to `max_per_size` idle buffers for
+    /// each distinct output size.
+    pub fn new(max_per_size: usize) -> Self {
+        Self {
+            free: FreeList::new(max_per_size),
+        }
+    }
+}
+
+impl ResponseAllocatorImpl for PoolAllocator {
+    fn allocate(
+        &self,
+        _tensor_name: &str,
+        byte_size: usize,
+        _memory_type: triton_sys::TRITONSERVER_MemoryType,
+        _memory_type_id: i64,
+    ) -> Result<ResponseBuffer, TritonError> {
+        let base = match self.free.take(byte_size) {
+            Some(addr) => addr as *mut c_void,
+            None => heap_alloc(byte_size)?,
+        };
+
+        Ok(cpu_buffer(base, byte_size))
+    }
+
+    fn release(&self, buffer: ResponseBuffer) -> Result<(), TritonError> {
+        if !buffer.base.is_null() && !self.free.put(buffer.byte_size, buffer.base as usize) {
+            heap_free(buffer.base, buffer.byte_size);
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for PoolAllocator {
+    fn drop(&mut self) {
+        for (byte_size, addr) in self.free.drain() {
+            heap_free(addr as *mut c_void, byte_size);
+        }
+    }
+}
+
+/// Idle buffer addresses of a [`PoolAllocator`] by size.
+struct FreeList {
+    max_per_size: usize,
+    idle: Mutex<HashMap<usize, Vec<usize>>>,
+}
+
+impl FreeList {
+    fn new(max_per_size: usize) -> Self {
+        Self {
+            max_per_size,
+            idle: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn take(&self, byte_size: usize) -> Option<usize> {
+        self.idle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(&byte_size)
+            .and_then(Vec::pop)
+    }
+
+    /// Keeps `addr` for reuse, or returns false if the list for its size is
+    /// full and the caller has to free it.
+    fn put(&self, byte_size: usize, addr: usize) -> bool {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        let idle = idle.entry(byte_size).or_default();
+
+        if idle.len() < self.max_per_size {
+            idle.push(addr);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn drain(&mut self) -> Vec<(usize, usize)> {
+        let idle = self.idle.get_mut().unwrap_or_else(|e| e.into_inner());
+
+        idle.drain()
+            .flat_map(|(byte_size, addrs)| addrs.into_iter().map(move |addr| (byte_size, addr)))
+            .collect()
+    }
+}
+
+/// Has Triton write outputs straight into buffers the caller provided, by
+/// tensor name, instead of into memory that would be copied out afterwards.
+/// A buffer is lent out while a response uses it and can be taken back,
+/// holding the output, once every tensor of that response is dropped.
+///
+/// Outputs without a buffer, or larger than theirs, are allocated from the
+/// heap. Typed views need the buffer to be aligned for the element type.
+///
+/// ```ignore
+/// let outputs = Arc::new(CallerBufferAllocator::new());
+/// outputs.insert("probabilities", vec![0u8; 10 * 4]);
+/// let allocator = ResponseAllocator::with_impl(Arc::clone(&outputs))?;
+/// request.set_response_allocator(Arc::new(allocator));
+///
+/// // The buffer comes back once the result's tensors are dropped.
+/// drop(server.infer_blocking(&request)?);
+/// let probabilities = outputs.take("probabilities").unwrap();
+/// ```
+#[derive(Default)]
+pub struct CallerBufferAllocator {
+    idle: Mutex<HashMap<String, Box<[u8]>>>,
+}
+
+/// The `userp` of a lent caller buffer, needed to rebuild the box.
+struct LentBuffer {
+    tensor_name: String,
+    capacity: usize,
+}
+
+impl CallerBufferAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Provides the buffer for `tensor_name`, replacing any idle one.
+    pub fn insert(&self, tensor_name: &str, buffer: impl Into<Box<[u8]>>) {
+        self.idle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(tensor_name.to_string(), buffer.into());
+    }
+
+    /// Takes back the buffer for `tensor_name` unless a response still uses
+    /// it.
+    pub fn take(&self, tensor_name: &str) -> Option<Box<[u8]>> {
+        self.idle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(tensor_name)
+    }
+
+    /// Lends the idle buffer for `tensor_name` if it can hold `byte_size`
+    /// bytes, returning its base and the userp to release it with.
+    fn lend(&self, tensor_name: &str, byte_size: usize) -> Option<(*mut c_void, *mut c_void)> {
+        let mut idle = self.idle.lock().unwrap_or_else(|e| e.into_inner());
+        if byte_size == 0 || idle.get(tensor_name)?.len() < byte_size {
+            return None;
+        }
+
+        let buffer = idle.remove(tensor_name)?;
+        let lent = LentBuffer {
+            tensor_name: tensor_name.to_string(),
+            capacity: buffer.len(),
+        };
+        let base = Box::into_raw(buffer) as *mut u8 as *mut c_void;
+        Some((base, Box::into_raw(Box::new(lent)) as *mut c_void))
+    }
+
+    /// Makes a buffer from [`CallerBufferAllocator::lend`] idle again.
+    ///
+    /// # Safety
+    ///
+    /// `base` and `userp` must come from one call to `lend` and not have
+    /// been given back yet.
+    unsafe fn give_back(&self, base: *mut c_void, userp: *mut c_void) {
+        let lent = unsafe { Box::from_raw(userp as *mut LentBuffer) };
+        let buffer = unsafe {
+            Box::from_raw(ptr::slice_from_raw_parts_mut(
+                base as *mut u8,
+                lent.capacity,
+            ))
+        };
+
+        self.idle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(lent.tensor_name, buffer);
+    }
+}
+
+impl ResponseAllocatorImpl for CallerBufferAllocator {
+    fn allocate(
+        &self,
+        tensor_name: &str,
+        byte_size: usize,
+        _memory_type: triton_sys::TRITONSERVER_MemoryType,
+        _memory_type_id: i64,
+    ) -> Result<ResponseBuffer, TritonError> {
+        match self.lend(tensor_name, byte_size) {
+            Some((base, userp)) => Ok(ResponseBuffer {
+                userp,
+                ..cpu_buffer(base, byte_size)
+            }),
+            None => Ok(cpu_buffer(heap_alloc(byte_size)?, byte_size)),
+        }
+    }
+
+    fn release(&self, buffer: ResponseBuffer) -> Result<(), TritonError> {
+        if buffer.userp.is_null() {
+            heap_free(buffer.base, buffer.byte_size);
+        } else {
+            // SAFETY: only `lend` sets a userp.
+            unsafe { self.give_back(buffer.base, buffer.userp) };
+        }
+        Ok(())
+    }
+}
 
 pub struct ResponseAllocator {
     ptr: *mut triton_sys::TRITONSERVER_ResponseAllocator,
+    implementation: Box<dyn ResponseAllocatorImpl>,
+}
+
+// SAFETY: the Triton allocator object is immutable after creation and the
+// implementation is required to be thread-safe.
+unsafe impl Send for ResponseAllocator {}
+unsafe impl Sync for ResponseAllocator {}
+
+/// Travels with every buffer so that the release callback, which receives no
+/// allocator userp, can find its way back to the implementation.
+struct BufferContext {
+    allocator: Arc<ResponseAllocator>,
+    userp: *mut c_void,
 }
 
 impl ResponseAllocator {
     pub fn new() -> Result<Self, TritonError> {
+        Self::with_impl(HeapAllocator)
+    }
+
+    pub fn with_impl(
+        implementation: impl ResponseAllocatorImpl + 'static,
+    ) -> Result<Self, TritonError> {
         let mut allocator_ptr: *mut triton_sys::TRITONSERVER_ResponseAllocator = ptr::null_mut();
 
         ffi_call!(triton_sys::TRITONSERVER_ResponseAllocatorNew(
@@ -20,55 +374,141 @@ impl ResponseAllocator {
 
         ensure_ptr!(allocator_ptr)?;
 
-        Ok(Self { ptr: allocator_ptr })
+        let allocator = Self {
+            ptr: allocator_ptr,
+            implementation: Box::new(implementation),
+        };
+
+        ffi_call!(triton_sys::TRITONSERVER_ResponseAllocatorSetQueryFunction(
+            allocator.ptr,
+            Some(response_query_fn),
+        ))?;
+
+        ffi_call!(
+            triton_sys::TRITONSERVER_ResponseAllocatorSetBufferAttributesFunction(
+                allocator.ptr,
+                Some(response_buffer_attributes_fn),
+            )
+        )?;
+
+        Ok(allocator)
+    }
+
+    /// Process-wide heap allocator used by requests that don't set their own.
+    pub fn shared() -> Result<Arc<Self>, TritonError> {
+        if let Some(allocator) = SHARED.get() {
+            return Ok(Arc::clone(allocator));
+        }
+
+        let allocator = Arc::new(Self::new()?);
+        Ok(Arc::clone(SHARED.get_or_init(|| allocator)))
     }
 
     pub fn as_ptr(&self) -> *mut triton_sys::TRITONSERVER_ResponseAllocator {
         self.ptr
     }
+
+    /// Value to pass as `response_allocator_userp`. The caller keeps the
+    /// `Arc` alive until the final response has been delivered.
+    pub(crate) fn userp(allocator: &Arc<Self>) -> *mut c_void {
+        Arc::as_ptr(allocator) as *mut c_void
+    }
+}
+
+impl Drop for ResponseAllocator {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe {
+                triton_sys::TRITONSERVER_ResponseAllocatorDelete(self.ptr);
+            }
+        }
+    }
+}
+
+fn heap_alloc(byte_size: usize) -> Result<*mut c_void, TritonError> {
+    if byte_size == 0 {
+        return Ok(ptr::null_mut());
+    }
+
+    let layout = Layout::from_size_align(byte_size, ALIGNMENT)
+        .map_err(|_| TritonError::from_message("Invalid layout"))?;
+
+    let buf = unsafe { alloc(layout) as *mut c_void };
+
+    if buf.is_null() {
+        return Err(TritonError::from_message("Failed to allocate memory"));
+    }
+
+    Ok(buf)
+}
+
+fn heap_free(buffer: *mut c_void, byte_size: usize) {
+    if !buffer.is_null()
+        && byte_size > 0
+        && let Ok(layout) = Layout::from_size_align(byte_size, ALIGNMENT)
+    {
+        unsafe { dealloc(buffer as *mut u8, layout) };
+    }
+}
+
+fn cpu_buffer(base: *mut c_void, byte_size: usize) -> ResponseBuffer {
+    ResponseBuffer {
+        base,
+        byte_size,
+        memory_type: triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_CPU,
+        memory_type_id: 0,
+        userp: ptr::null_mut(),
+    }
+}
+
+/// Borrows the allocator behind a `response_allocator_userp` and takes a
+/// new strong reference to it.
+///
+/// # Safety
+///
+/// `userp` must come from [`ResponseAllocator::userp`] with the `Arc` alive.
+unsafe fn allocator_from_userp(userp: *mut c_void) -> Arc<ResponseAllocator> {
+    let allocator = userp as *const ResponseAllocator;
+
+    unsafe {
+        Arc::increment_strong_count(allocator);
+        Arc::from_raw(allocator)
+    }
 }
 
 unsafe extern "C" fn response_allocator_fn(
     _allocator: *mut triton_sys::TRITONSERVER_ResponseAllocator,
-    _tensor_name: *const c_char,
+    tensor_name: *const c_char,
     byte_size: usize,
-    _memory_type: triton_sys::TRITONSERVER_MemoryType,
-    _memory_type_id: i64,
-    _userp: *mut c_void,
+    memory_type: triton_sys::TRITONSERVER_MemoryType,
+    memory_type_id: i64,
+    userp: *mut c_void,
     buffer: *mut *mut c_void,
     buffer_userp: *mut *mut c_void,
     actual_memory_type: *mut triton_sys::TRITONSERVER_MemoryType,
     actual_memory_type_id: *mut i64,
 ) -> *mut triton_sys::TRITONSERVER_Error {
-    let layout = match Layout::from_size_align(byte_size, 8) {
-        Ok(l) => l,
-        Err(_) => {
-            return unsafe {
-                triton_sys::TRITONSERVER_ErrorNew(
-                    triton_sys::TRITONSERVER_errorcode_enum_TRITONSERVER_ERROR_INTERNAL,
-                    c"Invalid layout".as_ptr() as *const c_char,
-                )
-            };
-        }
-    };
+    let allocator = unsafe { allocator_from_userp(userp) };
+    let tensor_name = unsafe { cstr_to_string(tensor_name) };
 
-    let buf = unsafe { alloc(layout) as *mut c_void };
-
-    if buf.is_null() {
-        return unsafe {
-            triton_sys::TRITONSERVER_ErrorNew(
-                triton_sys::TRITONSERVER_errorcode_enum_TRITONSERVER_ERROR_INTERNAL,
-                c"Failed to allocate memory".as_ptr() as *const c_char,
-            )
-        };
-    }
+    let allocated = match allocator.implementation.allocate(
+        &tensor_name,
+        byte_size,
+        memory_type,
+        memory_type_id,
+    ) {
+        Ok(allocated) => allocated,
+        Err(e) => return e.into_raw(),
+    };
 
-    // TODO: TRITONSERVER_MEMORY_GPU
     unsafe {
-        *buffer = buf;
-        *buffer_userp = ptr::null_mut();
-        *actual_memory_type = triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_CPU;
-        *actual_memory_type_id = 0;
+        *buffer = allocated.base;
+        *actual_memory_type = allocated.memory_type;
+        *actual_memory_type_id = allocated.memory_type_id;
+        *buffer_userp = Box::into_raw(Box::new(BufferContext {
+            allocator,
+            userp: allocated.userp,
+        })) as *mut c_void;
     }
 
     ptr::null_mut()
@@ -77,15 +517,139 @@ unsafe extern "C" fn response_allocator_fn(
 unsafe extern "C" fn response_release_fn(
     _allocator: *mut triton_sys::TRITONSERVER_ResponseAllocator,
     buffer: *mut c_void,
-    _buffer_userp: *mut c_void,
+    buffer_userp: *mut c_void,
     byte_size: usize,
-    _memory_type: triton_sys::TRITONSERVER_MemoryType,
-    _memory_type_id: i64,
+    memory_type: triton_sys::TRITONSERVER_MemoryType,
+    memory_type_id: i64,
+) -> *mut triton_sys::TRITONSERVER_Error {
+    if buffer_userp.is_null() {
+        return ptr::null_mut();
+    }
+
+    let context = unsafe { Box::from_raw(buffer_userp as *mut BufferContext) };
+
+    let released = context.allocator.implementation.release(ResponseBuffer {
+        base: buffer,
+        byte_size,
+        memory_type,
+        memory_type_id,
+        userp: context.userp,
+    });
+
+    match released {
+        Ok(()) => ptr::null_mut(),
+        Err(e) => e.into_raw(),
+    }
+}
+
+unsafe extern "C" fn response_query_fn(
+    _allocator: *mut triton_sys::TRITONSERVER_ResponseAllocator,
+    userp: *mut c_void,
+    tensor_name: *const c_char,
+    byte_size: *mut usize,
+    memory_type: *mut triton_sys::TRITONSERVER_MemoryType,
+    memory_type_id: *mut i64,
+) -> *mut triton_sys::TRITONSERVER_Error {
+    let allocator = unsafe { allocator_from_userp(userp) };
+    let tensor_name = (!tensor_name.is_null()).then(|| unsafe { cstr_to_string(tensor_name) });
+    let byte_size = (!byte_size.is_null()).then(|| unsafe { *byte_size });
+
+    let queried = allocator.implementation.query(
+        tensor_name.as_deref(),
+        byte_size,
+        unsafe { &mut *memory_type },
+        unsafe { &mut *memory_type_id },
+    );
+
+    match queried {
+        Ok(()) => ptr::null_mut(),
+        Err(e) => e.into_raw(),
+    }
+}
+
+unsafe extern "C" fn response_buffer_attributes_fn(
+    _allocator: *mut triton_sys::TRITONSERVER_ResponseAllocator,
+    tensor_name: *const c_char,
+    buffer_attributes: *mut triton_sys::TRITONSERVER_BufferAttributes,
+    _userp: *mut c_void,
+    buffer_userp: *mut c_void,
 ) -> *mut triton_sys::TRITONSERVER_Error {
-    if !buffer.is_null() && byte_size > 0 {
-        if let Ok(layout) = Layout::from_size_align(byte_size, 8) {
-            unsafe { dealloc(buffer as *mut u8, layout) };
+    if buffer_userp.is_null() {
+        return ptr::null_mut();
+    }
+
+    let context = unsafe { &*(buffer_userp as *const BufferContext) };
+    let tensor_name = unsafe { cstr_to_string(tensor_name) };
+
+    // The attributes are owned by Triton, so they must not be deleted here.
+    let mut attributes = ManuallyDrop::new(BufferAttributes::from_ptr(buffer_attributes));
+
+    let filled = context.allocator.implementation.buffer_attributes(
+        &tensor_name,
+        &mut attributes,
+        context.userp,
+    );
+
+    match filled {
+        Ok(()) => ptr::null_mut(),
+        Err(e) => e.into_raw(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_list_reuses_buffers_of_the_same_size() {
+        let free = FreeList::new(2);
+        assert_eq!(free.take(64), None);
+
+        assert!(free.put(64, 0x1000));
+        assert!(free.put(32, 0x2000));
+        assert_eq!(free.take(16), None);
+        assert_eq!(free.take(64), Some(0x1000));
+        assert_eq!(free.take(64), None);
+        assert_eq!(free.take(32), Some(0x2000));
+    }
+
+    #[test]
+    fn free_list_is_bounded_per_size() {
+        let mut free = FreeList::new(1);
+        assert!(free.put(16, 0x1000));
+        assert!(!free.put(16, 0x2000));
+        assert!(free.put(8, 0x3000));
+
+        let mut drained = free.drain();
+        drained.sort();
+        assert_eq!(drained, [(8, 0x3000), (16, 0x1000)]);
+        assert_eq!(free.take(16), None);
+    }
+
+    #[test]
+    fn caller_buffers_are_lent_and_given_back() {
+        let allocator = CallerBufferAllocator::new();
+        allocator.insert("output", vec![0u8; 8]);
+        assert!(allocator.lend("other", 4).is_none());
+        assert!(allocator.lend("output", 16).is_none());
+        assert!(allocator.lend("output", 0).is_none());
+
+        let (base, userp) = allocator.lend("output", 4).unwrap();
+        assert!(allocator.lend("output", 4).is_none());
+        assert!(allocator.take("output").is_none());
+
+        unsafe {
+            std::slice::from_raw_parts_mut(base as *mut u8, 4).copy_from_slice(&[1, 2, 3, 4]);
+            allocator.give_back(base, userp);
         }
+        let buffer = allocator.take("output").unwrap();
+        assert_eq!(*buffer, [1, 2, 3, 4, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn empty_pool_keeps_nothing() {
+        let free = FreeList::new(0);
+        assert!(!free.put(16, 0x1000));
+        assert_eq!(free.take(16), None);
     }
-    ptr::null_mut()
 }
@@ -2,16 +2,109 @@ use crate::TritonError;
 use crate::inference_request::InferenceRequest;
 use crate::inference_response::InferenceResponse;
 use crate::response_allocator::ResponseAllocator;
+use crate::tensor::{DataType, TensorElement, cast_slice};
+#[cfg(feature = "ndarray")]
+use crate::tensor::{array_dim, shape_error};
 use crate::utils::cstring_from_str;
-use crossbeam::channel::{Sender, bounded};
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
+use futures::executor::{BlockingStream, block_on, block_on_stream};
+use futures::{Stream, StreamExt};
 use std::ffi::c_void;
-use std::ptr;
-
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::{ptr, slice};
+
+/// An output of an in-process inference. The data stays in the buffer the
+/// response allocator provided and is released once every tensor of the
+/// response has been dropped.
 pub struct OutputTensor {
     pub name: String,
-    pub data: Vec<u8>,
     pub shape: Vec<i64>,
-    pub datatype: String,
+    pub datatype: DataType,
+    pub memory_type: triton_sys::TRITONSERVER_MemoryType,
+    pub memory_type_id: i64,
+    base: *const c_void,
+    byte_size: usize,
+    _response: Arc<InferenceResponse>,
+}
+
+// SAFETY: the buffer is only read, and the response that owns it is shared
+// through an `Arc`.
+unsafe impl Send for OutputTensor {}
+unsafe impl Sync for OutputTensor {}
+
+impl OutputTensor {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        response: Arc<InferenceResponse>,
+        name: String,
+        shape: Vec<i64>,
+        datatype: DataType,
+        base: *const c_void,
+        byte_size: usize,
+        memory_type: triton_sys::TRITONSERVER_MemoryType,
+        memory_type_id: i64,
+    ) -> Self {
+        Self {
+            name,
+            shape,
+            datatype,
+            memory_type,
+            memory_type_id,
+            base,
+            byte_size,
+            _response: response,
+        }
+    }
+
+    pub fn byte_size(&self) -> usize {
+        self.byte_size
+    }
+
+    /// Borrows the raw output bytes. Only buffers in CPU memory can be
+    /// accessed this way.
+    pub fn data(&self) -> Result<&[u8], TritonError> {
+        if self.memory_type == triton_sys::TRITONSERVER_memorytype_enum_TRITONSERVER_MEMORY_GPU {
+            return Err(TritonError::from_message(format!(
+                "Output '{}' is in GPU memory",
+                self.name
+            )));
+        }
+
+        if self.base.is_null() || self.byte_size == 0 {
+            return Ok(&[]);
+        }
+
+        Ok(unsafe { slice::from_raw_parts(self.base as *const u8, self.byte_size) })
+    }
+
+    /// Borrows the output as elements of `T`, which must match its datatype.
+    pub fn as_slice<T: TensorElement>(&self) -> Result<&[T], TritonError> {
+        if T::DATATYPE != self.datatype {
+            return Err(TritonError::from_message(format!(
+                "Output '{}' has datatype {}, not {}",
+                self.name,
+                self.datatype,
+                T::DATATYPE
+            )));
+        }
+
+        cast_slice(self.data()?)
+    }
+
+    pub fn to_vec<T: TensorElement>(&self) -> Result<Vec<T>, TritonError> {
+        Ok(self.as_slice()?.to_vec())
+    }
+
+    /// Borrows the output as an n-dimensional array shaped like the output.
+    #[cfg(feature = "ndarray")]
+    pub fn as_array_view<T: TensorElement>(
+        &self,
+    ) -> Result<ndarray::ArrayViewD<'_, T>, TritonError> {
+        ndarray::ArrayViewD::from_shape(array_dim(&self.shape)?, self.as_slice()?)
+            .map_err(shape_error)
+    }
 }
 
 pub struct InferenceResult {
@@ -19,9 +112,53 @@ pub struct InferenceResult {
     pub error: Option<String>,
 }
 
+impl InferenceResult {
+    fn from_error(error: impl ToString) -> Self {
+        Self {
+            outputs: vec![],
+            error: Some(error.to_string()),
+        }
+    }
+
+    fn into_result(self) -> Result<Self, TritonError> {
+        match self.error {
+            Some(error) => Err(TritonError::from_message(error)),
+            None => Ok(self),
+        }
+    }
+}
+
+/// Lives from submission until the response flagged
+/// `TRITONSERVER_RESPONSE_COMPLETE_FINAL` has been delivered.
 pub struct InferenceContext {
-    tx: Sender<InferenceResult>,
-    allocator: ResponseAllocator,
+    tx: UnboundedSender<InferenceResult>,
+    // Keeps the allocator's userp valid while responses are produced.
+    _allocator: Arc<ResponseAllocator>,
+}
+
+/// Responses of a single in-process request, ending after the final one.
+///
+/// Decoupled models may send any number of responses, including none.
+pub struct InferenceStream {
+    rx: UnboundedReceiver<InferenceResult>,
+}
+
+impl InferenceStream {
+    /// Turns the stream into a blocking iterator for callers that are not
+    /// running inside an executor.
+    pub fn blocking_iter(self) -> BlockingStream<Self> {
+        block_on_stream(self)
+    }
+}
+
+impl Stream for InferenceStream {
+    type Item = Result<InferenceResult, TritonError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx
+            .poll_next_unpin(cx)
+            .map(|result| result.map(InferenceResult::into_result))
+    }
 }
 
 pub struct Server {
@@ -101,76 +238,119 @@ impl Server {
         Ok(result)
     }
 
-    pub fn infer_async(&self, request: &InferenceRequest) -> Result<InferenceResult, TritonError> {
-        let (tx, rx) = bounded(1);
-        let allocator = ResponseAllocator::new()?;
+    /// Submits `request` for asynchronous execution and returns a future
+    /// that resolves once Triton delivers the response.
+    ///
+    /// Several calls can be in flight at once, which lets a backend fan out
+    /// BLS requests and await them together. Use [`Server::infer_stream`]
+    /// for decoupled models that send more than one response.
+    pub fn infer(
+        &self,
+        request: &InferenceRequest,
+    ) -> impl Future<Output = Result<InferenceResult, TritonError>> + use<> {
+        let rx = self.submit(request);
+
+        async move {
+            rx?.next()
+                .await
+                .ok_or_else(|| TritonError::from_message("Inference channel closed"))?
+                .into_result()
+        }
+    }
+
+    /// Blocking convenience wrapper around [`Server::infer`].
+    pub fn infer_blocking(
+        &self,
+        request: &InferenceRequest,
+    ) -> Result<InferenceResult, TritonError> {
+        block_on(self.infer(request))
+    }
+
+    /// Submits `request` and returns every response Triton produces for it,
+    /// as needed for decoupled models.
+    pub fn infer_stream(&self, request: &InferenceRequest) -> Result<InferenceStream, TritonError> {
+        Ok(InferenceStream {
+            rx: self.submit(request)?,
+        })
+    }
 
-        let context = Box::new(InferenceContext { tx, allocator });
+    fn submit(
+        &self,
+        request: &InferenceRequest,
+    ) -> Result<UnboundedReceiver<InferenceResult>, TritonError> {
+        let (tx, rx) = unbounded();
+        let allocator = request.response_allocator()?;
+        let allocator_ptr = allocator.as_ptr();
+        let allocator_userp = ResponseAllocator::userp(&allocator);
+
+        let context = Box::new(InferenceContext {
+            tx,
+            _allocator: allocator,
+        });
+
+        let release_userp = request.acquire()?;
         let context_ptr = Box::into_raw(context) as *mut c_void;
 
-        ffi_call!(
+        let submitted = ffi_call!(
             triton_sys::TRITONSERVER_InferenceRequestSetResponseCallback(
                 request.as_ptr(),
-                (*context_ptr.cast::<InferenceContext>()).allocator.as_ptr(),
-                ptr::null_mut(),
+                allocator_ptr,
+                allocator_userp,
                 Some(inference_response_complete),
                 context_ptr,
             )
-        )?;
-
-        ffi_call!(triton_sys::TRITONSERVER_ServerInferAsync(
-            self.ptr,
-            request.as_ptr(),
-            ptr::null_mut(),
-        ))?;
-
-        let result = rx
-            .recv()
-            .map_err(|_| TritonError::from_message("Inference channel closed"))?;
-
-        if let Some(error) = result.error {
-            return Err(TritonError::from_message(&error));
+        )
+        .and_then(|_| {
+            ffi_call!(triton_sys::TRITONSERVER_ServerInferAsync(
+                self.ptr,
+                request.as_ptr(),
+                ptr::null_mut(),
+            ))
+        });
+
+        if let Err(e) = submitted {
+            // SAFETY: Triton never took ownership of the request, so neither
+            // callback will run and both contexts are still ours.
+            drop(unsafe { Box::from_raw(context_ptr as *mut InferenceContext) });
+            request.abandon(release_userp);
+            return Err(e);
         }
 
-        Ok(result)
+        Ok(rx)
     }
 }
 
 unsafe extern "C" fn inference_response_complete(
     response_ptr: *mut triton_sys::TRITONSERVER_InferenceResponse,
-    _flags: u32,
+    flags: u32,
     userp: *mut std::os::raw::c_void,
 ) {
-    let context = unsafe { Box::from_raw(userp as *mut InferenceContext) };
-
-    let response = match InferenceResponse::from_ptr(response_ptr) {
-        Ok(r) => r,
-        Err(e) => {
-            let _ = context.tx.send(InferenceResult {
-                outputs: vec![],
-                error: Some(e.to_string()),
-            });
-            return;
-        }
-    };
-
-    let result = if let Some(error) = response.error() {
-        InferenceResult {
-            outputs: vec![],
-            error: Some(error.to_string()),
-        }
-    } else {
-        match response.outputs() {
-            Ok(outputs) => InferenceResult {
-                outputs,
-                error: None,
-            },
-            Err(e) => InferenceResult {
-                outputs: vec![],
-                error: Some(e.to_string()),
+    let context = userp as *mut InferenceContext;
+
+    // A decoupled model may signal completion without a response.
+    if !response_ptr.is_null() {
+        let result = match InferenceResponse::from_ptr(response_ptr).map(Arc::new) {
+            Ok(response) => match response.error() {
+                Some(error) => InferenceResult::from_error(error),
+                None => match response.outputs() {
+                    Ok(outputs) => InferenceResult {
+                        outputs,
+                        error: None,
+                    },
+                    Err(e) => InferenceResult::from_error(e),
+                },
             },
-        }
-    };
+            Err(e) => InferenceResult::from_error(e),
+        };
+
+        // The receiver may already be gone; Triton keeps calling until final.
+        let _ = unsafe { &(*context).tx }.unbounded_send(result);
+    }
 
-    let _ = context.tx.send(result);
+    if flags
+        & triton_sys::tritonserver_responsecompleteflag_enum_TRITONSERVER_RESPONSE_COMPLETE_FINAL
+        != 0
+    {
+        drop(unsafe { Box::from_raw(context) });
+    }
 }
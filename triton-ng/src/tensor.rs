@@ -0,0 +1,125 @@
+use crate::TritonError;
+use std::mem::{size_of, size_of_val};
+use std::{ptr, slice};
+
+#[cfg(feature = "ndarray")]
+pub use ndarray;
+pub use triton_types::{
+    DataType, TensorError, TensorSink, TensorSource, TensorSpec, TensorValue, TritonInputs,
+    TritonOutputs, element_count,
+};
+#[cfg(feature = "half")]
+pub use triton_types::{bf16, bf16_to_f32, f16, f16_to_f32, f32_to_bf16, f32_to_f16};
+
+/// Plain element types that map onto a Triton datatype.
+///
+/// # Safety
+///
+/// Implementors must have no padding and be valid for every bit pattern, so
+/// that a tensor buffer can be reinterpreted as `[Self]` and vice versa.
+pub unsafe trait TensorElement: Copy + 'static {
+    const DATATYPE: DataType;
+}
+
+macro_rules! tensor_element {
+    ($ty:ty, $datatype:ident) => {
+        unsafe impl TensorElement for $ty {
+            const DATATYPE: DataType = DataType::$datatype;
+        }
+    };
+}
+
+tensor_element!(u8, Uint8);
+tensor_element!(u16, Uint16);
+tensor_element!(u32, Uint32);
+tensor_element!(u64, Uint64);
+tensor_element!(i8, Int8);
+tensor_element!(i16, Int16);
+tensor_element!(i32, Int32);
+tensor_element!(i64, Int64);
+tensor_element!(f32, Fp32);
+tensor_element!(f64, Fp64);
+#[cfg(feature = "half")]
+tensor_element!(f16, Fp16);
+#[cfg(feature = "half")]
+tensor_element!(bf16, Bf16);
+
+/// Views a typed slice as the raw bytes Triton expects.
+pub fn as_bytes<T: TensorElement>(data: &[T]) -> &[u8] {
+    // SAFETY: `TensorElement` types have no padding.
+    unsafe { slice::from_raw_parts(data.as_ptr() as *const u8, size_of_val(data)) }
+}
+
+/// Views raw tensor bytes as elements of `T` without copying. Fails if the
+/// length is not a multiple of the element size or the buffer is misaligned.
+pub fn cast_slice<T: TensorElement>(bytes: &[u8]) -> Result<&[T], TritonError> {
+    if !bytes.len().is_multiple_of(size_of::<T>()) {
+        return Err(TritonError::from_message(format!(
+            "Buffer of {} bytes is not a whole number of {} elements",
+            bytes.len(),
+            T::DATATYPE
+        )));
+    }
+
+    if bytes.is_empty() {
+        return Ok(&[]);
+    }
+
+    if !bytes.as_ptr().cast::<T>().is_aligned() {
+        return Err(TritonError::from_message(format!(
+            "Buffer is not aligned for {} elements",
+            T::DATATYPE
+        )));
+    }
+
+    // SAFETY: length and alignment were checked and `TensorElement` types
+    // accept every bit pattern.
+    Ok(unsafe { slice::from_raw_parts(bytes.as_ptr() as *const T, bytes.len() / size_of::<T>()) })
+}
+
+/// Copies raw tensor bytes into a `Vec<T>`, regardless of alignment.
+pub fn read_vec<T: TensorElement>(bytes: &[u8]) -> Result<Vec<T>, TritonError> {
+    if !bytes.len().is_multiple_of(size_of::<T>()) {
+        return Err(TritonError::from_message(format!(
+            "Buffer of {} bytes is not a whole number of {} elements",
+            bytes.len(),
+            T::DATATYPE
+        )));
+    }
+
+    Ok(bytes
+        .chunks_exact(size_of::<T>())
+        // SAFETY: each chunk holds exactly one element and `TensorElement`
+        // types accept every bit pattern.
+        .map(|chunk| unsafe { ptr::read_unaligned(chunk.as_ptr() as *const T) })
+        .collect())
+}
+
+/// Converts a Triton shape into ndarray dimensions. Triton tensors are
+/// row-major, which is also ndarray's default layout.
+#[cfg(feature = "ndarray")]
+pub fn array_dim(shape: &[i64]) -> Result<ndarray::IxDyn, TritonError> {
+    let dims = shape
+        .iter()
+        .map(|&dim| usize::try_from(dim))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| TritonError::from_message(format!("Shape {shape:?} is not concrete")))?;
+
+    Ok(ndarray::IxDyn(&dims))
+}
+
+#[cfg(feature = "ndarray")]
+pub(crate) fn shape_error(error: ndarray::ShapeError) -> TritonError {
+    TritonError::from_message(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_bytes_is_little_endian() {
+        assert_eq!(as_bytes(&[1u16, 0x0302]), [1, 0, 2, 3]);
+        assert!(as_bytes::<f32>(&[]).is_empty());
+    }
+}
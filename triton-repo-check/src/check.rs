@@ -0,0 +1,900 @@
+use crate::report::{Diagnostic, Report, Severity};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use triton_grpc_client::inference::model_config::SchedulingChoice;
+use triton_grpc_client::inference::model_version_policy::PolicyChoice;
+use triton_grpc_client::inference::{DataType, ModelConfig, ModelTensorReshape};
+use triton_model_config::Error as ConfigError;
+
+const CONFIG_FILE: &str = "config.pbtxt";
+const ENSEMBLE: &str = "ensemble";
+
+/// Backends shipped in the tritonserver image, which need no library under
+/// the backends directory.
+const BUILTIN_BACKENDS: &[&str] = &[
+    "onnxruntime",
+    "tensorrt",
+    "pytorch",
+    "tensorflow",
+    "python",
+    "openvino",
+    "fil",
+    "dali",
+];
+
+struct Model {
+    name: String,
+    path: PathBuf,
+    config: Option<ModelConfig>,
+    versions: Vec<i64>,
+}
+
+impl Model {
+    fn config_path(&self) -> PathBuf {
+        self.path.join(CONFIG_FILE)
+    }
+}
+
+/// Checks every model directory of `repository`, resolving custom backends
+/// against `backends`.
+pub fn check_repository(repository: &Path, backends: &Path) -> io::Result<Report> {
+    let mut checker = Checker::default();
+
+    let mut directories = std::fs::read_dir(repository)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && !is_hidden(path))
+        .collect::<Vec<_>>();
+    directories.sort();
+
+    let models = directories
+        .into_iter()
+        .map(|path| checker.load(path))
+        .collect::<Vec<_>>();
+    let by_name: HashMap<&str, &Model> = models
+        .iter()
+        .map(|model| (model.name.as_str(), model))
+        .collect();
+
+    for model in &models {
+        let Some(config) = &model.config else {
+            continue;
+        };
+
+        checker.check_name(model, config);
+        checker.check_versions(model, config);
+        checker.check_backend(model, config, backends);
+        checker.check_tensors(model, config);
+        if config.platform == ENSEMBLE {
+            checker.check_ensemble(model, config, &by_name);
+        }
+    }
+
+    Ok(Report::new(repository, models.len(), checker.diagnostics))
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+#[derive(Default)]
+struct Checker {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker {
+    fn report(
+        &mut self,
+        severity: Severity,
+        code: &'static str,
+        model: &Model,
+        path: PathBuf,
+        message: String,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            code,
+            model: Some(model.name.clone()),
+            path,
+            line: None,
+            column: None,
+            message,
+        });
+    }
+
+    fn error(&mut self, code: &'static str, model: &Model, message: String) {
+        self.report(Severity::Error, code, model, model.config_path(), message);
+    }
+
+    fn load(&mut self, path: PathBuf) -> Model {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut model = Model {
+            name,
+            path,
+            config: None,
+            versions: vec![],
+        };
+
+        let listing = std::fs::read_dir(&model.path).and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>()
+        });
+        let mut entries = match listing {
+            Ok(entries) => entries,
+            Err(error) => {
+                // Without a listing, version and config checks would only
+                // report misleading follow-up errors.
+                let message = format!("Cannot read model directory: {error}");
+                self.report(
+                    Severity::Error,
+                    "io-error",
+                    &model,
+                    model.path.clone(),
+                    message,
+                );
+                return model;
+            }
+        };
+        entries.retain(|path| path.is_dir() && !is_hidden(path));
+        entries.sort();
+
+        for entry in entries {
+            let file_name = entry.file_name().unwrap_or_default().to_string_lossy();
+            // Triton skips anything that is not a positive number without a
+            // leading zero.
+            match file_name.parse::<i64>() {
+                Ok(version) if version > 0 && !file_name.starts_with(['0', '+']) => {
+                    model.versions.push(version)
+                }
+                _ => {
+                    let message =
+                        format!("'{file_name}' is not a version directory and is ignored");
+                    self.report(
+                        Severity::Warning,
+                        "ignored-directory",
+                        &model,
+                        entry,
+                        message,
+                    );
+                }
+            }
+        }
+        model.versions.sort_unstable();
+
+        let config_path = model.config_path();
+        if !config_path.is_file() {
+            let message = format!("Model has no {CONFIG_FILE}");
+            self.report(
+                Severity::Error,
+                "missing-config",
+                &model,
+                config_path,
+                message,
+            );
+            return model;
+        }
+
+        match triton_model_config::load(&config_path) {
+            Ok(config) => model.config = Some(config),
+            Err(ConfigError::Parse { path, source }) => self.diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                code: "parse-error",
+                model: Some(model.name.clone()),
+                path,
+                line: Some(source.line),
+                column: Some(source.column),
+                message: source.message,
+            }),
+            Err(error) => {
+                let message = error.to_string();
+                self.report(Severity::Error, "io-error", &model, config_path, message);
+            }
+        }
+
+        model
+    }
+
+    fn check_name(&mut self, model: &Model, config: &ModelConfig) {
+        if !config.name.is_empty() && config.name != model.name {
+            self.error(
+                "name-mismatch",
+                model,
+                format!(
+                    "Config name '{}' does not match directory '{}'",
+                    config.name, model.name
+                ),
+            );
+        }
+    }
+
+    fn check_versions(&mut self, model: &Model, config: &ModelConfig) {
+        if model.versions.is_empty() {
+            self.error(
+                "no-versions",
+                model,
+                "Model has no version directories such as '1/'".to_string(),
+            );
+            return;
+        }
+
+        let policy = config
+            .version_policy
+            .as_ref()
+            .and_then(|policy| policy.policy_choice.as_ref());
+        if let Some(PolicyChoice::Specific(specific)) = policy {
+            for version in &specific.versions {
+                if !model.versions.contains(version) {
+                    self.error(
+                        "missing-version",
+                        model,
+                        format!("version_policy selects version {version}, which does not exist"),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Versions Triton will try to load under the version policy.
+    fn served_versions(model: &Model, config: &ModelConfig) -> Vec<i64> {
+        let policy = config
+            .version_policy
+            .as_ref()
+            .and_then(|policy| policy.policy_choice.as_ref());
+
+        match policy {
+            Some(PolicyChoice::All(_)) => model.versions.clone(),
+            Some(PolicyChoice::Specific(specific)) => model
+                .versions
+                .iter()
+                .copied()
+                .filter(|version| specific.versions.contains(version))
+                .collect(),
+            Some(PolicyChoice::Latest(latest)) => {
+                let count = latest.num_versions.max(1) as usize;
+                model.versions.iter().rev().take(count).copied().collect()
+            }
+            None => model.versions.last().copied().into_iter().collect(),
+        }
+    }
+
+    fn check_backend(&mut self, model: &Model, config: &ModelConfig, backends: &Path) {
+        let backend = match (config.backend.as_str(), config.platform.as_str()) {
+            ("", "") => {
+                self.error(
+                    "missing-backend",
+                    model,
+                    "Neither backend nor platform is set".to_string(),
+                );
+                return;
+            }
+            ("", ENSEMBLE) => return,
+            ("", platform) => match platform_backend(platform) {
+                Some(backend) => backend,
+                None => {
+                    self.error(
+                        "unknown-platform",
+                        model,
+                        format!("Unknown platform '{platform}'"),
+                    );
+                    return;
+                }
+            },
+            (backend, _) => backend,
+        };
+
+        if BUILTIN_BACKENDS.contains(&backend) {
+            self.check_artifacts(model, config, backend);
+            return;
+        }
+
+        let directory = backends.join(backend);
+        let library = if config.runtime.ends_with(".so") {
+            directory.join(&config.runtime)
+        } else {
+            directory.join(format!("libtriton_{backend}.so"))
+        };
+        if !library.is_file() && !directory.join("model.py").is_file() {
+            self.error(
+                "missing-backend-library",
+                model,
+                format!(
+                    "Backend '{backend}' has no shared library at {}",
+                    library.display()
+                ),
+            );
+        }
+    }
+
+    fn check_artifacts(&mut self, model: &Model, config: &ModelConfig, backend: &str) {
+        let file_name = if !config.default_model_filename.is_empty() {
+            config.default_model_filename.as_str()
+        } else {
+            match default_model_filename(backend, &config.platform) {
+                Some(file_name) => file_name,
+                None => return,
+            }
+        };
+
+        for version in Self::served_versions(model, config) {
+            let artifact = model.path.join(version.to_string()).join(file_name);
+            if !artifact.exists() {
+                let message = format!("Backend '{backend}' expects {}", artifact.display());
+                self.report(
+                    Severity::Error,
+                    "missing-artifact",
+                    model,
+                    artifact,
+                    message,
+                );
+            }
+        }
+    }
+
+    fn check_tensors(&mut self, model: &Model, config: &ModelConfig) {
+        if config.max_batch_size < 0 {
+            self.error(
+                "invalid-max-batch-size",
+                model,
+                format!(
+                    "max_batch_size must not be negative, got {}",
+                    config.max_batch_size
+                ),
+            );
+        }
+
+        let inputs = config.input.iter().map(|input| {
+            (
+                "input",
+                &input.name,
+                input.data_type,
+                &input.dims,
+                &input.reshape,
+            )
+        });
+        let outputs = config.output.iter().map(|output| {
+            (
+                "output",
+                &output.name,
+                output.data_type,
+                &output.dims,
+                &output.reshape,
+            )
+        });
+
+        let mut seen = HashSet::new();
+        for (kind, name, data_type, dims, reshape) in inputs.chain(outputs) {
+            if name.is_empty() {
+                self.error("invalid-tensor", model, format!("An {kind} has no name"));
+                continue;
+            }
+            if !seen.insert((kind, name)) {
+                self.error(
+                    "duplicate-tensor",
+                    model,
+                    format!("{kind} '{name}' is declared more than once"),
+                );
+            }
+
+            match DataType::try_from(data_type) {
+                Ok(DataType::TypeInvalid) | Err(_) => self.error(
+                    "invalid-datatype",
+                    model,
+                    format!("{kind} '{name}' has invalid data_type {data_type}"),
+                ),
+                Ok(_) => {}
+            }
+
+            self.check_dims(model, kind, name, dims, reshape.as_ref(), config);
+        }
+    }
+
+    fn check_dims(
+        &mut self,
+        model: &Model,
+        kind: &str,
+        name: &str,
+        dims: &[i64],
+        reshape: Option<&ModelTensorReshape>,
+        config: &ModelConfig,
+    ) {
+        // Empty dims are only valid as a batched scalar reshaped to `[]`.
+        if dims.is_empty() && !(reshape.is_some() && config.max_batch_size > 0) {
+            self.error(
+                "invalid-dims",
+                model,
+                format!("{kind} '{name}' has no dims"),
+            );
+        }
+
+        let invalid = |shape: &[i64]| shape.iter().any(|&dim| dim == 0 || dim < -1);
+        if invalid(dims) {
+            self.error(
+                "invalid-dims",
+                model,
+                format!("{kind} '{name}' has dims {dims:?}; each must be positive or -1"),
+            );
+        }
+
+        let Some(reshape) = reshape else {
+            return;
+        };
+        if invalid(&reshape.shape) {
+            self.error(
+                "invalid-reshape",
+                model,
+                format!(
+                    "{kind} '{name}' has reshape {:?}; each dim must be positive or -1",
+                    reshape.shape
+                ),
+            );
+        } else if !dims.contains(&-1)
+            && !reshape.shape.contains(&-1)
+            && !invalid(dims)
+            && dims.iter().product::<i64>() != reshape.shape.iter().product::<i64>()
+        {
+            self.error(
+                "invalid-reshape",
+                model,
+                format!(
+                    "{kind} '{name}' cannot reshape {dims:?} to {:?}",
+                    reshape.shape
+                ),
+            );
+        }
+    }
+
+    fn check_ensemble(
+        &mut self,
+        model: &Model,
+        config: &ModelConfig,
+        models: &HashMap<&str, &Model>,
+    ) {
+        let steps = match &config.scheduling_choice {
+            Some(SchedulingChoice::EnsembleScheduling(ensemble)) if !ensemble.step.is_empty() => {
+                &ensemble.step
+            }
+            _ => {
+                self.error(
+                    "invalid-ensemble",
+                    model,
+                    "Ensemble has no ensemble_scheduling steps".to_string(),
+                );
+                return;
+            }
+        };
+
+        // Ensemble tensor name -> (producer, data type if known).
+        let mut producers: HashMap<&str, (String, Option<i32>)> = config
+            .input
+            .iter()
+            .map(|input| {
+                (
+                    input.name.as_str(),
+                    ("ensemble input".to_string(), Some(input.data_type)),
+                )
+            })
+            .collect();
+        let mut consumed = HashSet::new();
+
+        let step_models: Vec<Option<&ModelConfig>> = steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let Some(step_model) = models.get(step.model_name.as_str()) else {
+                    self.error(
+                        "unknown-model",
+                        model,
+                        format!(
+                            "Step {index} uses model '{}', which is not in the repository",
+                            step.model_name
+                        ),
+                    );
+                    return None;
+                };
+                if step.model_version > 0 && !step_model.versions.contains(&step.model_version) {
+                    self.error(
+                        "missing-version",
+                        model,
+                        format!(
+                            "Step {index} uses version {} of '{}', which does not exist",
+                            step.model_version, step.model_name
+                        ),
+                    );
+                }
+                step_model.config.as_ref()
+            })
+            .collect();
+
+        for (index, (step, step_config)) in steps.iter().zip(&step_models).enumerate() {
+            for (output, tensor) in sorted(&step.output_map) {
+                let data_type = match step_config {
+                    Some(step_config) => {
+                        match step_config.output.iter().find(|o| &o.name == output) {
+                            Some(output) => Some(output.data_type),
+                            None => {
+                                self.error(
+                                    "unknown-tensor",
+                                    model,
+                                    format!(
+                                        "Step {index}: model '{}' has no output '{output}'",
+                                        step.model_name
+                                    ),
+                                );
+                                None
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                let producer = format!("step {index} ({})", step.model_name);
+                if let Some((previous, _)) = producers.get(tensor.as_str()) {
+                    self.error(
+                        "duplicate-producer",
+                        model,
+                        format!("Tensor '{tensor}' is produced by both {previous} and {producer}"),
+                    );
+                } else {
+                    producers.insert(tensor, (producer, data_type));
+                }
+            }
+        }
+
+        for (index, (step, step_config)) in steps.iter().zip(&step_models).enumerate() {
+            for (input, tensor) in sorted(&step.input_map) {
+                consumed.insert(tensor.as_str());
+
+                let expected = match step_config {
+                    Some(step_config) => {
+                        match step_config.input.iter().find(|i| &i.name == input) {
+                            Some(input) => Some(input.data_type),
+                            None => {
+                                self.error(
+                                    "unknown-tensor",
+                                    model,
+                                    format!(
+                                        "Step {index}: model '{}' has no input '{input}'",
+                                        step.model_name
+                                    ),
+                                );
+                                None
+                            }
+                        }
+                    }
+                    None => None,
+                };
+
+                self.check_consumer(
+                    model,
+                    &producers,
+                    tensor,
+                    expected,
+                    &format!("step {index} ({}) input '{input}'", step.model_name),
+                );
+            }
+
+            if let Some(step_config) = step_config {
+                for input in &step_config.input {
+                    if !input.optional && !step.input_map.contains_key(&input.name) {
+                        self.error(
+                            "unmapped-input",
+                            model,
+                            format!(
+                                "Step {index}: input '{}' of model '{}' is not in input_map",
+                                input.name, step.model_name
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        for output in &config.output {
+            if config.input.iter().any(|input| input.name == output.name) {
+                self.error(
+                    "unproduced-tensor",
+                    model,
+                    format!(
+                        "Ensemble output '{}' is not produced by any step",
+                        output.name
+                    ),
+                );
+                continue;
+            }
+            self.check_consumer(
+                model,
+                &producers,
+                &output.name,
+                Some(output.data_type),
+                "ensemble output",
+            );
+        }
+
+        for input in &config.input {
+            if !consumed.contains(input.name.as_str()) {
+                self.report(
+                    Severity::Warning,
+                    "unused-tensor",
+                    model,
+                    model.config_path(),
+                    format!("Ensemble input '{}' is not used by any step", input.name),
+                );
+            }
+        }
+
+        self.check_ensemble_order(model, config, steps);
+    }
+
+    fn check_consumer(
+        &mut self,
+        model: &Model,
+        producers: &HashMap<&str, (String, Option<i32>)>,
+        tensor: &str,
+        expected: Option<i32>,
+        consumer: &str,
+    ) {
+        let Some((producer, produced)) = producers.get(tensor) else {
+            self.error(
+                "unproduced-tensor",
+                model,
+                format!("Tensor '{tensor}' used by {consumer} is not produced by any step"),
+            );
+            return;
+        };
+
+        if let (Some(produced), Some(expected)) = (*produced, expected)
+            && produced != expected
+        {
+            self.error(
+                "datatype-mismatch",
+                model,
+                format!(
+                    "Tensor '{tensor}' is {} from {producer} but {consumer} expects {}",
+                    data_type_name(produced),
+                    data_type_name(expected)
+                ),
+            );
+        }
+    }
+
+    /// Reports steps that can never run because their inputs depend on each
+    /// other.
+    fn check_ensemble_order(
+        &mut self,
+        model: &Model,
+        config: &ModelConfig,
+        steps: &[triton_grpc_client::inference::model_ensembling::Step],
+    ) {
+        let produced: HashSet<&str> = steps
+            .iter()
+            .flat_map(|step| step.output_map.values())
+            .map(String::as_str)
+            .chain(config.input.iter().map(|input| input.name.as_str()))
+            .collect();
+        let mut available: HashSet<&str> = config
+            .input
+            .iter()
+            .map(|input| input.name.as_str())
+            .collect();
+        let mut pending: Vec<usize> = (0..steps.len()).collect();
+
+        loop {
+            let ready = pending.iter().position(|&index| {
+                steps[index]
+                    .input_map
+                    .values()
+                    .all(|tensor| available.contains(tensor.as_str()))
+            });
+            let Some(position) = ready else {
+                break;
+            };
+            let index = pending.remove(position);
+            available.extend(steps[index].output_map.values().map(String::as_str));
+        }
+
+        // Steps with unproduced inputs were already reported.
+        let cyclic = pending
+            .into_iter()
+            .filter(|&index| {
+                steps[index]
+                    .input_map
+                    .values()
+                    .all(|tensor| produced.contains(tensor.as_str()))
+            })
+            .map(|index| format!("{} ({})", index, steps[index].model_name))
+            .collect::<Vec<_>>();
+        if !cyclic.is_empty() {
+            self.error(
+                "ensemble-cycle",
+                model,
+                format!("Steps {} depend on each other", cyclic.join(", ")),
+            );
+        }
+    }
+}
+
+fn sorted(map: &HashMap<String, String>) -> BTreeMap<&String, &String> {
+    map.iter().collect()
+}
+
+fn platform_backend(platform: &str) -> Option<&'static str> {
+    match platform {
+        "onnxruntime_onnx" => Some("onnxruntime"),
+        "tensorrt_plan" => Some("tensorrt"),
+        "pytorch_libtorch" => Some("pytorch"),
+        "tensorflow_graphdef" | "tensorflow_savedmodel" => Some("tensorflow"),
+        _ => None,
+    }
+}
+
+fn default_model_filename(backend: &str, platform: &str) -> Option<&'static str> {
+    match (backend, platform) {
+        ("onnxruntime", _) => Some("model.onnx"),
+        ("tensorrt", _) => Some("model.plan"),
+        ("pytorch", _) => Some("model.pt"),
+        ("python", _) => Some("model.py"),
+        ("openvino", _) => Some("model.xml"),
+        ("tensorflow", "tensorflow_savedmodel") => Some("model.savedmodel"),
+        ("tensorflow", "tensorflow_graphdef") => Some("model.graphdef"),
+        _ => None,
+    }
+}
+
+fn data_type_name(data_type: i32) -> String {
+    DataType::try_from(data_type)
+        .map(|data_type| data_type.as_str_name().to_string())
+        .unwrap_or_else(|_| data_type.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A scratch model repository, removed when dropped.
+    struct Fixture {
+        root: PathBuf,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let root = std::env::temp_dir().join(format!(
+                "triton-repo-check-{}-{}",
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            ));
+            std::fs::create_dir_all(root.join("models")).unwrap();
+            std::fs::create_dir_all(root.join("backends")).unwrap();
+            Self { root }
+        }
+
+        /// Writes `contents` to `path` below the root, creating directories.
+        fn file(&self, path: &str, contents: &str) -> &Self {
+            let path = self.root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+            self
+        }
+
+        fn codes(&self) -> Vec<&'static str> {
+            let report =
+                check_repository(&self.root.join("models"), &self.root.join("backends")).unwrap();
+            report
+                .diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.code)
+                .collect()
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    /// A python model with one input and one output, and its `model.py`.
+    fn python_model(fixture: &Fixture, name: &str, input: &str, output: &str, data_type: &str) {
+        fixture
+            .file(
+                &format!("models/{name}/config.pbtxt"),
+                &format!(
+                    "backend: \"python\"\n\
+                     input [{{ name: \"{input}\" data_type: {data_type} dims: [ 1 ] }}]\n\
+                     output [{{ name: \"{output}\" data_type: {data_type} dims: [ 1 ] }}]\n"
+                ),
+            )
+            .file(&format!("models/{name}/1/model.py"), "");
+    }
+
+    #[test]
+    fn valid_repository() {
+        let fixture = Fixture::new();
+        python_model(&fixture, "identity", "x", "y", "TYPE_FP32");
+        assert_eq!(fixture.codes(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn missing_artifact() {
+        let fixture = Fixture::new();
+        fixture
+            .file(
+                "models/onnx/config.pbtxt",
+                "platform: \"onnxruntime_onnx\"\n\
+                 input [{ name: \"x\" data_type: TYPE_FP32 dims: [ 1 ] }]\n\
+                 output [{ name: \"y\" data_type: TYPE_FP32 dims: [ 1 ] }]\n",
+            )
+            .file("models/onnx/1/.keep", "");
+        assert_eq!(fixture.codes(), ["missing-artifact"]);
+
+        fixture.file("models/onnx/1/model.onnx", "");
+        assert_eq!(fixture.codes(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn missing_backend_library() {
+        let fixture = Fixture::new();
+        fixture
+            .file(
+                "models/custom/config.pbtxt",
+                "backend: \"custom\"\n\
+                 input [{ name: \"x\" data_type: TYPE_FP32 dims: [ 1 ] }]\n\
+                 output [{ name: \"y\" data_type: TYPE_FP32 dims: [ 1 ] }]\n",
+            )
+            .file("models/custom/1/.keep", "");
+        assert_eq!(fixture.codes(), ["missing-backend-library"]);
+
+        fixture.file("backends/custom/libtriton_custom.so", "");
+        assert_eq!(fixture.codes(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn ensemble_cycle() {
+        let fixture = Fixture::new();
+        python_model(&fixture, "a", "in", "out", "TYPE_FP32");
+        python_model(&fixture, "b", "in", "out", "TYPE_FP32");
+        fixture.file(
+            "models/pipeline/config.pbtxt",
+            "platform: \"ensemble\"\n\
+             input [{ name: \"x\" data_type: TYPE_FP32 dims: [ 1 ] }]\n\
+             output [{ name: \"y\" data_type: TYPE_FP32 dims: [ 1 ] }]\n\
+             ensemble_scheduling { step [\n\
+               { model_name: \"a\" input_map { key: \"in\" value: \"t2\" }\n\
+                 output_map { key: \"out\" value: \"t1\" } },\n\
+               { model_name: \"b\" input_map { key: \"in\" value: \"t1\" }\n\
+                 output_map { key: \"out\" value: \"t2\" } }\n\
+             ] }\n",
+        );
+        fixture.file("models/pipeline/1/.keep", "");
+
+        let codes = fixture.codes();
+        assert!(codes.contains(&"ensemble-cycle"), "{codes:?}");
+    }
+
+    #[test]
+    fn ensemble_datatype_mismatch() {
+        let fixture = Fixture::new();
+        python_model(&fixture, "a", "in", "out", "TYPE_INT32");
+        fixture.file(
+            "models/pipeline/config.pbtxt",
+            "platform: \"ensemble\"\n\
+             input [{ name: \"x\" data_type: TYPE_FP32 dims: [ 1 ] }]\n\
+             output [{ name: \"y\" data_type: TYPE_INT32 dims: [ 1 ] }]\n\
+             ensemble_scheduling { step [\n\
+               { model_name: \"a\" input_map { key: \"in\" value: \"x\" }\n\
+                 output_map { key: \"out\" value: \"y\" } }\n\
+             ] }\n",
+        );
+        fixture.file("models/pipeline/1/.keep", "");
+
+        assert_eq!(fixture.codes(), ["datatype-mismatch"]);
+    }
+}
@@ -0,0 +1,62 @@
+#[path = "check.rs"]
+mod check;
+#[path = "report.rs"]
+mod report;
+
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Validates a Triton model repository before tritonserver loads it.
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// Model repository to check.
+    #[arg(default_value = "models")]
+    repository: PathBuf,
+
+    /// Directory holding custom backends as `<name>/libtriton_<name>.so`.
+    #[arg(long, default_value = "backends")]
+    backends: PathBuf,
+
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Fail on warnings as well as errors.
+    #[arg(long)]
+    deny_warnings: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let report = match check::check_repository(&args.repository, &args.backends) {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("{}: {}", args.repository.display(), error);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    let written = match args.format {
+        Format::Text => report.write_text(&mut stdout),
+        Format::Json => report.write_json(&mut stdout),
+    };
+    if let Err(error) = written {
+        eprintln!("{error}");
+        return ExitCode::from(2);
+    }
+
+    if report.errors > 0 || (args.deny_warnings && report.warnings > 0) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,87 @@
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable identifier of the check, e.g. `missing-artifact`.
+    pub code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub repository: PathBuf,
+    pub models: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    pub fn new(repository: &Path, models: usize, diagnostics: Vec<Diagnostic>) -> Self {
+        let count = |severity| {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == severity)
+                .count()
+        };
+
+        Self {
+            repository: repository.to_path_buf(),
+            models,
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            diagnostics,
+        }
+    }
+
+    /// One `path[:line:column]: severity[code]: message` line per diagnostic
+    /// followed by a summary.
+    pub fn write_text(&self, out: &mut impl Write) -> io::Result<()> {
+        for diagnostic in &self.diagnostics {
+            write!(out, "{}", diagnostic.path.display())?;
+            if let (Some(line), Some(column)) = (diagnostic.line, diagnostic.column) {
+                write!(out, ":{line}:{column}")?;
+            }
+            let severity = match diagnostic.severity {
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            };
+            writeln!(
+                out,
+                ": {severity}[{}]: {}",
+                diagnostic.code, diagnostic.message
+            )?;
+        }
+
+        writeln!(
+            out,
+            "checked {} model(s) in {}: {} error(s), {} warning(s)",
+            self.models,
+            self.repository.display(),
+            self.errors,
+            self.warnings
+        )
+    }
+
+    pub fn write_json(&self, out: &mut impl Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, self)?;
+        writeln!(out)
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Tensor element types known to Triton.
+///
+/// The discriminants match both `TRITONSERVER_DataType` and the `DataType`
+/// enum of `model_config.proto`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum DataType {
+    Invalid = 0,
+    Bool = 1,
+    Uint8 = 2,
+    Uint16 = 3,
+    Uint32 = 4,
+    Uint64 = 5,
+    Int8 = 6,
+    Int16 = 7,
+    Int32 = 8,
+    Int64 = 9,
+    Fp16 = 10,
+    Fp32 = 11,
+    Fp64 = 12,
+    Bytes = 13,
+    Bf16 = 14,
+}
+
+impl DataType {
+    pub const ALL: [DataType; 14] = [
+        Self::Bool,
+        Self::Uint8,
+        Self::Uint16,
+        Self::Uint32,
+        Self::Uint64,
+        Self::Int8,
+        Self::Int16,
+        Self::Int32,
+        Self::Int64,
+        Self::Fp16,
+        Self::Fp32,
+        Self::Fp64,
+        Self::Bytes,
+        Self::Bf16,
+    ];
+
+    /// Converts a `TRITONSERVER_DataType` or `model_config.proto` value.
+    /// Unknown values map to [`DataType::Invalid`].
+    pub fn from_raw(value: u32) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|datatype| *datatype as u32 == value)
+            .unwrap_or(Self::Invalid)
+    }
+
+    /// The `TRITONSERVER_DataType` / `model_config.proto` value.
+    pub fn as_raw(self) -> u32 {
+        self as u32
+    }
+
+    /// The KServe v2 protocol name, e.g. `"FP32"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Invalid => "INVALID",
+            Self::Bool => "BOOL",
+            Self::Uint8 => "UINT8",
+            Self::Uint16 => "UINT16",
+            Self::Uint32 => "UINT32",
+            Self::Uint64 => "UINT64",
+            Self::Int8 => "INT8",
+            Self::Int16 => "INT16",
+            Self::Int32 => "INT32",
+            Self::Int64 => "INT64",
+            Self::Fp16 => "FP16",
+            Self::Fp32 => "FP32",
+            Self::Fp64 => "FP64",
+            Self::Bytes => "BYTES",
+            Self::Bf16 => "BF16",
+        }
+    }
+
+    /// The name used in `config.pbtxt`, e.g. `"TYPE_FP32"`. Note that BYTES
+    /// is called `TYPE_STRING` there.
+    pub fn config_name(self) -> &'static str {
+        match self {
+            Self::Invalid => "TYPE_INVALID",
+            Self::Bool => "TYPE_BOOL",
+            Self::Uint8 => "TYPE_UINT8",
+            Self::Uint16 => "TYPE_UINT16",
+            Self::Uint32 => "TYPE_UINT32",
+            Self::Uint64 => "TYPE_UINT64",
+            Self::Int8 => "TYPE_INT8",
+            Self::Int16 => "TYPE_INT16",
+            Self::Int32 => "TYPE_INT32",
+            Self::Int64 => "TYPE_INT64",
+            Self::Fp16 => "TYPE_FP16",
+            Self::Fp32 => "TYPE_FP32",
+            Self::Fp64 => "TYPE_FP64",
+            Self::Bytes => "TYPE_STRING",
+            Self::Bf16 => "TYPE_BF16",
+        }
+    }
+
+    pub fn from_config_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|datatype| datatype.config_name() == name)
+    }
+
+    /// Size of one element in bytes, or `None` for variable-sized BYTES.
+    pub fn byte_size(self) -> Option<usize> {
+        match self {
+            Self::Invalid | Self::Bytes => None,
+            Self::Bool | Self::Uint8 | Self::Int8 => Some(1),
+            Self::Uint16 | Self::Int16 | Self::Fp16 | Self::Bf16 => Some(2),
+            Self::Uint32 | Self::Int32 | Self::Fp32 => Some(4),
+            Self::Uint64 | Self::Int64 | Self::Fp64 => Some(8),
+        }
+    }
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDataTypeError(String);
+
+impl fmt::Display for ParseDataTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown datatype: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseDataTypeError {}
+
+impl FromStr for DataType {
+    type Err = ParseDataTypeError;
+
+    /// Parses a KServe name such as `"FP32"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|datatype| datatype.as_str() == s)
+            .ok_or_else(|| ParseDataTypeError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_values_round_trip() {
+        for datatype in DataType::ALL {
+            assert_eq!(DataType::from_raw(datatype.as_raw()), datatype);
+        }
+        assert_eq!(DataType::from_raw(0), DataType::Invalid);
+        assert_eq!(DataType::from_raw(15), DataType::Invalid);
+        assert_eq!(DataType::Bf16.as_raw(), 14);
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for datatype in DataType::ALL {
+            assert_eq!(datatype.as_str().parse(), Ok(datatype));
+            assert_eq!(datatype.to_string(), datatype.as_str());
+            assert_eq!(
+                DataType::from_config_name(datatype.config_name()),
+                Some(datatype)
+            );
+        }
+        assert_eq!(DataType::Bytes.config_name(), "TYPE_STRING");
+        assert_eq!(DataType::from_config_name("TYPE_BYTES"), None);
+        assert!("fp32".parse::<DataType>().is_err());
+        assert!("INVALID".parse::<DataType>().is_err());
+    }
+
+    #[test]
+    fn names_are_distinct() {
+        for (index, datatype) in DataType::ALL.into_iter().enumerate() {
+            for other in &DataType::ALL[index + 1..] {
+                assert_ne!(datatype.as_str(), other.as_str());
+                assert_ne!(datatype.config_name(), other.config_name());
+            }
+        }
+    }
+
+    #[test]
+    fn byte_sizes() {
+        let sizes = DataType::ALL.map(DataType::byte_size);
+        assert_eq!(
+            sizes,
+            [
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(4),
+                Some(8),
+                Some(1),
+                Some(2),
+                Some(4),
+                Some(8),
+                Some(2),
+                Some(4),
+                Some(8),
+                None,
+                Some(2),
+            ]
+        );
+        assert_eq!(DataType::Invalid.byte_size(), None);
+    }
+}
@@ -0,0 +1,92 @@
+use half::slice::HalfFloatSliceExt;
+
+pub use half::{bf16, f16};
+
+/// Converts FP32 values to FP16, rounding to nearest.
+pub fn f32_to_f16(values: &[f32]) -> Vec<f16> {
+    let mut out = vec![f16::ZERO; values.len()];
+    out.convert_from_f32_slice(values);
+    out
+}
+
+pub fn f16_to_f32(values: &[f16]) -> Vec<f32> {
+    values.to_f32_vec()
+}
+
+/// Converts FP32 values to BF16, rounding to nearest.
+pub fn f32_to_bf16(values: &[f32]) -> Vec<bf16> {
+    let mut out = vec![bf16::ZERO; values.len()];
+    out.convert_from_f32_slice(values);
+    out
+}
+
+pub fn bf16_to_f32(values: &[bf16]) -> Vec<f32> {
+    values.to_f32_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TensorValue;
+
+    /// NaN, both infinities, both zeros, the smallest and largest
+    /// subnormals, and ordinary values.
+    const F16_BITS: [u16; 9] = [
+        0x7e00, 0x7c00, 0xfc00, 0x0000, 0x8000, 0x0001, 0x03ff, 0x3c00, 0xc500,
+    ];
+    const BF16_BITS: [u16; 9] = [
+        0x7fc0, 0x7f80, 0xff80, 0x0000, 0x8000, 0x0001, 0x007f, 0x3f80, 0xc0a0,
+    ];
+
+    #[test]
+    fn f16_bytes_round_trip() {
+        let values = F16_BITS.map(f16::from_bits).to_vec();
+        let bytes = values.encode();
+        assert_eq!(&bytes[..4], [0x00, 0x7e, 0x00, 0x7c]);
+
+        let decoded = Vec::<f16>::decode(&bytes).unwrap();
+        assert_eq!(
+            decoded
+                .iter()
+                .map(|value| value.to_bits())
+                .collect::<Vec<_>>(),
+            F16_BITS
+        );
+        assert!(decoded[0].is_nan());
+        assert!(Vec::<f16>::decode(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn bf16_bytes_round_trip() {
+        let values = BF16_BITS.map(bf16::from_bits).to_vec();
+        let bytes = values.encode();
+        assert_eq!(&bytes[..4], [0xc0, 0x7f, 0x80, 0x7f]);
+
+        let decoded = Vec::<bf16>::decode(&bytes).unwrap();
+        assert_eq!(
+            decoded
+                .iter()
+                .map(|value| value.to_bits())
+                .collect::<Vec<_>>(),
+            BF16_BITS
+        );
+        assert!(decoded[0].is_nan());
+        assert!(Vec::<bf16>::decode(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn f32_conversions() {
+        let smallest_f16 = 2f32.powi(-24);
+        let values = [f32::INFINITY, f32::NEG_INFINITY, smallest_f16, 1.0, -5.0];
+
+        assert_eq!(f16_to_f32(&f32_to_f16(&values)), values);
+        assert!(f16_to_f32(&f32_to_f16(&[f32::NAN]))[0].is_nan());
+        assert_eq!(f32_to_f16(&[smallest_f16 / 4.0])[0], f16::ZERO);
+        assert_eq!(f32_to_f16(&[1e6])[0], f16::INFINITY);
+
+        let smallest_bf16 = f32::from_bits(0x0001_0000);
+        let values = [f32::INFINITY, f32::NEG_INFINITY, smallest_bf16, 1.0, -5.0];
+        assert_eq!(bf16_to_f32(&f32_to_bf16(&values)), values);
+        assert!(bf16_to_f32(&f32_to_bf16(&[f32::NAN]))[0].is_nan());
+    }
+}
@@ -0,0 +1,14 @@
+#[path = "datatype.rs"]
+pub mod datatype;
+#[cfg(feature = "half")]
+#[path = "float16.rs"]
+pub mod float16;
+#[path = "tensors.rs"]
+pub mod tensors;
+
+pub use datatype::*;
+#[cfg(feature = "half")]
+pub use float16::*;
+pub use tensors::*;
+#[cfg(feature = "derive")]
+pub use triton_derive::{TritonInputs, TritonOutputs};
@@ -0,0 +1,350 @@
+use crate::DataType;
+use std::borrow::Cow;
+use std::fmt;
+
+/// Name, datatype and shape of one model input or output. A `-1` dimension
+/// is resolved from the element count when the tensor is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TensorSpec {
+    pub name: &'static str,
+    pub datatype: DataType,
+    pub shape: &'static [i64],
+}
+
+impl TensorSpec {
+    /// The concrete shape of this tensor when it holds `len` elements.
+    pub fn resolve_shape(&self, len: usize) -> Result<Vec<i64>, TensorError> {
+        let len = len as i64;
+        let known: i64 = self.shape.iter().filter(|&&dim| dim >= 0).product();
+        let dynamic = self.shape.iter().filter(|&&dim| dim < 0).count();
+
+        match dynamic {
+            0 if known == len => Ok(self.shape.to_vec()),
+            // With a zero dimension any size fits, so an empty tensor takes
+            // the smallest one.
+            1 if known == 0 && len == 0 => Ok(self.shape.iter().map(|&dim| dim.max(0)).collect()),
+            1 if known > 0 && len % known == 0 => Ok(self
+                .shape
+                .iter()
+                .map(|&dim| if dim < 0 { len / known } else { dim })
+                .collect()),
+            0 | 1 => Err(TensorError::new(format!(
+                "Tensor '{}' with {} elements does not fit shape {:?}",
+                self.name, len, self.shape
+            ))),
+            _ => Err(TensorError::new(format!(
+                "Tensor '{}' has shape {:?} with more than one -1 dimension",
+                self.name, self.shape
+            ))),
+        }
+    }
+
+    /// Decodes this tensor from `source`, failing if its element count does
+    /// not fit the declared shape.
+    pub fn read<T: TensorValue, S: TensorSource + ?Sized>(
+        &self,
+        source: &S,
+    ) -> Result<T, TensorError> {
+        let value = T::decode(&source.tensor_bytes(self)?)
+            .map_err(|error| TensorError::new(format!("Tensor '{}': {}", self.name, error)))?;
+        self.resolve_shape(value.element_count())?;
+        Ok(value)
+    }
+
+    pub fn write<T: TensorValue, S: TensorSink + ?Sized>(
+        &self,
+        sink: &mut S,
+        value: &T,
+    ) -> Result<(), TensorError> {
+        let shape = self.resolve_shape(value.element_count())?;
+        sink.write_tensor(self, &shape, value.encode())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TensorError(String);
+
+impl TensorError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl fmt::Display for TensorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TensorError {}
+
+/// A Rust type that can be carried in a single tensor, encoded the way
+/// Triton lays out raw tensor contents.
+pub trait TensorValue: Sized {
+    const DATATYPE: DataType;
+
+    fn element_count(&self) -> usize;
+
+    fn encode(&self) -> Vec<u8>;
+
+    fn decode(bytes: &[u8]) -> Result<Self, TensorError>;
+}
+
+/// Something tensors can be read from by name, e.g. a backend request or an
+/// inference response.
+pub trait TensorSource {
+    fn tensor_bytes(&self, spec: &TensorSpec) -> Result<Cow<'_, [u8]>, TensorError>;
+}
+
+/// Something tensors can be written into, e.g. a backend response or an
+/// inference request.
+pub trait TensorSink {
+    fn write_tensor(
+        &mut self,
+        spec: &TensorSpec,
+        shape: &[i64],
+        bytes: Vec<u8>,
+    ) -> Result<(), TensorError>;
+}
+
+/// A struct whose fields are the inputs of a model. Usually derived.
+pub trait TritonInputs: Sized {
+    const INPUTS: &'static [TensorSpec];
+
+    fn read_inputs<S: TensorSource + ?Sized>(source: &S) -> Result<Self, TensorError>;
+
+    fn write_inputs<S: TensorSink + ?Sized>(&self, sink: &mut S) -> Result<(), TensorError>;
+}
+
+/// A struct whose fields are the outputs of a model. Usually derived.
+pub trait TritonOutputs: Sized {
+    const OUTPUTS: &'static [TensorSpec];
+
+    fn read_outputs<S: TensorSource + ?Sized>(source: &S) -> Result<Self, TensorError>;
+
+    fn write_outputs<S: TensorSink + ?Sized>(&self, sink: &mut S) -> Result<(), TensorError>;
+}
+
+fn single<T>(mut values: Vec<T>) -> Result<T, TensorError> {
+    match values.len() {
+        1 => Ok(values.remove(0)),
+        len => Err(TensorError::new(format!(
+            "Expected a single element, found {len}"
+        ))),
+    }
+}
+
+macro_rules! numeric_value {
+    ($ty:ty, $datatype:ident) => {
+        impl TensorValue for Vec<$ty> {
+            const DATATYPE: DataType = DataType::$datatype;
+
+            fn element_count(&self) -> usize {
+                self.len()
+            }
+
+            fn encode(&self) -> Vec<u8> {
+                self.iter().flat_map(|value| value.to_le_bytes()).collect()
+            }
+
+            fn decode(bytes: &[u8]) -> Result<Self, TensorError> {
+                let size = size_of::<$ty>();
+                if !bytes.len().is_multiple_of(size) {
+                    return Err(TensorError::new(format!(
+                        "{} bytes is not a whole number of {} elements",
+                        bytes.len(),
+                        DataType::$datatype
+                    )));
+                }
+
+                Ok(bytes
+                    .chunks_exact(size)
+                    .map(|chunk| <$ty>::from_le_bytes(chunk.try_into().unwrap()))
+                    .collect())
+            }
+        }
+
+        impl TensorValue for $ty {
+            const DATATYPE: DataType = DataType::$datatype;
+
+            fn element_count(&self) -> usize {
+                1
+            }
+
+            fn encode(&self) -> Vec<u8> {
+                self.to_le_bytes().to_vec()
+            }
+
+            fn decode(bytes: &[u8]) -> Result<Self, TensorError> {
+                single(Vec::<$ty>::decode(bytes)?)
+            }
+        }
+    };
+}
+
+numeric_value!(u8, Uint8);
+numeric_value!(u16, Uint16);
+numeric_value!(u32, Uint32);
+numeric_value!(u64, Uint64);
+numeric_value!(i8, Int8);
+numeric_value!(i16, Int16);
+numeric_value!(i32, Int32);
+numeric_value!(i64, Int64);
+numeric_value!(f32, Fp32);
+numeric_value!(f64, Fp64);
+#[cfg(feature = "half")]
+numeric_value!(half::f16, Fp16);
+#[cfg(feature = "half")]
+numeric_value!(half::bf16, Bf16);
+
+/// BOOL elements take one byte each.
+impl TensorValue for Vec<bool> {
+    const DATATYPE: DataType = DataType::Bool;
+
+    fn element_count(&self) -> usize {
+        self.len()
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.iter().map(|&value| u8::from(value)).collect()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, TensorError> {
+        Ok(bytes.iter().map(|&byte| byte != 0).collect())
+    }
+}
+
+impl TensorValue for bool {
+    const DATATYPE: DataType = DataType::Bool;
+
+    fn element_count(&self) -> usize {
+        1
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![u8::from(*self)]
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, TensorError> {
+        single(Vec::<bool>::decode(bytes)?)
+    }
+}
+
+/// BYTES elements are each prefixed with their length as a little-endian
+/// `u32`.
+impl TensorValue for Vec<String> {
+    const DATATYPE: DataType = DataType::Bytes;
+
+    fn element_count(&self) -> usize {
+        self.len()
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for value in self {
+            bytes.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(value.as_bytes());
+        }
+        bytes
+    }
+
+    fn decode(mut bytes: &[u8]) -> Result<Self, TensorError> {
+        let mut values = Vec::new();
+        while !bytes.is_empty() {
+            let Some((len, rest)) = bytes.split_first_chunk::<4>() else {
+                return Err(TensorError::new("Truncated BYTES length prefix"));
+            };
+            let len = u32::from_le_bytes(*len) as usize;
+            if rest.len() < len {
+                return Err(TensorError::new("Truncated BYTES element"));
+            }
+
+            let (value, rest) = rest.split_at(len);
+            values.push(String::from_utf8_lossy(value).into_owned());
+            bytes = rest;
+        }
+
+        Ok(values)
+    }
+}
+
+impl TensorValue for String {
+    const DATATYPE: DataType = DataType::Bytes;
+
+    fn element_count(&self) -> usize {
+        1
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = (self.len() as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(self.as_bytes());
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, TensorError> {
+        single(Vec::<String>::decode(bytes)?)
+    }
+}
+
+/// Number of elements described by `shape`, or `None` if a dimension is
+/// negative or the count overflows.
+pub fn element_count(shape: &[i64]) -> Option<usize> {
+    shape.iter().try_fold(1usize, |count, &dim| {
+        count.checked_mul(usize::try_from(dim).ok()?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(shape: &'static [i64]) -> TensorSpec {
+        TensorSpec {
+            name: "INPUT",
+            datatype: DataType::Fp32,
+            shape,
+        }
+    }
+
+    #[test]
+    fn element_counts() {
+        assert_eq!(element_count(&[]), Some(1));
+        assert_eq!(element_count(&[2, 3, 4]), Some(24));
+        assert_eq!(element_count(&[0, 5]), Some(0));
+        assert_eq!(element_count(&[-1, 4]), None);
+        assert_eq!(element_count(&[i64::MAX, i64::MAX]), None);
+    }
+
+    #[test]
+    fn fixed_shapes() {
+        assert_eq!(spec(&[2, 3]).resolve_shape(6).unwrap(), [2, 3]);
+        assert_eq!(spec(&[]).resolve_shape(1).unwrap(), [] as [i64; 0]);
+        assert_eq!(spec(&[0, 3]).resolve_shape(0).unwrap(), [0, 3]);
+        assert!(spec(&[2, 3]).resolve_shape(5).is_err());
+    }
+
+    #[test]
+    fn one_dynamic_dimension() {
+        assert_eq!(spec(&[-1]).resolve_shape(5).unwrap(), [5]);
+        assert_eq!(spec(&[-1, 3]).resolve_shape(6).unwrap(), [2, 3]);
+        assert_eq!(spec(&[-1, 3]).resolve_shape(0).unwrap(), [0, 3]);
+        assert!(spec(&[-1, 3]).resolve_shape(7).is_err());
+    }
+
+    #[test]
+    fn empty_tensor_with_a_zero_dimension() {
+        assert_eq!(spec(&[-1, 0]).resolve_shape(0).unwrap(), [0, 0]);
+        assert_eq!(spec(&[0, -1, 4]).resolve_shape(0).unwrap(), [0, 0, 4]);
+        assert!(spec(&[-1, 0]).resolve_shape(1).is_err());
+    }
+
+    #[test]
+    fn several_dynamic_dimensions() {
+        let error = spec(&[-1, -1]).resolve_shape(4).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Tensor 'INPUT' has shape [-1, -1] with more than one -1 dimension"
+        );
+        assert!(spec(&[-1, 2, -1]).resolve_shape(0).is_err());
+    }
+}